@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Prints the JSON schema of the export documents, it's kept in info/export-schema.json:
+//!
+//! ```
+//! cargo run --example export_schema --features schema > info/export-schema.json
+//! ```
+
+use oboete::models::ExportDocument;
+
+fn main() {
+    let schema = schemars::schema_for!(ExportDocument);
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
@@ -8,10 +8,21 @@ about-anki-importing = ABOUT ANKI IMPORTING
 <#-- Application MenuBar -->
 file = File
 new-studyset = New StudySet
+import-anki-package = Import Anki Package
+import-studysets = Import StudySets
+export-studyset = Export StudySet
+export-all-studysets = Export All StudySets
+backup-database = Back Up Database
 edit = Edit
 rename-studyset = Rename StudySet
+duplicate-studyset = Duplicate StudySet
+reset-studyset-progress = Reset StudySet Progress
 delete-studyset = Delete StudySet
 view = View
+all-flashcards = All Flashcards
+study-all-due = Study All Due
+settings = Settings
+keyboard-shortcuts = Keyboard Shortcuts
 about = About
 
 <#-- Page Headers -->
@@ -20,50 +31,310 @@ flashcards = Flashcards
 study = Study
 new = New
 options = Options
+due-count = {$due} due
+study-streak = {$days} day streak
+studyset-nav-label = {$name} ({$count})
 
 <#-- New StudySet Dialog -->
 create-studyset = Create StudySet
 studyset-name = StudySet Name
+name-empty = The name can't be empty
+name-taken = "{$name}" is already taken
 
 <#-- Delete StudySet Dialog -->
 confirm-delete = Confirm Delete
 ok = Ok
 
+<#-- Delete Flashcard Dialog -->
+delete-flashcard = Delete Flashcard
+confirm-delete-flashcard = Are you sure you want to delete "{$front}"?
+delete = Delete
+
+<#-- Duplicate Flashcard Dialog -->
+duplicate-flashcard = Duplicate Flashcard
+confirm-duplicate-flashcard = The folder already has a flashcard with "{$front}" as its front, do you want to add it anyway?
+add-anyway = Add Anyway
+
+<#-- Discard Flashcard Edit Dialog -->
+discard-changes = Discard Changes
+confirm-discard-changes = The changes made to the flashcard will be lost, are you sure?
+discard = Discard
+
+<#-- Move Flashcard Dialog -->
+move-flashcard = Move Flashcard
+move-flashcard-destination = Choose the folder the flashcard will be moved to
+move = Move
+
+<#-- Merge Folder Dialog -->
+merge-folder = Merge Folder
+merge-folder-destination = Choose the folder its flashcards will be moved to, this folder will be deleted
+merge = Merge
+
+<#-- Error Dialog -->
+error-database = The database couldn't be used: {$error}
+error-file = The file couldn't be used: {$error}
+error-newer-database = The database was created by a newer version of Oboete (schema version {$version}), update the app to open it
+error-flashcard-not-found = The flashcard doesn't exist
+error-folder-not-found = The folder doesn't exist
+
+<#-- Backup Dialog -->
+backup-created = The backup has been saved to {$path}
+
 <#-- New Folder Dialog -->
 create-folder = Create Folder
 rename-folder = Rename Folder
 folder-name = Folder Name
+flashcard-count = {$count} flashcards
 
-<#-- Folder Details Context Page -->
-folder-details = Folder Details
+<#-- Folder Outline Context Page -->
+folder-outline = Outline
+folder-outline-title = Create Folders from an Outline
+folder-outline-description = Each line becomes a folder of the studyset, blank lines and names already taken are skipped
+folder-outline-result = {$created} folders created
 
 <#-- Flashcards Page -->
 ok-status = Ok
 good-status = Good
 bad-status = Bad
+new-flashcard-badge = New
+study-front-first = Front First
+study-back-first = Back First
+study-random-side = Random Side
+study-filter-all-due = Due Flashcards
+study-filter-only-bad = Only Bad
+study-filter-unseen = Only New
+study-filter-mixed = Bad & New
+study-filter-favorites = Only Favorites
+study-order-random = Random Order
+study-order-sequential = Creation Order
+study-order-oldest-first = Oldest Due First
+study-mode-flashcards = Flashcards
+study-mode-multiple-choice = Multiple Choice
+study-mode-typed = Typed Answer
+typed-answer-placeholder = Type the answer
+typed-answer-check = Check
+typed-answer-correct = Correct:
+typed-answer-incorrect = Incorrect, the answer is:
+typed-answer-next = Next
+empty-flashcard = [Empty]
+no-flashcards-due = No flashcards left to study for now
+no-flashcards-filter = No flashcards match the study filter
+no-flashcards-to-study = No flashcards to study
+back-to-flashcards = Back to Flashcards
+search-flashcards = Search flashcards
+select = Select
+select-all = Select All
+clear-selection = Clear Selection
+delete-selected = Delete Selected
+selected-flashcards = {$selected} selected
+flip-flashcard = Flip Flashcard
+add-favorite = Add to Favorites
+remove-favorite = Remove from Favorites
+study-shortcuts-hint = 0: {$again} · 1 or J: {$bad} · 2 or K: {$ok} · 3 or L: {$good} · 4: {$easy} · Space: Flip
+grade-again = Again
+grade-easy = Easy
+folder-stats = {$good} good · {$ok} ok · {$bad} bad · {$unseen} unseen · {$total} total
+study-progress = {$current} of {$total}
+list-page = Page {$current} of {$total}
+sort-created = Creation Order
+sort-front = Front A–Z
+sort-back = Back A–Z
+sort-status = Status
+sort-recently-added = Recently Added
+session-complete = Session complete, {$studied} flashcards studied
+session-average-response = Average answer time: {$seconds} s
+session-folder-studied = {$folder}: {$studied} studied
+study-again = Study Again
+play-audio = Play Audio
+undo-grade = Undo Last Grade
+browse-mode = Browse
+grade-mode = Grade
+previous-flashcard = Previous
+next-flashcard = Next
+add-flashcard = Add Flashcard
+edit-flashcard = Edit Flashcard
+flashcard-front-shown = Front: {$content}
+flashcard-back-shown = Back: {$content}
+grade-flashcard = Grade as {$grade}
 
 <#-- Flashcard Create/Edit Context Page -->
 flashcard-front-title = Flashcard Front
 flashcard-back-title = Flashcard Back
 flashcard-front-placeholder = Front Content
 flashcard-back-placeholder = Back Content
+flashcard-tags-title = Tags
+flashcard-tags-placeholder = Comma separated tags
+flashcard-cloze = Cloze, the answers of the front go between {"{{"} and {"}}"}
+flashcard-image-title = Image
+flashcard-image-none = No image selected
+flashcard-image-select-button = Select Image
+flashcard-image-remove-button = Remove Image
+flashcard-audio-title = Audio
+flashcard-audio-none = No audio selected
+flashcard-audio-select-button = Select Audio
+flashcard-audio-remove-button = Remove Audio
 create = Create
 reset-flashcard-title = Reset Flashcard Status
 reset-flashcard-button = Reset
+flashcard-front-empty = The front of the flashcard can't be empty
+flashcard-cloze-empty = The front of a cloze flashcard needs an answer between {"{{"} and {"}}"}
+flashcard-too-long = The front and back of the flashcard can't be longer than {$limit} characters
+
+<#-- Flashcard History Context Page -->
+flashcard-history = Flashcard History
+flashcard-history-reviews = Studied {$reviews} times
+flashcard-history-average-response = Average answer time: {$seconds} s
+flashcard-history-grades = Past Grades
+flashcard-history-empty = This flashcard hasn't been studied yet
 
 <#-- Flashcard Options Context Page -->
 flashcard-options = Flashcard Options
-folder-import = Import
+folder-study-preferences = Study Preferences
+study-mode-title = Mode
+study-direction-title = Direction
+study-filter-title = Flashcards Studied
+study-order-title = Order
+folder-import = Import from Quizlet or Text
 import-between-term-title = Between Term & Definition
-import-between-term-placeholder = Character Between Term & Definition
+import-between-term-placeholder = Character Between Term & Definition, \t for tab
 import-between-cards-title = Between Cards
-import-between-cards-placeholder = Character Between Cards
+import-between-cards-placeholder = Character Between Cards, \n for new line
 import-content-title = Import Content
 import-content-placeholder = Content to Import
 import-button = Import
+paste-import-title = Paste from Spreadsheet
+paste-import-open-button = Paste Rows
+paste-import-description = Paste rows with the front in the first column and the back in the second, blank lines are ignored
+paste-import-result = {$added} flashcards added, {$skipped} rows skipped
+find-replace-title = Find and Replace
+find-title = Find
+find-placeholder = Text to find
+replace-title = Replace With
+replace-placeholder = Replacement text
+replace-sides = Sides
+replace-both-sides = Front & Back
+replace-front-side = Front
+replace-back-side = Back
+replace-case-sensitive = Match case
+find-replace-preview = {$affected} flashcards will change
+find-replace-button = Replace
 reset-folder-flashcards-title = Reset All Flashcard Status
 reset-folder-flashcards-button = Reset
 import-anki-title = Import from Anki File
 import-anki-button = Select Anki File
+import-csv-title = Import from CSV File
+import-csv-button = Select CSV File
 export-folder-flashcards-title = Export Folder Flashcards
-export-folder-flashcards-button = Export
\ No newline at end of file
+export-folder-flashcards-button = Export
+export-test-sheet-title = Export Test Sheet
+test-sheet-shuffled = Shuffle questions
+test-sheet-answer-key = Answer Key
+export-test-sheet-button = Export Test Sheet
+test-sheet-title = Test Sheet
+export-pdf-title = Export Printable PDF
+pdf-layout = Layout
+pdf-layout-two-columns = Term & Definition Columns
+pdf-layout-cut-out-cards = Cut-Out Cards
+export-pdf-button = Export PDF
+
+<#-- Sync From Source File -->
+sync-source-title = Sync from Source File
+sync-source-none = No source file selected
+sync-source-select-button = Select Source File
+sync-source-delete-missing = Delete flashcards missing from the file
+sync-source-button = Sync Now
+
+<#-- Settings Context Page -->
+bulk-operations = Bulk Operations
+bulk-confirmation-threshold = Ask for confirmation from (flashcards)
+flashcard-display = Flashcard Display
+render-markdown = Render Markdown
+match-all-tags = Only list the flashcards with every selected tag
+flashcard-length-limit = Maximum characters per side
+flashcard-length-unlimited = Unlimited
+typed-answers = Typed Answers
+answer-typo-tolerance = Accepted typos
+study-sessions = Study Sessions
+session-length = Flashcards per session
+session-length-unlimited = Unlimited
+mastered-streak = Mastered after (Good grades in a row)
+mastered-streak-never = Never
+include-mastered = Study mastered flashcards with the due ones
+study-reminders = Study Reminders
+study-reminders-enabled = Remind me when there are flashcards due
+reminder-days = Days
+reminder-hour = Remind from
+quiet-hours = Quiet hours
+quiet-hours-start = Quiet from
+quiet-hours-end = Quiet until
+study-reminder-title = Time to study
+study-reminder-body = {$count ->
+    [one] 1 flashcard is due
+   *[other] {$count} flashcards are due
+}
+weekday-monday = Mon
+weekday-tuesday = Tue
+weekday-wednesday = Wed
+weekday-thursday = Thu
+weekday-friday = Fri
+weekday-saturday = Sat
+weekday-sunday = Sun
+study-labels = Study Button Labels
+studyset-icon = StudySet Icon
+studyset-color = StudySet Color
+studyset-color-default = Default
+studyset-scheduler = StudySet Scheduler
+scheduler = Scheduler
+scheduler-sm2 = SM-2
+scheduler-leitner = Leitner Boxes
+min-interval = Shortest interval
+max-interval = Longest interval
+interval-days = {$days ->
+    [one] 1 day
+   *[other] {$days} days
+}
+
+sync = Sync
+sync-url = WebDAV folder
+sync-url-placeholder = https://example.com/remote.php/dav/files/user/oboete
+sync-username = Username
+sync-password = Password
+sync-push = Upload Backup
+sync-pull = Download Backup
+
+<#-- Sync Conflict Dialog -->
+sync-conflict = Sync Conflict
+sync-conflict-push = The remote backup has changed since the last sync, uploading will overwrite it.
+sync-conflict-pull = The remote backup isn't newer than the last sync, downloading it will replace the local StudySets and the changes that haven't been uploaded.
+sync-overwrite = Overwrite
+
+<#-- Resume Session Dialog -->
+resume-session = Resume Study Session
+resume-session-body = You had studied {$studied} of {$total} flashcards when the app was closed, do you want to go on with the session?
+resume = Resume
+abandon-session = Abandon
+
+<#-- File Chooser -->
+open = Open
+open-anki-file = Open Anki File
+open-csv-file = Open CSV File
+save-export = Save Export
+save-test-sheet = Save Test Sheet
+save-pdf = Save PDF
+open-source-file = Open Source File
+open-image = Open Image
+open-audio = Open Audio
+open-anki-package = Open Anki Package
+txt-file = TXT File
+csv-file = CSV File
+tsv-file = TSV File
+markdown-file = Markdown File
+json-file = JSON File
+pdf-file = PDF File
+image-file = Image
+audio-file = Audio
+anki-package = Anki Package
+
+<#-- Bulk Action Dialog -->
+delete-folder = Delete Folder
+confirm-bulk-action = This will affect {$affected} flashcards, are you sure?
\ No newline at end of file
@@ -8,10 +8,21 @@ about-anki-importing = ACERCA DE LA IMPORTACIÓN A ANKI
 <#-- Application MenuBar -->
 file = Archivo
 new-studyset = Nueva Unidad de Estudio
+import-anki-package = Importar Paquete de Anki
+import-studysets = Importar Unidades de Estudio
+export-studyset = Exportar Unidad de Estudio
+export-all-studysets = Exportar Todas las Unidades de Estudio
+backup-database = Copia de Seguridad de la Base de Datos
 edit = Editar
 rename-studyset = Renombrar Unidad de Estudio
+duplicate-studyset = Duplicar Unidad de Estudio
+reset-studyset-progress = Reiniciar Progreso de la Unidad de Estudio
 delete-studyset = Borrar Unidad de Estudio
 view = Ver
+all-flashcards = Todas las Flashcards
+study-all-due = Estudiar todas las pendientes
+settings = Ajustes
+keyboard-shortcuts = Atajos de Teclado
 about = Acerca de
 
 <#-- Page Headers -->
@@ -20,50 +31,310 @@ flashcards = Flashcards
 study = Estudiar
 new = Nueva
 options = Opciones
+due-count = {$due} pendientes
+study-streak = Racha de {$days} días
+studyset-nav-label = {$name} ({$count})
 
 <#-- New StudySet Dialog -->
 create-studyset = Crear Unidad de Estudio
 studyset-name = Nombre de la Unidad de Estudio
+name-empty = El nombre no puede estar vacío
+name-taken = "{$name}" ya está en uso
 
 <#-- Delete StudySet Dialog -->
 confirm-delete = Confirmar Eliminación
 ok = Ok
 
+<#-- Delete Flashcard Dialog -->
+delete-flashcard = Borrar Tarjeta
+confirm-delete-flashcard = ¿Seguro que quieres borrar "{$front}"?
+delete = Borrar
+
+<#-- Duplicate Flashcard Dialog -->
+duplicate-flashcard = Tarjeta Duplicada
+confirm-duplicate-flashcard = La carpeta ya tiene una tarjeta con "{$front}" en la cara, ¿quieres añadirla de todos modos?
+add-anyway = Añadir de Todos Modos
+
+<#-- Discard Flashcard Edit Dialog -->
+discard-changes = Descartar Cambios
+confirm-discard-changes = Los cambios hechos en la tarjeta se perderán, ¿estás seguro?
+discard = Descartar
+
+<#-- Move Flashcard Dialog -->
+move-flashcard = Mover Tarjeta
+move-flashcard-destination = Elige la carpeta a la que se moverá la tarjeta
+move = Mover
+
+<#-- Merge Folder Dialog -->
+merge-folder = Combinar Carpeta
+merge-folder-destination = Elige la carpeta a la que se moverán sus flashcards, esta carpeta se borrará
+merge = Combinar
+
+<#-- Error Dialog -->
+error-database = No se pudo usar la base de datos: {$error}
+error-file = No se pudo usar el archivo: {$error}
+error-newer-database = La base de datos se ha creado con una versión más reciente de Oboete (versión del esquema {$version}), actualiza la aplicación para abrirla
+error-flashcard-not-found = La tarjeta no existe
+error-folder-not-found = La carpeta no existe
+
+<#-- Backup Dialog -->
+backup-created = La copia de seguridad se ha guardado en {$path}
+
 <#-- New Folder Dialog -->
 create-folder = Crear Carpeta
 rename-folder = Renombrar Carpeta
 folder-name = Nombre de la Carpeta
+flashcard-count = {$count} tarjetas
 
-<#-- Folder Details Context Page -->
-folder-details = Detalles de la Carpeta
+<#-- Folder Outline Context Page -->
+folder-outline = Esquema
+folder-outline-title = Crear Carpetas desde un Esquema
+folder-outline-description = Cada línea se convierte en una carpeta de la unidad de estudio, se omiten las líneas vacías y los nombres ya usados
+folder-outline-result = {$created} carpetas creadas
 
 <#-- Flashcards Page -->
 ok-status = Ok
 good-status = Bien
 bad-status = Mal
+new-flashcard-badge = Nueva
+study-front-first = Cara Primero
+study-back-first = Reverso Primero
+study-random-side = Lado Aleatorio
+study-filter-all-due = Flashcards Pendientes
+study-filter-only-bad = Solo Mal
+study-filter-unseen = Solo Nuevas
+study-filter-mixed = Mal y Nuevas
+study-filter-favorites = Solo Favoritas
+study-order-random = Orden Aleatorio
+study-order-sequential = Orden de Creación
+study-order-oldest-first = Más Antiguas Primero
+study-mode-flashcards = Flashcards
+study-mode-multiple-choice = Opción Múltiple
+study-mode-typed = Respuesta Escrita
+typed-answer-placeholder = Escribe la respuesta
+typed-answer-check = Comprobar
+typed-answer-correct = Correcto:
+typed-answer-incorrect = Incorrecto, la respuesta es:
+typed-answer-next = Siguiente
+empty-flashcard = [Vacía]
+no-flashcards-due = No quedan flashcards por estudiar por ahora
+no-flashcards-filter = Ninguna flashcard coincide con el filtro de estudio
+no-flashcards-to-study = No hay tarjetas que estudiar
+back-to-flashcards = Volver a las Tarjetas
+search-flashcards = Buscar tarjetas
+select = Seleccionar
+select-all = Seleccionar Todas
+clear-selection = Quitar Selección
+delete-selected = Borrar Seleccionadas
+selected-flashcards = {$selected} seleccionadas
+flip-flashcard = Voltear Tarjeta
+add-favorite = Añadir a Favoritas
+remove-favorite = Quitar de Favoritas
+study-shortcuts-hint = 0: {$again} · 1 o J: {$bad} · 2 o K: {$ok} · 3 o L: {$good} · 4: {$easy} · Espacio: Voltear
+grade-again = Otra Vez
+grade-easy = Fácil
+folder-stats = {$good} bien · {$ok} regular · {$bad} mal · {$unseen} sin estudiar · {$total} en total
+study-progress = {$current} de {$total}
+list-page = Página {$current} de {$total}
+sort-created = Orden de Creación
+sort-front = Cara A–Z
+sort-back = Reverso A–Z
+sort-status = Estado
+sort-recently-added = Añadidas Recientemente
+session-complete = Sesión completada, {$studied} tarjetas estudiadas
+session-average-response = Tiempo medio de respuesta: {$seconds} s
+session-folder-studied = {$folder}: {$studied} estudiadas
+study-again = Estudiar de Nuevo
+play-audio = Reproducir Audio
+undo-grade = Deshacer Última Nota
+browse-mode = Explorar
+grade-mode = Calificar
+previous-flashcard = Anterior
+next-flashcard = Siguiente
+add-flashcard = Añadir Tarjeta
+edit-flashcard = Editar Tarjeta
+flashcard-front-shown = Cara: {$content}
+flashcard-back-shown = Reverso: {$content}
+grade-flashcard = Calificar como {$grade}
 
 <#-- Flashcard Create/Edit Context Page -->
 flashcard-front-title = Cara de la Flashcard
 flashcard-back-title = Reverso de la Flashcard
 flashcard-front-placeholder = Contenido de la Cara
 flashcard-back-placeholder = Contenido del Reverso
+flashcard-tags-title = Etiquetas
+flashcard-tags-placeholder = Etiquetas separadas por comas
+flashcard-cloze = Huecos, las respuestas de la cara van entre {"{{"} y {"}}"}
+flashcard-image-title = Imagen
+flashcard-image-none = Ninguna imagen seleccionada
+flashcard-image-select-button = Seleccionar Imagen
+flashcard-image-remove-button = Quitar Imagen
+flashcard-audio-title = Audio
+flashcard-audio-none = Ningún audio seleccionado
+flashcard-audio-select-button = Seleccionar Audio
+flashcard-audio-remove-button = Quitar Audio
 create = Create
 reset-flashcard-title = Reiniciar estado de la Flashcard
 reset-flashcard-button = Reiniciar
+flashcard-front-empty = La cara de la tarjeta no puede estar vacía
+flashcard-cloze-empty = La cara de una tarjeta de huecos necesita una respuesta entre {"{{"} y {"}}"}
+flashcard-too-long = La cara y el reverso de la tarjeta no pueden tener más de {$limit} caracteres
+
+<#-- Flashcard History Context Page -->
+flashcard-history = Historial de la Tarjeta
+flashcard-history-reviews = Estudiada {$reviews} veces
+flashcard-history-average-response = Tiempo medio de respuesta: {$seconds} s
+flashcard-history-grades = Calificaciones Anteriores
+flashcard-history-empty = Esta tarjeta aún no se ha estudiado
 
 <#-- Flashcard Options Context Page -->
 flashcard-options = Opciones de la Flashcard
-folder-import = Importar
+folder-study-preferences = Preferencias de Estudio
+study-mode-title = Modo
+study-direction-title = Dirección
+study-filter-title = Tarjetas Estudiadas
+study-order-title = Orden
+folder-import = Importar de Quizlet o Texto
 import-between-term-title = Entre Término y Definición
-import-between-term-placeholder = Carácter Entre Término y Definición
+import-between-term-placeholder = Carácter Entre Término y Definición, \t para tabulación
 import-between-cards-title = Entre Flashcards
-import-between-cards-placeholder = Carácter Entre Flashcards
+import-between-cards-placeholder = Carácter Entre Flashcards, \n para salto de línea
 import-content-title = Contenido a Importar
 import-content-placeholder = Contenido a Importar
 import-button = Importar
+paste-import-title = Pegar desde Hoja de Cálculo
+paste-import-open-button = Pegar Filas
+paste-import-description = Pega filas con la cara en la primera columna y el reverso en la segunda, las líneas en blanco se ignoran
+paste-import-result = {$added} tarjetas añadidas, {$skipped} filas omitidas
+find-replace-title = Buscar y Reemplazar
+find-title = Buscar
+find-placeholder = Texto a buscar
+replace-title = Reemplazar Con
+replace-placeholder = Texto de reemplazo
+replace-sides = Caras
+replace-both-sides = Cara y Reverso
+replace-front-side = Cara
+replace-back-side = Reverso
+replace-case-sensitive = Distinguir mayúsculas
+find-replace-preview = {$affected} tarjetas cambiarán
+find-replace-button = Reemplazar
 reset-folder-flashcards-title = Reinicar Estado de todas las Flashcard
 reset-folder-flashcards-button = Reinicar
 import-anki-title = Importar desde Archivo de Anki
 import-anki-button = Seleccionar Archivo
+import-csv-title = Importar desde Archivo CSV
+import-csv-button = Seleccionar Archivo CSV
 export-folder-flashcards-title = Exportar Flashcards
-export-folder-flashcards-button = Exportar
\ No newline at end of file
+export-folder-flashcards-button = Exportar
+export-test-sheet-title = Exportar Hoja de Examen
+test-sheet-shuffled = Mezclar las preguntas
+test-sheet-answer-key = Respuestas
+export-test-sheet-button = Exportar Hoja de Examen
+test-sheet-title = Hoja de Examen
+export-pdf-title = Exportar PDF Imprimible
+pdf-layout = Diseño
+pdf-layout-two-columns = Columnas de Término y Definición
+pdf-layout-cut-out-cards = Tarjetas Recortables
+export-pdf-button = Exportar PDF
+
+<#-- Sync From Source File -->
+sync-source-title = Sincronizar desde Archivo
+sync-source-none = Ningún archivo seleccionado
+sync-source-select-button = Seleccionar Archivo de Origen
+sync-source-delete-missing = Borrar las flashcards que no estén en el archivo
+sync-source-button = Sincronizar
+
+<#-- Settings Context Page -->
+bulk-operations = Operaciones en Bloque
+bulk-confirmation-threshold = Pedir confirmación a partir de (flashcards)
+flashcard-display = Visualización de Tarjetas
+render-markdown = Mostrar Markdown
+match-all-tags = Mostrar solo las tarjetas con todas las etiquetas seleccionadas
+flashcard-length-limit = Máximo de caracteres por lado
+flashcard-length-unlimited = Sin límite
+typed-answers = Respuestas Escritas
+answer-typo-tolerance = Errores tipográficos aceptados
+study-sessions = Sesiones de Estudio
+session-length = Flashcards por sesión
+session-length-unlimited = Sin límite
+mastered-streak = Dominada tras (notas Bien seguidas)
+mastered-streak-never = Nunca
+include-mastered = Estudiar las tarjetas dominadas con las pendientes
+study-reminders = Recordatorios de Estudio
+study-reminders-enabled = Avisarme cuando haya flashcards pendientes
+reminder-days = Días
+reminder-hour = Avisar a partir de
+quiet-hours = Horas de silencio
+quiet-hours-start = Silencio desde
+quiet-hours-end = Silencio hasta
+study-reminder-title = Hora de estudiar
+study-reminder-body = {$count ->
+    [one] 1 flashcard pendiente
+   *[other] {$count} flashcards pendientes
+}
+weekday-monday = Lun
+weekday-tuesday = Mar
+weekday-wednesday = Mié
+weekday-thursday = Jue
+weekday-friday = Vie
+weekday-saturday = Sáb
+weekday-sunday = Dom
+study-labels = Etiquetas de los Botones de Estudio
+studyset-icon = Icono del StudySet
+studyset-color = Color del StudySet
+studyset-color-default = Predeterminado
+studyset-scheduler = Planificador del StudySet
+scheduler = Planificador
+scheduler-sm2 = SM-2
+scheduler-leitner = Cajas de Leitner
+min-interval = Intervalo más corto
+max-interval = Intervalo más largo
+interval-days = {$days ->
+    [one] 1 día
+   *[other] {$days} días
+}
+
+sync = Sincronización
+sync-url = Carpeta WebDAV
+sync-url-placeholder = https://example.com/remote.php/dav/files/usuario/oboete
+sync-username = Usuario
+sync-password = Contraseña
+sync-push = Subir Copia de Seguridad
+sync-pull = Descargar Copia de Seguridad
+
+<#-- Sync Conflict Dialog -->
+sync-conflict = Conflicto de Sincronización
+sync-conflict-push = La copia de seguridad remota ha cambiado desde la última sincronización, subirla la sobrescribirá.
+sync-conflict-pull = La copia de seguridad remota no es más reciente que la última sincronización, descargarla reemplazará los StudySets locales y los cambios que no se han subido.
+sync-overwrite = Sobrescribir
+
+<#-- Resume Session Dialog -->
+resume-session = Reanudar Sesión de Estudio
+resume-session-body = Habías estudiado {$studied} de {$total} tarjetas cuando se cerró la aplicación, ¿quieres continuar con la sesión?
+resume = Reanudar
+abandon-session = Abandonar
+
+<#-- File Chooser -->
+open = Abrir
+open-anki-file = Abrir Archivo de Anki
+open-csv-file = Abrir Archivo CSV
+save-export = Guardar Exportación
+save-test-sheet = Guardar Hoja de Examen
+save-pdf = Guardar PDF
+open-source-file = Abrir Archivo de Origen
+open-image = Abrir Imagen
+open-audio = Abrir Audio
+open-anki-package = Abrir Paquete de Anki
+txt-file = Archivo TXT
+csv-file = Archivo CSV
+tsv-file = Archivo TSV
+markdown-file = Archivo Markdown
+json-file = Archivo JSON
+pdf-file = Archivo PDF
+image-file = Imagen
+audio-file = Audio
+anki-package = Paquete de Anki
+
+<#-- Bulk Action Dialog -->
+delete-folder = Borrar Carpeta
+confirm-bulk-action = Esto afectará a {$affected} flashcards, ¿estás seguro?
\ No newline at end of file
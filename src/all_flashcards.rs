@@ -0,0 +1,164 @@
+use cosmic::{
+    iced::{
+        alignment::{Horizontal, Vertical},
+        Alignment, Length,
+    },
+    theme, widget, Apply, Element,
+};
+
+use crate::{
+    core::icon_cache::IconCache,
+    fl,
+    models::{Flashcard, FlashcardLocation},
+    utils::break_long_words,
+};
+
+/// Max length of a word before it's forced to wrap
+const MAX_WORD_LENGTH: usize = 20;
+
+pub struct AllFlashcards {
+    pub flashcards: Vec<FlashcardLocation>,
+    /// Flashcard waiting for the delete confirmation
+    pub pending_delete: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LoadFlashcards,
+    //The i32 is the Folder Id
+    Edit(i32, Flashcard),
+    RequestDelete(Option<i32>),
+    CancelDelete,
+    Delete(Option<i32>),
+
+    SetFlashcards(Vec<FlashcardLocation>),
+}
+
+pub enum Command {
+    LoadFlashcards,
+    //The i32 is the Folder Id the flashcard belongs to
+    EditFlashcard(i32, Flashcard),
+    //The String is the front of the flashcard
+    ConfirmDelete(String),
+    DeleteFlashcard(Option<i32>),
+}
+
+impl AllFlashcards {
+    pub fn new() -> Self {
+        Self {
+            flashcards: Vec::new(),
+            pending_delete: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Vec<Command> {
+        let mut commands = Vec::new();
+
+        match message {
+            Message::LoadFlashcards => commands.push(Command::LoadFlashcards),
+            Message::Edit(folder_id, flashcard) => {
+                commands.push(Command::EditFlashcard(folder_id, flashcard))
+            }
+            Message::RequestDelete(flashcard_id) => {
+                if let Some(location) = self.flashcards.iter().find(|location| {
+                    flashcard_id.is_some() && location.flashcard.id == flashcard_id
+                }) {
+                    self.pending_delete = flashcard_id;
+                    commands.push(Command::ConfirmDelete(location.flashcard.front.clone()));
+                }
+            }
+            Message::CancelDelete => self.pending_delete = None,
+            Message::Delete(flashcard_id) => {
+                self.pending_delete = None;
+                commands.push(Command::DeleteFlashcard(flashcard_id))
+            }
+            Message::SetFlashcards(flashcards) => self.flashcards = flashcards,
+        }
+
+        commands
+    }
+
+    fn header_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        widget::row::with_capacity(1)
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_s)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .push(widget::text::title3(fl!("all-flashcards")).width(Length::Fill))
+            .into()
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        if self.flashcards.is_empty() == false {
+            let mut flashcards = widget::list::list_column()
+                .style(theme::Container::ContextDrawer)
+                .spacing(spacing.space_xxxs)
+                .padding([spacing.space_none, spacing.space_xxs]);
+
+            for location in &self.flashcards {
+                let edit_button = widget::button(IconCache::get("edit-button-symbolic", 18))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Standard)
+                    .on_press(Message::Edit(
+                        location.folder_id,
+                        location.flashcard.clone(),
+                    ));
+
+                let delete_button = widget::button(IconCache::get("user-trash-full-symbolic", 18))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Destructive)
+                    .on_press(Message::RequestDelete(location.flashcard.id));
+
+                let flashcard_front = widget::column::with_capacity(2)
+                    .push(
+                        widget::text(break_long_words(&location.flashcard.front, MAX_WORD_LENGTH))
+                            .vertical_alignment(Vertical::Center)
+                            .horizontal_alignment(Horizontal::Left),
+                    )
+                    .push(widget::text::caption(format!(
+                        "{} / {}",
+                        location.studyset_name, location.folder_name
+                    )))
+                    .width(Length::Fill);
+
+                let row = widget::row::with_capacity(3)
+                    .align_items(Alignment::Center)
+                    .spacing(spacing.space_xxs)
+                    .padding([spacing.space_xxxs, spacing.space_xxs])
+                    .push(flashcard_front)
+                    .push(delete_button)
+                    .push(edit_button);
+
+                flashcards = flashcards.add(row);
+            }
+
+            widget::column::with_capacity(2)
+                .spacing(spacing.space_xxs)
+                .push(self.header_row())
+                .push(flashcards)
+                .apply(widget::container)
+                .height(Length::Shrink)
+                .apply(widget::scrollable)
+                .height(Length::Fill)
+                .into()
+        } else {
+            widget::column::with_capacity(2)
+                .spacing(spacing.space_xxs)
+                .push(self.header_row())
+                .push(
+                    widget::Container::new(
+                        widget::Text::new(fl!("empty-page")).size(spacing.space_xl),
+                    )
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .align_x(cosmic::iced::alignment::Horizontal::Center)
+                    .align_y(cosmic::iced::alignment::Vertical::Center),
+                )
+                .height(Length::Fill)
+                .into()
+        }
+    }
+}
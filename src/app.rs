@@ -1,27 +1,83 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use std::collections::{HashMap, VecDeque};
-
-use crate::core::database::{
-    delete_flashcard, delete_folder, delete_studyset, get_all_studysets, get_folder_flashcards,
-    get_single_flashcard, get_single_folder, get_studyset_folders, import_flashcards,
-    reset_folder_flashcard_status, reset_single_flashcard_status, update_flashcard_status,
-    upsert_flashcard, upsert_folder, upsert_studyset, OboeteDb,
+use std::time::Duration;
+
+use crate::all_flashcards::{self, AllFlashcards};
+use crate::core::api::{
+    cards::{
+        delete_flashcard, delete_flashcards, find_replace_flashcards, flashcard_exists,
+        get_all_flashcards, get_folder_flashcards, get_single_flashcard, move_flashcard,
+        reset_single_flashcard_status, search_flashcards, set_flashcard_favorite, upsert_flashcard,
+    },
+    folders::{
+        count_folder_flashcards, create_folder, create_folders, delete_folder, get_folder_stats,
+        get_folder_study_preferences, get_single_folder, get_studyset_folders, merge_folders,
+        move_folder, rename_folder, reset_folder_flashcard_status, set_folder_source_path,
+        set_folder_study_preferences, sync_folder_from_source,
+    },
+    study::{
+        check_study_reminder, get_all_due_flashcards, get_current_streak, get_due_count,
+        get_flashcard_history, grade_flashcard, log_study_session, undo_flashcard_grade,
+        StudyReminder,
+    },
+    studysets::{
+        count_flashcards_by_studyset, count_studyset_flashcards, create_studyset, delete_studyset,
+        duplicate_studyset, get_all_studysets, rename_studyset, reset_studyset_flashcard_status,
+        set_studyset_color, set_studyset_icon,
+    },
+    transfer::{
+        backup_database, export_all, export_folder_pdf, export_studyset, import_all, import_apkg,
+        import_flashcards, import_flashcards_csv, import_quizlet, import_studysets, import_tsv,
+        pull_backup, push_backup, SyncDirection, SyncOutcome,
+    },
+    OboeteDb,
+};
+use crate::core::config::{
+    OboeteConfig, StudyLabels, ANSWER_TYPO_TOLERANCES, BULK_CONFIRMATION_THRESHOLDS,
+    FLASHCARD_LENGTH_LIMITS, MASTERED_STREAKS, MAX_INTERVALS, MIN_INTERVALS, SESSION_LENGTHS,
 };
+use crate::core::icon_cache::IconCache;
+use crate::core::key_binds::key_binds;
+use crate::core::notifications::send_study_reminder;
+use crate::core::scheduler::{IntervalLimits, SchedulerKind, SCHEDULER_KINDS};
 use crate::fl;
-use crate::flashcards::{self, Flashcards};
+use crate::flashcards::{self, Flashcards, PausedSession, StudyActions};
 use crate::folders::{self, Folders};
-use crate::models::{Folder, StudySet};
-use crate::utils::select_random_flashcard;
+use crate::models::{
+    FlashcardLocation, FolderStudyPreferences, StudyMode, StudySet, STUDYSET_COLORS, STUDYSET_ICONS,
+};
+use crate::utils::{app_data_dir, decode_file_path, store_attachment, OboeteError};
 use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
 use cosmic::app::{message, Core, Message as CosmicMessage};
-use cosmic::iced::{Alignment, Length};
+use cosmic::iced::keyboard::{self, key::Named, Key, Modifiers};
+use cosmic::iced::{event, time, window, Alignment, Event, Length, Subscription};
 use cosmic::widget::segmented_button::{EntityMut, SingleSelect};
 use cosmic::widget::{self, menu, nav_bar, segmented_button};
-use cosmic::{cosmic_theme, theme, Application, ApplicationExt, Command, Element};
+use cosmic::{cosmic_config, cosmic_theme, theme, Application, ApplicationExt, Command, Element};
+use serde::{Deserialize, Serialize};
 
 const REPOSITORY: &str = "https://github.com/mariinkys/oboete";
 
+/// How often the study reminder is checked while the app is running
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Updates a config value, persisting it if the config handler is available
+macro_rules! config_set {
+    ($app:expr, $name:ident, $value:expr) => {
+        match &$app.config_handler {
+            Some(config_handler) => {
+                if let Err(err) =
+                    paste::paste! { $app.config.[<set_ $name>](config_handler, $value) }
+                {
+                    eprintln!("Error saving config {}: {}", stringify!($name), err);
+                }
+            }
+            None => $app.config.$name = $value,
+        }
+    };
+}
+
 pub struct Oboete {
     /// Application state which is managed by the COSMIC runtime.
     core: Core,
@@ -43,6 +99,44 @@ pub struct Oboete {
     folders: Folders,
     /// Flashcards Page (A folder flashcards, not all flashcards)
     flashcards: Flashcards,
+    /// All Flashcards Page (Every flashcard of every studyset)
+    all_flashcards: AllFlashcards,
+    /// Handler used to persist the configuration of the application
+    config_handler: Option<cosmic_config::Config>,
+    /// Configuration of the application
+    config: OboeteConfig,
+    /// Labels of the selectable bulk confirmation thresholds
+    bulk_threshold_labels: Vec<String>,
+    typo_tolerance_labels: Vec<String>,
+    session_length_labels: Vec<String>,
+    mastered_streak_labels: Vec<String>,
+    length_limit_labels: Vec<String>,
+    scheduler_labels: Vec<String>,
+    min_interval_labels: Vec<String>,
+    max_interval_labels: Vec<String>,
+    /// Labels of the hours of the day, for the study reminder and the quiet hours
+    hour_labels: Vec<String>,
+    /// Whether the last session state has already been restored (until then it must not be overwritten)
+    state_restored: bool,
+    /// Message of the last failed operation, shown in a banner until dismissed
+    error_banner: Option<String>,
+    /// Flashcards of every studyset due now, shown in the header
+    due_count: i32,
+    /// Flashcards of each studyset (by id), shown next to their names in the nav bar
+    studyset_card_counts: HashMap<i32, usize>,
+    /// Consecutive days with a completed study session, shown in the header
+    streak: i32,
+    /// Navigation waiting for the confirmation to discard the flashcard being edited
+    pending_navigation: Option<Message>,
+    /// Study session of the last launch, kept until it's resumed or abandoned
+    pending_resume: Option<PausedSession>,
+}
+
+/// Flags passed to the application on startup
+#[derive(Clone, Debug)]
+pub struct Flags {
+    pub config_handler: Option<cosmic_config::Config>,
+    pub config: OboeteConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -52,43 +146,126 @@ pub enum Message {
     DbConnected(OboeteDb),
     Folders(folders::Message),
     Flashcards(flashcards::Message),
+    AllFlashcards(all_flashcards::Message),
+    OpenAllFlashcards,
+    StudyAllDue,
+    //The flashcards are the due ones of every folder
+    AllDueLoaded(Vec<FlashcardLocation>),
     FetchStudySets,
     PopulateStudySets(Vec<StudySet>),
     OpenNewStudySetDialog,
     OpenRenameStudySetDialog,
     OpenDeleteStudySetDialog,
+    DuplicateStudySet,
+    ResetStudySetProgress,
+    ExportStudySet,
+    ExportStudySetResult(Vec<String>),
+    ExportAll,
+    ExportAllResult(Vec<String>),
+    ImportAll,
+    ImportAllResult(Vec<String>),
+    BackupDatabase,
+    //The String is the path of the backup
+    BackupCreated(String),
+    ImportAnkiPackage,
+    ImportAnkiPackageResult(Vec<String>),
+    ImportedStudySets(Vec<StudySet>),
     DialogCancel,
     DialogComplete,
     DialogUpdate(DialogPage),
     AddStudySet(StudySet),
     DeleteStudySet,
     OpenNewFolderDialog,
+    RequestBulkAction(BulkAction, usize),
+    RunBulkAction(BulkAction),
+    UpdateBulkConfirmationThreshold(usize),
+    UpdateAnswerTypoTolerance(usize),
+    UpdateSessionLength(usize),
+    UpdateMasteredStreak(usize),
+    UpdateIncludeMastered(bool),
+    UpdateFlashcardLengthLimit(usize),
+    UpdateRenderMarkdown(bool),
+    UpdateMatchAllTags(bool),
+    UpdateStudyReminders(bool),
+    //Day of the week, 0 is Sunday
+    ToggleReminderWeekday(usize),
+    UpdateReminderHour(usize),
+    UpdateQuietHours(bool),
+    UpdateQuietHoursStart(usize),
+    UpdateQuietHoursEnd(usize),
+    CheckStudyReminder,
+    SendStudyReminder(StudyReminder),
+    WindowResized(u32, u32),
+    UpdateStudyLabels(StudyLabels),
+    UpdateStudySetIcon(&'static str),
+    //None goes back to the default styling
+    UpdateStudySetColor(Option<&'static str>),
+    UpdateStudySetScheduler(usize),
+    UpdateStudySetMinInterval(usize),
+    UpdateStudySetMaxInterval(usize),
+    UpdateSyncUrl(String),
+    UpdateSyncUsername(String),
+    UpdateSyncPassword(String),
+    //The bool forces the sync even if it overwrites changes of the other side
+    Sync(SyncDirection, bool),
+    SyncFinished(SyncDirection, SyncOutcome),
+    Key(Modifiers, Key),
+    OpenKeyboardShortcutsDialog,
+    /// Failures of background operations (loading, saving...), shown in a banner
+    Error(OboeteError),
+    DismissError,
+    FetchDueCount,
+    OfferSessionResume,
+    ForgetPausedSession,
+    FetchCardCounts,
+    //The flashcards of each studyset (by id)
+    SetCardCounts(HashMap<i32, usize>),
+    SetDueCount(i32),
+    FetchStreak,
+    SetStreak(i32),
+    SelectNavItem(segmented_button::Entity),
+    //The i32 is the StudySet Id
+    StudySetRenamed(i32, String),
+    GradeStudiedFlashcard(StudyActions),
+    FlipStudiedFlashcard,
+    //The bool is true to move to the next flashcard, false for the previous one
+    BrowseStudiedFlashcards(bool),
+    UndoStudiedGrade,
 }
 
 /// Identifies a page in the application.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Page {
+    #[default]
     Folders,
     FolderFlashcards,
     StudyFolderFlashcards,
+    AllFlashcards,
 }
 
 /// Identifies a context page to display in the context drawer.
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum ContextPage {
     #[default]
     About,
-    EditFolder,
     CreateEditFlashcard,
+    FlashcardHistory,
     FlashcardOptions,
+    FolderOutline,
+    PasteImport,
+    Settings,
 }
 
 impl ContextPage {
     fn title(&self) -> String {
         match self {
             Self::About => fl!("about"),
-            Self::EditFolder => fl!("folder-details"),
             Self::CreateEditFlashcard => fl!("flashcard-options"),
+            Self::FlashcardHistory => fl!("flashcard-history"),
             Self::FlashcardOptions => fl!("flashcard-options"),
+            Self::FolderOutline => fl!("folder-outline-title"),
+            Self::PasteImport => fl!("paste-import-title"),
+            Self::Settings => fl!("settings"),
         }
     }
 }
@@ -96,9 +273,60 @@ impl ContextPage {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MenuAction {
     About,
+    AllFlashcards,
+    StudyAllDue,
+    Settings,
     NewStudySet,
     RenameStudySet,
     DeleteStudySet,
+    DuplicateStudySet,
+    ResetStudySetProgress,
+    ExportStudySet,
+    ExportAll,
+    ImportAll,
+    ImportAnkiPackage,
+    BackupDatabase,
+    KeyboardShortcuts,
+    GradeAgain,
+    GradeBad,
+    GradeOk,
+    GradeGood,
+    GradeEasy,
+    FlipFlashcard,
+    UndoGrade,
+    PreviousFlashcard,
+    NextFlashcard,
+}
+
+impl MenuAction {
+    fn title(&self) -> String {
+        match self {
+            Self::About => fl!("about"),
+            Self::AllFlashcards => fl!("all-flashcards"),
+            Self::StudyAllDue => fl!("study-all-due"),
+            Self::Settings => fl!("settings"),
+            Self::NewStudySet => fl!("new-studyset"),
+            Self::RenameStudySet => fl!("rename-studyset"),
+            Self::DeleteStudySet => fl!("delete-studyset"),
+            Self::DuplicateStudySet => fl!("duplicate-studyset"),
+            Self::ResetStudySetProgress => fl!("reset-studyset-progress"),
+            Self::ExportStudySet => fl!("export-studyset"),
+            Self::ExportAll => fl!("export-all-studysets"),
+            Self::ImportAll => fl!("import-studysets"),
+            Self::ImportAnkiPackage => fl!("import-anki-package"),
+            Self::BackupDatabase => fl!("backup-database"),
+            Self::KeyboardShortcuts => fl!("keyboard-shortcuts"),
+            Self::GradeAgain => fl!("grade-again"),
+            Self::GradeBad => fl!("bad-status"),
+            Self::GradeOk => fl!("ok-status"),
+            Self::GradeGood => fl!("good-status"),
+            Self::GradeEasy => fl!("grade-easy"),
+            Self::FlipFlashcard => fl!("flip-flashcard"),
+            Self::UndoGrade => fl!("undo-grade"),
+            Self::PreviousFlashcard => fl!("previous-flashcard"),
+            Self::NextFlashcard => fl!("next-flashcard"),
+        }
+    }
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -107,9 +335,29 @@ impl menu::action::MenuAction for MenuAction {
     fn message(&self) -> Self::Message {
         match self {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
+            MenuAction::AllFlashcards => Message::OpenAllFlashcards,
+            MenuAction::StudyAllDue => Message::StudyAllDue,
+            MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
             MenuAction::NewStudySet => Message::OpenNewStudySetDialog,
             MenuAction::RenameStudySet => Message::OpenRenameStudySetDialog,
             MenuAction::DeleteStudySet => Message::OpenDeleteStudySetDialog,
+            MenuAction::DuplicateStudySet => Message::DuplicateStudySet,
+            MenuAction::ResetStudySetProgress => Message::ResetStudySetProgress,
+            MenuAction::ExportStudySet => Message::ExportStudySet,
+            MenuAction::ExportAll => Message::ExportAll,
+            MenuAction::ImportAll => Message::ImportAll,
+            MenuAction::ImportAnkiPackage => Message::ImportAnkiPackage,
+            MenuAction::BackupDatabase => Message::BackupDatabase,
+            MenuAction::KeyboardShortcuts => Message::OpenKeyboardShortcutsDialog,
+            MenuAction::GradeAgain => Message::GradeStudiedFlashcard(StudyActions::Again),
+            MenuAction::GradeBad => Message::GradeStudiedFlashcard(StudyActions::Bad),
+            MenuAction::GradeOk => Message::GradeStudiedFlashcard(StudyActions::Ok),
+            MenuAction::GradeGood => Message::GradeStudiedFlashcard(StudyActions::Good),
+            MenuAction::GradeEasy => Message::GradeStudiedFlashcard(StudyActions::Easy),
+            MenuAction::FlipFlashcard => Message::FlipStudiedFlashcard,
+            MenuAction::UndoGrade => Message::UndoStudiedGrade,
+            MenuAction::PreviousFlashcard => Message::BrowseStudiedFlashcards(false),
+            MenuAction::NextFlashcard => Message::BrowseStudiedFlashcards(true),
         }
     }
 }
@@ -117,15 +365,59 @@ impl menu::action::MenuAction for MenuAction {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DialogPage {
     NewStudySet(String),
-    RenameStudySet { to: String },
+    RenameStudySet {
+        to: String,
+    },
     DeleteStudySet,
     NewFolder(String),
+    ConfirmBulkAction {
+        action: BulkAction,
+        affected: usize,
+    },
+    KeyboardShortcuts,
+    //The String is the front of the flashcard
+    DeleteFlashcard(String),
+    //The String is the front of the new flashcard
+    DuplicateFlashcard(String),
+    //The String is the path of the backup
+    BackupCreated(String),
+    //The target is the id of the picked folder
+    MoveFlashcard {
+        flashcard_id: i32,
+        target: Option<i32>,
+    },
+    //The target is the id of the picked folder
+    MergeFolder {
+        source_id: i32,
+        target: Option<i32>,
+    },
+    DiscardFlashcardEdit,
+    SyncConflict(SyncDirection),
+    //The session is the pending_resume one
+    ResumeSession,
+}
+
+/// Operations that affect many flashcards at once and may need a confirmation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BulkAction {
+    //The i32 is the Folder Id
+    ResetFolderFlashcards(i32),
+    //The i32 is the StudySet Id
+    ResetStudySetFlashcards(i32),
+    //The i32 is the Folder Id
+    DeleteFolder(i32),
+    SyncFolder {
+        folder_id: i32,
+        delete_missing: bool,
+    },
+    //The flashcards selected in the open folder
+    DeleteSelectedFlashcards,
 }
 
 impl Application for Oboete {
     type Executor = cosmic::executor::Default;
 
-    type Flags = ();
+    type Flags = Flags;
 
     type Message = Message;
 
@@ -144,27 +436,103 @@ impl Application for Oboete {
         Some(&self.nav)
     }
 
-    fn init(mut core: Core, _flags: Self::Flags) -> (Self, Command<CosmicMessage<Self::Message>>) {
+    fn init(mut core: Core, flags: Self::Flags) -> (Self, Command<CosmicMessage<Self::Message>>) {
         core.nav_bar_toggle_condensed();
         let nav = segmented_button::ModelBuilder::default().build();
 
-        let app = Oboete {
+        let mut app = Oboete {
             core,
             context_page: ContextPage::default(),
-            key_binds: HashMap::new(),
+            key_binds: key_binds(),
             nav,
             current_page: Page::Folders,
             db: None,
             folders: Folders::new(),
             flashcards: Flashcards::new(),
+            all_flashcards: AllFlashcards::new(),
             dialog_pages: VecDeque::new(),
             dialog_text_input: widget::Id::unique(),
+            config_handler: flags.config_handler,
+            config: flags.config,
+            bulk_threshold_labels: BULK_CONFIRMATION_THRESHOLDS
+                .iter()
+                .map(|threshold| threshold.to_string())
+                .collect(),
+            typo_tolerance_labels: ANSWER_TYPO_TOLERANCES
+                .iter()
+                .map(|tolerance| tolerance.to_string())
+                .collect(),
+            session_length_labels: SESSION_LENGTHS
+                .iter()
+                .map(|length| match length {
+                    Some(length) => length.to_string(),
+                    None => fl!("session-length-unlimited"),
+                })
+                .collect(),
+            mastered_streak_labels: MASTERED_STREAKS
+                .iter()
+                .map(|streak| match streak {
+                    Some(streak) => streak.to_string(),
+                    None => fl!("mastered-streak-never"),
+                })
+                .collect(),
+            length_limit_labels: FLASHCARD_LENGTH_LIMITS
+                .iter()
+                .map(|limit| match limit {
+                    Some(limit) => limit.to_string(),
+                    None => fl!("flashcard-length-unlimited"),
+                })
+                .collect(),
+            scheduler_labels: SCHEDULER_KINDS
+                .iter()
+                .map(|kind| match kind {
+                    SchedulerKind::Sm2 => fl!("scheduler-sm2"),
+                    SchedulerKind::Leitner => fl!("scheduler-leitner"),
+                })
+                .collect(),
+            min_interval_labels: MIN_INTERVALS
+                .iter()
+                .map(|days| fl!("interval-days", days = *days))
+                .collect(),
+            max_interval_labels: MAX_INTERVALS
+                .iter()
+                .map(|days| fl!("interval-days", days = *days))
+                .collect(),
+            hour_labels: (0..24).map(|hour| format!("{hour:02}:00")).collect(),
+            state_restored: false,
+            error_banner: None,
+            due_count: 0,
+            studyset_card_counts: HashMap::new(),
+            streak: 0,
+            pending_navigation: None,
+            pending_resume: None,
         };
+        app.flashcards.render_markdown = app.config.render_markdown;
+        app.flashcards.match_all_tags = app.config.match_all_tags;
+        app.flashcards.mastered_streak = app.config.mastered_streak;
+        app.flashcards.include_mastered = app.config.include_mastered;
+        app.flashcards.length_limit = app.config.flashcard_length_limit;
+
+        //Only the context pages that don't depend on a loaded folder or flashcard are restored
+        if app.config.context_drawer_open
+            && matches!(
+                app.config.context_page,
+                ContextPage::About | ContextPage::Settings
+            )
+        {
+            app.context_page = app.config.context_page;
+            app.core.window.show_context = true;
+            app.set_context_title(app.context_page.title());
+        }
 
         //Connect to the Database and Run the needed migrations
-        let commands = vec![Command::perform(OboeteDb::init(Self::APP_ID), |database| {
-            message::app(Message::DbConnected(database))
-        })];
+        let commands = vec![Command::perform(
+            OboeteDb::init(Self::APP_ID),
+            |result| match result {
+                Ok(database) => message::app(Message::DbConnected(database)),
+                Err(err) => message::app(Message::Error(err)),
+            },
+        )];
 
         (app, Command::batch(commands))
     }
@@ -176,10 +544,17 @@ impl Application for Oboete {
                 menu::root(fl!("file")),
                 menu::items(
                     &self.key_binds,
-                    vec![menu::Item::Button(
-                        fl!("new-studyset"),
-                        MenuAction::NewStudySet,
-                    )],
+                    vec![
+                        menu::Item::Button(fl!("new-studyset"), MenuAction::NewStudySet),
+                        menu::Item::Button(
+                            fl!("import-anki-package"),
+                            MenuAction::ImportAnkiPackage,
+                        ),
+                        menu::Item::Button(fl!("import-studysets"), MenuAction::ImportAll),
+                        menu::Item::Button(fl!("export-studyset"), MenuAction::ExportStudySet),
+                        menu::Item::Button(fl!("export-all-studysets"), MenuAction::ExportAll),
+                        menu::Item::Button(fl!("backup-database"), MenuAction::BackupDatabase),
+                    ],
                 ),
             ),
             menu::Tree::with_children(
@@ -188,6 +563,14 @@ impl Application for Oboete {
                     &self.key_binds,
                     vec![
                         menu::Item::Button(fl!("rename-studyset"), MenuAction::RenameStudySet),
+                        menu::Item::Button(
+                            fl!("duplicate-studyset"),
+                            MenuAction::DuplicateStudySet,
+                        ),
+                        menu::Item::Button(
+                            fl!("reset-studyset-progress"),
+                            MenuAction::ResetStudySetProgress,
+                        ),
                         menu::Item::Button(fl!("delete-studyset"), MenuAction::DeleteStudySet),
                     ],
                 ),
@@ -196,7 +579,16 @@ impl Application for Oboete {
                 menu::root(fl!("view")),
                 menu::items(
                     &self.key_binds,
-                    vec![menu::Item::Button(fl!("about"), MenuAction::About)],
+                    vec![
+                        menu::Item::Button(fl!("all-flashcards"), MenuAction::AllFlashcards),
+                        menu::Item::Button(fl!("study-all-due"), MenuAction::StudyAllDue),
+                        menu::Item::Button(fl!("settings"), MenuAction::Settings),
+                        menu::Item::Button(
+                            fl!("keyboard-shortcuts"),
+                            MenuAction::KeyboardShortcuts,
+                        ),
+                        menu::Item::Button(fl!("about"), MenuAction::About),
+                    ],
                 ),
             ),
         ]);
@@ -204,6 +596,40 @@ impl Application for Oboete {
         vec![menu_bar.into()]
     }
 
+    /// Elements to pack at the end of the header bar.
+    fn header_end(&self) -> Vec<Element<Self::Message>> {
+        let spacing = theme::active().cosmic().spacing;
+        let mut elements = Vec::new();
+
+        //The streak is a status badge, the due badge starts studying every due flashcard
+        if self.streak > 0 {
+            elements.push(
+                widget::button(widget::text::caption(fl!(
+                    "study-streak",
+                    days = self.streak
+                )))
+                .padding([spacing.space_xxxs, spacing.space_xs])
+                .style(theme::Button::Standard)
+                .into(),
+            );
+        }
+
+        if self.due_count > 0 {
+            elements.push(
+                widget::button(widget::text::caption(fl!(
+                    "due-count",
+                    due = self.due_count
+                )))
+                .padding([spacing.space_xxxs, spacing.space_xs])
+                .style(theme::Button::Suggested)
+                .on_press(Message::StudyAllDue)
+                .into(),
+            );
+        }
+
+        elements
+    }
+
     fn view(&self) -> Element<Self::Message> {
         let content = match self.current_page {
             Page::Folders => self.folders.view().map(Message::Folders),
@@ -211,6 +637,15 @@ impl Application for Oboete {
             Page::StudyFolderFlashcards => {
                 self.flashcards.view_study_page().map(Message::Flashcards)
             }
+            Page::AllFlashcards => self.all_flashcards.view().map(Message::AllFlashcards),
+        };
+
+        let content = match &self.error_banner {
+            Some(error) => widget::column::with_capacity(2)
+                .push(self.error_banner(error))
+                .push(content)
+                .into(),
+            None => content,
         };
 
         widget::Container::new(content)
@@ -222,6 +657,11 @@ impl Application for Oboete {
     fn update(&mut self, message: Self::Message) -> Command<CosmicMessage<Self::Message>> {
         let mut commands = vec![];
 
+        if self.has_unsaved_edit() && leaves_flashcard_edit(&message) {
+            self.confirm_discard_edit(message);
+            return Command::none();
+        }
+
         match message {
             Message::LaunchUrl(url) => {
                 let _result = open::that_detached(url);
@@ -244,6 +684,10 @@ impl Application for Oboete {
                 self.db = Some(db);
                 let command = self.update(Message::FetchStudySets);
                 commands.push(command);
+                commands.push(self.update(Message::FetchDueCount));
+                commands.push(self.update(Message::FetchStreak));
+                commands.push(self.backup_database(false));
+                commands.push(self.move_folder_study_preferences());
             }
             Message::Folders(message) => {
                 let folder_commands = self.folders.update(message);
@@ -258,11 +702,12 @@ impl Application for Oboete {
                                     Ok(folders) => message::app(Message::Folders(
                                         folders::Message::SetFolders(folders),
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
 
                             commands.push(command);
+                            commands.push(self.update(Message::FetchCardCounts));
                         }
                         //Opens a folder => Loads the flashcards of a given folder => Updates the current_folder_id
                         folders::Command::OpenFolder(folder_id) => {
@@ -272,12 +717,66 @@ impl Application for Oboete {
                                     Ok(flashcards) => message::app(Message::Flashcards(
                                         flashcards::Message::SetFlashcards(flashcards),
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            //The source file is needed in case the folder gets synced
+                            let folder_command = Command::perform(
+                                get_single_folder(self.db.clone(), folder_id),
+                                |result| match result {
+                                    Ok(folder) => message::app(Message::Flashcards(
+                                        flashcards::Message::SetFolderSource(folder.source_path),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             self.current_page = Page::FolderFlashcards;
+                            self.flashcards.leave_session();
                             self.flashcards.current_folder_id = folder_id;
+                            self.flashcards.current_folder_source = None;
+                            self.flashcards.tag_filter.clear();
+                            self.flashcards.search_query = String::new();
+                            self.flashcards.search_results = None;
+                            self.flashcards.list_page = 0;
+                            self.flashcards.selection_mode = false;
+                            self.flashcards.selected_flashcards.clear();
+                            //The defaults are kept until the preferences of the folder are loaded
+                            self.flashcards
+                                .set_study_preferences(FolderStudyPreferences::default());
+                            let preferences_command = Command::perform(
+                                get_folder_study_preferences(self.db.clone(), folder_id),
+                                |result| match result {
+                                    Ok(preferences) => message::app(Message::Flashcards(
+                                        flashcards::Message::SetStudyPreferences(preferences),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+
+                            commands.push(command);
+                            commands.push(folder_command);
+                            commands.push(preferences_command);
+                        }
+                        folders::Command::ToggleOutlinePage => {
+                            if self.context_page == ContextPage::FolderOutline {
+                                self.core.window.show_context = !self.core.window.show_context;
+                            } else {
+                                self.context_page = ContextPage::FolderOutline;
+                                self.core.window.show_context = true;
+                            }
 
+                            self.set_context_title(ContextPage::FolderOutline.title());
+                        }
+                        folders::Command::CreateFolders(studyset_id, names) => {
+                            let command = Command::perform(
+                                create_folders(self.db.clone(), studyset_id, names),
+                                |result| match result {
+                                    Ok(created) => message::app(Message::Folders(
+                                        folders::Message::CreatedFromOutline(created),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
                             commands.push(command);
                         }
                         folders::Command::OpenCreateFolderDialog => {
@@ -288,63 +787,71 @@ impl Application for Oboete {
                             );
                             commands.push(command);
                         }
-                        folders::Command::UpsertFolder(folder) => {
+                        //Renames the folder and reloads the list, failures are shown in a dialog
+                        folders::Command::RenameFolder(folder_id, name) => {
                             let command = Command::perform(
-                                upsert_folder(
-                                    self.db.clone(),
-                                    folder,
-                                    self.flashcards.current_folder_id,
-                                ),
-                                |_result| {
-                                    message::app(Message::Folders(folders::Message::Upserted))
+                                rename_folder(self.db.clone(), folder_id, name),
+                                |result| match result {
+                                    Ok(_) => {
+                                        message::app(Message::Folders(folders::Message::Upserted))
+                                    }
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
-                            self.core.window.show_context = false;
                             commands.push(command);
                         }
-                        folders::Command::ToggleEditContextPage(folder) => {
-                            if self.context_page == ContextPage::EditFolder {
-                                // Close the context drawer if the toggled context page is the same.
-                                self.core.window.show_context = !self.core.window.show_context;
-                            } else {
-                                // Open the context drawer to display the requested context page.
-                                self.context_page = ContextPage::EditFolder;
-                                self.core.window.show_context = true;
-                            }
-
-                            //Loads the flashcard in case is an edit operation
-                            if folder.is_some() {
-                                let command = Command::perform(
-                                    get_single_folder(self.db.clone(), folder.unwrap().id.unwrap()),
-                                    |result| match result {
-                                        Ok(folder) => message::app(Message::Folders(
-                                            folders::Message::LoadedSingle(folder),
-                                        )),
-                                        Err(_) => message::none(),
-                                    },
-                                );
-                                commands.push(command);
-                            }
-
-                            // Set the title of the context drawer.
-                            self.set_context_title(ContextPage::EditFolder.title());
+                        folders::Command::FocusRenameInput => {
+                            commands.push(widget::text_input::focus(
+                                self.folders.rename_input_id.clone(),
+                            ));
                         }
-                        folders::Command::DeleteFolder(folder_id) => {
+                        folders::Command::PickMergeTarget(source_id) => {
+                            self.dialog_pages.push_back(DialogPage::MergeFolder {
+                                source_id,
+                                target: None,
+                            });
+                        }
+                        folders::Command::MoveFolder(folder_id, direction) => {
                             let command = Command::perform(
-                                delete_folder(self.db.clone(), folder_id.unwrap()),
+                                move_folder(self.db.clone(), folder_id, direction),
                                 |result| match result {
                                     Ok(_) => message::app(Message::Folders(
                                         folders::Message::LoadFolders,
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             commands.push(command);
                         }
+                        //Counts the flashcards that would be deleted before asking for confirmation
+                        folders::Command::DeleteFolder(folder_id) => {
+                            if let Some(folder_id) = folder_id {
+                                let command = Command::perform(
+                                    count_folder_flashcards(self.db.clone(), folder_id),
+                                    move |result| match result {
+                                        Ok(affected) => message::app(Message::RequestBulkAction(
+                                            BulkAction::DeleteFolder(folder_id),
+                                            affected,
+                                        )),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    },
+                                );
+                                commands.push(command);
+                            }
+                        }
                     }
                 }
             }
             Message::Flashcards(message) => {
+                //Flashcards edited from the All Flashcards page have to be reloaded there too
+                if matches!(message, flashcards::Message::Upserted)
+                    && self.current_page == Page::AllFlashcards
+                {
+                    commands.push(self.update(Message::AllFlashcards(
+                        all_flashcards::Message::LoadFlashcards,
+                    )));
+                }
+
                 let flashcard_commands = self.flashcards.update(message);
 
                 for flashcard_command in flashcard_commands {
@@ -357,7 +864,58 @@ impl Application for Oboete {
                                     Ok(flashcards) => message::app(Message::Flashcards(
                                         flashcards::Message::SetFlashcards(flashcards),
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+
+                            //The flashcards of the folder are reloaded after being added or deleted
+                            commands.push(command);
+                            commands.push(self.update(Message::FetchCardCounts));
+                        }
+                        flashcards::Command::DebounceSearch(generation) => {
+                            let command = Command::perform(
+                                tokio::time::sleep(std::time::Duration::from_millis(300)),
+                                move |_| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::RunSearch(generation),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::SearchFlashcards(folder_id, query) => {
+                            let command = Command::perform(
+                                search_flashcards(self.db.clone(), folder_id, query),
+                                |result| match result {
+                                    Ok(flashcards) => message::app(Message::Flashcards(
+                                        flashcards::Message::SetSearchResults(flashcards),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::LoadDueCount => {
+                            commands.push(self.update(Message::FetchDueCount));
+                        }
+                        flashcards::Command::LogStudySession => {
+                            let command =
+                                Command::perform(log_study_session(self.db.clone()), |result| {
+                                    match result {
+                                        Ok(_) => message::app(Message::FetchStreak),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    }
+                                });
+                            commands.push(command);
+                        }
+                        flashcards::Command::LoadFolderStats(folder_id) => {
+                            let command = Command::perform(
+                                get_folder_stats(self.db.clone(), folder_id),
+                                |result| match result {
+                                    Ok(stats) => message::app(Message::Flashcards(
+                                        flashcards::Message::SetFolderStats(stats),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
 
@@ -385,7 +943,7 @@ impl Application for Oboete {
                                         Ok(flashcard) => message::app(Message::Flashcards(
                                             flashcards::Message::LoadedSingle(flashcard),
                                         )),
-                                        Err(_) => message::none(),
+                                        Err(err) => message::app(Message::Error(err)),
                                     },
                                 );
                                 commands.push(command);
@@ -394,6 +952,28 @@ impl Application for Oboete {
                             // Set the title of the context drawer.
                             self.set_context_title(ContextPage::CreateEditFlashcard.title());
                         }
+                        flashcards::Command::LoadStudiedFlashcard(flashcard_id) => {
+                            let command = Command::perform(
+                                get_single_flashcard(self.db.clone(), flashcard_id),
+                                |result| match result {
+                                    Ok(flashcard) => message::app(Message::Flashcards(
+                                        flashcards::Message::StudiedFlashcardEdited(flashcard),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::FocusQuickAddFront => {
+                            commands.push(widget::text_input::focus(
+                                self.flashcards.quick_add_front_id.clone(),
+                            ));
+                        }
+                        flashcards::Command::FocusQuickAddBack => {
+                            commands.push(widget::text_input::focus(
+                                self.flashcards.quick_add_back_id.clone(),
+                            ));
+                        }
                         //Upserts a Flashcard inside a Folder
                         flashcards::Command::UpsertFlashcard(flashcard) => {
                             let command = Command::perform(
@@ -402,37 +982,146 @@ impl Application for Oboete {
                                     flashcard,
                                     self.flashcards.current_folder_id,
                                 ),
-                                |_result| {
-                                    message::app(Message::Flashcards(flashcards::Message::Upserted))
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::Upserted,
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             self.core.window.show_context = false;
                             commands.push(command);
                         }
-                        //We select a random (weighted) flashcard and open the page
+                        //Selecting the active studyset again brings back its folders and title
+                        flashcards::Command::OpenFolders => {
+                            self.flashcards.leave_session();
+                            self.current_page = Page::Folders;
+                            let entity = self.nav.active();
+                            commands.push(self.on_nav_select(entity));
+                        }
+                        //We select a random (weighted) due flashcard and open the page
+                        flashcards::Command::OpenFolder(folder_id) => {
+                            commands.push(
+                                self.update(Message::Folders(folders::Message::OpenFolder(
+                                    folder_id,
+                                ))),
+                            );
+                        }
+                        flashcards::Command::ResumeStudySession(paused) => {
+                            self.pending_resume = None;
+                            self.flashcards.study_labels = self.active_study_labels();
+                            self.flashcards.scheduler = self.active_scheduler();
+                            self.flashcards.interval_limits = self.active_interval_limits();
+                            self.flashcards.typo_tolerance = self.config.answer_typo_tolerance;
+                            self.flashcards.session_length = self.config.session_length;
+                            self.flashcards.resume_session(paused);
+                            self.current_page = Page::StudyFolderFlashcards
+                        }
                         flashcards::Command::OpenStudyFolderFlashcardsPage => {
-                            self.flashcards.currently_studying_flashcard =
-                                select_random_flashcard(&self.flashcards.flashcards)
-                                    .unwrap_or(crate::models::Flashcard::new_error_variant());
+                            self.flashcards.study_labels = self.active_study_labels();
+                            self.flashcards.scheduler = self.active_scheduler();
+                            self.flashcards.interval_limits = self.active_interval_limits();
+                            self.flashcards.typo_tolerance = self.config.answer_typo_tolerance;
+                            self.flashcards.session_length = self.config.session_length;
+                            self.flashcards.start_session();
                             self.current_page = Page::StudyFolderFlashcards
                         }
+                        flashcards::Command::ShowChoiceFeedback => {
+                            let command = Command::perform(
+                                tokio::time::sleep(std::time::Duration::from_millis(800)),
+                                |_| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::GradeChoice,
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::SaveStudyPreferences(folder_id, preferences) => {
+                            let command = Command::perform(
+                                set_folder_study_preferences(
+                                    self.db.clone(),
+                                    folder_id,
+                                    preferences,
+                                ),
+                                |result| match result {
+                                    Ok(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
                         //Update the status on the db and return the folder flashcards once again (with the updated status)
-                        flashcards::Command::UpdateFlashcardStatus(flashcard) => {
+                        flashcards::Command::UpdateFlashcardStatus(flashcard, grade) => {
                             let command = Command::perform(
-                                update_flashcard_status(
+                                grade_flashcard(
                                     self.db.clone(),
                                     flashcard,
-                                    self.flashcards.current_folder_id,
+                                    grade,
+                                    self.flashcards.study_scope(),
                                 ),
                                 |result| match result {
                                     Ok(flashcards) => message::app(Message::Flashcards(
                                         flashcards::Message::UpdatedStatus(flashcards),
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::RestoreFlashcardStatus(flashcard) => {
+                            let flashcard_id = flashcard.id.unwrap_or_default();
+                            let command = Command::perform(
+                                undo_flashcard_grade(
+                                    self.db.clone(),
+                                    flashcard,
+                                    self.flashcards.study_scope(),
+                                ),
+                                move |result| match result {
+                                    Ok(flashcards) => message::app(Message::Flashcards(
+                                        flashcards::Message::RestoredStatus(
+                                            flashcard_id,
+                                            flashcards,
+                                        ),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::ConfirmDelete(front) => {
+                            self.dialog_pages
+                                .push_back(DialogPage::DeleteFlashcard(front));
+                        }
+                        flashcards::Command::CheckDuplicate(folder_id, front) => {
+                            let command = Command::perform(
+                                flashcard_exists(self.db.clone(), folder_id, front),
+                                |result| match result {
+                                    Ok(exists) => message::app(Message::Flashcards(
+                                        flashcards::Message::DuplicateChecked(exists),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             commands.push(command);
                         }
+                        flashcards::Command::ConfirmDuplicate(front) => {
+                            self.dialog_pages
+                                .push_back(DialogPage::DuplicateFlashcard(front));
+                        }
+                        //Deleting many flashcards is always confirmed, whatever the bulk threshold
+                        flashcards::Command::ConfirmDeleteSelected(affected) => {
+                            self.dialog_pages.push_back(DialogPage::ConfirmBulkAction {
+                                action: BulkAction::DeleteSelectedFlashcards,
+                                affected,
+                            });
+                        }
+                        flashcards::Command::PickMoveDestination(flashcard_id) => {
+                            self.dialog_pages.push_back(DialogPage::MoveFlashcard {
+                                flashcard_id,
+                                target: None,
+                            });
+                        }
                         flashcards::Command::DeleteFlashcard(flashcard_id) => {
                             let command = Command::perform(
                                 delete_flashcard(self.db.clone(), flashcard_id.unwrap()),
@@ -440,7 +1129,7 @@ impl Application for Oboete {
                                     Ok(_) => message::app(Message::Flashcards(
                                         flashcards::Message::LoadFlashcards,
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             commands.push(command);
@@ -458,6 +1147,55 @@ impl Application for Oboete {
                             // Set the title of the context drawer.
                             self.set_context_title(ContextPage::FlashcardOptions.title());
                         }
+                        flashcards::Command::SetFavorite(flashcard_id, favorite) => {
+                            let command = Command::perform(
+                                set_flashcard_favorite(self.db.clone(), flashcard_id, favorite),
+                                |result| match result {
+                                    Ok(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::LoadFlashcardHistory(flashcard_id) => {
+                            let command = Command::perform(
+                                get_flashcard_history(self.db.clone(), flashcard_id),
+                                |result| match result {
+                                    Ok(history) => message::app(Message::Flashcards(
+                                        flashcards::Message::HistoryLoaded(history),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenHistoryPage => {
+                            self.context_page = ContextPage::FlashcardHistory;
+                            self.core.window.show_context = true;
+                            self.set_context_title(ContextPage::FlashcardHistory.title());
+                        }
+                        flashcards::Command::TogglePasteImportPage => {
+                            if self.context_page == ContextPage::PasteImport {
+                                self.core.window.show_context = !self.core.window.show_context;
+                            } else {
+                                self.context_page = ContextPage::PasteImport;
+                                self.core.window.show_context = true;
+                            }
+
+                            self.set_context_title(ContextPage::PasteImport.title());
+                        }
+                        flashcards::Command::ImportTsv(folder_id, text) => {
+                            let command = Command::perform(
+                                import_tsv(self.db.clone(), folder_id, text),
+                                |result| match result {
+                                    Ok(summary) => message::app(Message::Flashcards(
+                                        flashcards::Message::PasteImported(summary),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
                         flashcards::Command::ImportFlashcards(flashcards) => {
                             let command = Command::perform(
                                 import_flashcards(
@@ -465,50 +1203,92 @@ impl Application for Oboete {
                                     flashcards,
                                     self.flashcards.current_folder_id,
                                 ),
-                                |_result| {
-                                    message::app(Message::Flashcards(flashcards::Message::Upserted))
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::Upserted,
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
                             self.core.window.show_context = false;
                             commands.push(command);
                         }
-                        flashcards::Command::RestartSingleFlashcardStatus(flashcard_id) => {
+                        flashcards::Command::ImportQuizlet(content, term_sep, card_sep) => {
                             let command = Command::perform(
-                                reset_single_flashcard_status(self.db.clone(), flashcard_id),
+                                import_quizlet(
+                                    self.db.clone(),
+                                    self.flashcards.current_folder_id,
+                                    content,
+                                    term_sep,
+                                    card_sep,
+                                ),
                                 |result| match result {
                                     Ok(_) => message::app(Message::Flashcards(
-                                        flashcards::Message::LoadFlashcards,
+                                        flashcards::Message::Upserted,
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
-
                             self.core.window.show_context = false;
                             commands.push(command);
                         }
-                        flashcards::Command::RestartFolderFlashcardStatus(folder_id) => {
+                        flashcards::Command::FindReplace(
+                            folder_id,
+                            find,
+                            replace,
+                            sides,
+                            case_sensitive,
+                        ) => {
+                            let command = Command::perform(
+                                find_replace_flashcards(
+                                    self.db.clone(),
+                                    folder_id,
+                                    find,
+                                    replace,
+                                    sides,
+                                    case_sensitive,
+                                ),
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::Replaced,
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::RestartSingleFlashcardStatus(flashcard_id) => {
                             let command = Command::perform(
-                                reset_folder_flashcard_status(self.db.clone(), Some(folder_id)),
+                                reset_single_flashcard_status(self.db.clone(), flashcard_id),
                                 |result| match result {
                                     Ok(_) => message::app(Message::Flashcards(
                                         flashcards::Message::LoadFlashcards,
                                     )),
-                                    Err(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
                                 },
                             );
 
                             self.core.window.show_context = false;
                             commands.push(command);
                         }
+                        flashcards::Command::RestartFolderFlashcardStatus(folder_id) => {
+                            let affected = self.flashcards.flashcards.len();
+                            commands.push(self.update(Message::RequestBulkAction(
+                                BulkAction::ResetFolderFlashcards(folder_id),
+                                affected,
+                            )));
+                        }
                         flashcards::Command::OpenAnkiFileSelection => {
                             let command = Command::perform(
                                 async move {
                                     let result = SelectedFiles::open_file()
-                                        .title("Open Anki File")
-                                        .accept_label("Open")
+                                        .title(fl!("open-anki-file").as_str())
+                                        .accept_label(fl!("open").as_str())
                                         .modal(true)
                                         .multiple(false)
-                                        .filter(FileFilter::new("TXT File").glob("*.txt"))
+                                        .filter(
+                                            FileFilter::new(fl!("txt-file").as_str()).glob("*.txt"),
+                                        )
                                         .send()
                                         .await
                                         .unwrap()
@@ -532,14 +1312,17 @@ impl Application for Oboete {
                             );
                             commands.push(command);
                         }
-                        flashcards::Command::OpenFolderExportDestination => {
+                        flashcards::Command::OpenCsvFileSelection => {
                             let command = Command::perform(
                                 async move {
-                                    let result = SelectedFiles::save_file()
-                                        .title("Save Export")
-                                        .accept_label("Save")
+                                    let result = SelectedFiles::open_file()
+                                        .title(fl!("open-csv-file").as_str())
+                                        .accept_label(fl!("open").as_str())
                                         .modal(true)
-                                        .filter(FileFilter::new("TXT File").glob("*.txt"))
+                                        .multiple(false)
+                                        .filter(
+                                            FileFilter::new(fl!("csv-file").as_str()).glob("*.csv"),
+                                        )
                                         .send()
                                         .await
                                         .unwrap()
@@ -557,38 +1340,503 @@ impl Application for Oboete {
                                 },
                                 |files| {
                                     message::app(Message::Flashcards(
-                                        flashcards::Message::OpenFolderExportDestinationResult(
-                                            files,
-                                        ),
+                                        flashcards::Message::OpenCsvFileResult(files),
                                     ))
                                 },
                             );
                             commands.push(command);
                         }
-                    }
-                }
-            }
-            Message::FetchStudySets => {
-                commands.push(Command::perform(
-                    get_all_studysets(self.db.clone()),
-                    |result| match result {
-                        Ok(data) => message::app(Message::PopulateStudySets(data)),
-                        Err(_) => message::none(),
-                    },
-                ));
-            }
-            Message::PopulateStudySets(studysets) => {
-                for set in studysets {
-                    self.create_nav_item(set);
-                }
-                let Some(entity) = self.nav.iter().next() else {
-                    return Command::none();
-                };
-                self.nav.activate(entity);
-                let command = self.on_nav_select(entity);
-                commands.push(command);
-            }
-            Message::OpenNewStudySetDialog => {
+                        flashcards::Command::ImportCsvFlashcards(content) => {
+                            let command = Command::perform(
+                                import_flashcards_csv(
+                                    self.db.clone(),
+                                    content,
+                                    self.flashcards.current_folder_id,
+                                ),
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::Upserted,
+                                    )),
+                                    Err(err) => message::app(Message::Flashcards(
+                                        flashcards::Message::ImportFailed(err),
+                                    )),
+                                },
+                            );
+                            self.core.window.show_context = false;
+                            commands.push(command);
+                        }
+                        flashcards::Command::ShowError(error) => {
+                            commands.push(self.update(Message::Error(error)));
+                        }
+                        flashcards::Command::OpenFolderExportDestination => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::save_file()
+                                        .title(fl!("save-export").as_str())
+                                        .accept_label(fl!("save").as_str())
+                                        .modal(true)
+                                        .filter(
+                                            FileFilter::new(fl!("txt-file").as_str()).glob("*.txt"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenFolderExportDestinationResult(
+                                            files,
+                                        ),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenTestSheetExportDestination => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::save_file()
+                                        .title(fl!("save-test-sheet").as_str())
+                                        .accept_label(fl!("save").as_str())
+                                        .modal(true)
+                                        .filter(
+                                            FileFilter::new(fl!("markdown-file").as_str())
+                                                .glob("*.md"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenTestSheetExportDestinationResult(
+                                            files,
+                                        ),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenPdfExportDestination => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::save_file()
+                                        .title(fl!("save-pdf").as_str())
+                                        .accept_label(fl!("save").as_str())
+                                        .modal(true)
+                                        .filter(
+                                            FileFilter::new(fl!("pdf-file").as_str()).glob("*.pdf"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenPdfExportDestinationResult(files),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::ExportFolderPdf(folder_id, path, layout) => {
+                            let command = Command::perform(
+                                export_folder_pdf(self.db.clone(), folder_id, path, layout),
+                                |result| match result {
+                                    Ok(_) => message::none(),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenSyncSourceSelection => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::open_file()
+                                        .title(fl!("open-source-file").as_str())
+                                        .accept_label(fl!("open").as_str())
+                                        .modal(true)
+                                        .multiple(false)
+                                        .filter(
+                                            FileFilter::new(fl!("tsv-file").as_str())
+                                                .glob("*.tsv")
+                                                .glob("*.txt"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenSyncSourceResult(files),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenImageFileSelection => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::open_file()
+                                        .title(fl!("open-image").as_str())
+                                        .accept_label(fl!("open").as_str())
+                                        .modal(true)
+                                        .multiple(false)
+                                        .filter(
+                                            FileFilter::new(fl!("image-file").as_str())
+                                                .mimetype("image/png")
+                                                .mimetype("image/jpeg")
+                                                .mimetype("image/gif")
+                                                .mimetype("image/webp"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenImageFileResult(files),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::OpenAudioFileSelection => {
+                            let command = Command::perform(
+                                async move {
+                                    let result = SelectedFiles::open_file()
+                                        .title(fl!("open-audio").as_str())
+                                        .accept_label(fl!("open").as_str())
+                                        .modal(true)
+                                        .multiple(false)
+                                        .filter(
+                                            FileFilter::new(fl!("audio-file").as_str())
+                                                .mimetype("audio/mpeg")
+                                                .mimetype("audio/ogg")
+                                                .mimetype("audio/flac")
+                                                .mimetype("audio/wav"),
+                                        )
+                                        .send()
+                                        .await
+                                        .unwrap()
+                                        .response();
+
+                                    if let Ok(result) = result {
+                                        result
+                                            .uris()
+                                            .iter()
+                                            .map(|file| file.path().to_string())
+                                            .collect::<Vec<String>>()
+                                    } else {
+                                        Vec::new()
+                                    }
+                                },
+                                |files| {
+                                    message::app(Message::Flashcards(
+                                        flashcards::Message::OpenAudioFileResult(files),
+                                    ))
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        flashcards::Command::StoreFlashcardAudio(path) => {
+                            match store_attachment(Self::APP_ID, "audio", &path) {
+                                Ok(audio_path) => {
+                                    commands.push(self.update(Message::Flashcards(
+                                        flashcards::Message::SetAudio(Some(audio_path)),
+                                    )));
+                                }
+                                Err(err) => commands.push(self.update(Message::Error(err))),
+                            }
+                        }
+                        flashcards::Command::StoreFlashcardImage(path) => {
+                            match store_attachment(Self::APP_ID, "images", &path) {
+                                Ok(image_path) => {
+                                    commands.push(self.update(Message::Flashcards(
+                                        flashcards::Message::SetImage(Some(image_path)),
+                                    )));
+                                }
+                                Err(err) => commands.push(self.update(Message::Error(err))),
+                            }
+                        }
+                        flashcards::Command::SetFolderSource(folder_id, source_path) => {
+                            let command = Command::perform(
+                                set_folder_source_path(
+                                    self.db.clone(),
+                                    folder_id,
+                                    Some(source_path.clone()),
+                                ),
+                                move |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::SetFolderSource(Some(source_path)),
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        //Deleting the flashcards missing from the source file always needs confirmation
+                        flashcards::Command::SyncFromSource {
+                            folder_id,
+                            delete_missing,
+                            missing,
+                        } => {
+                            let action = BulkAction::SyncFolder {
+                                folder_id,
+                                delete_missing,
+                            };
+
+                            if missing > 0 {
+                                self.dialog_pages.push_back(DialogPage::ConfirmBulkAction {
+                                    action,
+                                    affected: missing,
+                                });
+                            } else {
+                                commands.push(self.update(Message::RunBulkAction(action)));
+                            }
+                        }
+                    }
+                }
+            }
+            Message::FetchStudySets => {
+                commands.push(Command::perform(
+                    get_all_studysets(self.db.clone()),
+                    |result| match result {
+                        Ok(data) => message::app(Message::PopulateStudySets(data)),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                ));
+                commands.push(self.update(Message::FetchCardCounts));
+            }
+            Message::PopulateStudySets(studysets) => {
+                for set in studysets {
+                    self.create_nav_item(set);
+                }
+
+                //Restore the last selected studyset (and folder) if there was one
+                let last_page = self.config.last_page;
+                let last_folder_id = self.config.last_folder_id;
+                let last_entity = self.nav.iter().find(|entity| {
+                    self.nav.data::<StudySet>(*entity).is_some_and(|set| {
+                        set.id.is_some() && set.id == self.config.last_studyset_id
+                    })
+                });
+                self.state_restored = true;
+
+                let Some(entity) = last_entity.or(self.nav.iter().next()) else {
+                    return Command::none();
+                };
+                self.nav.activate(entity);
+                let command = self.on_nav_select(entity);
+                commands.push(command);
+
+                //The paused session is only offered if its folder is still in the restored studyset
+                self.pending_resume = self.config.paused_session.clone();
+                if let (Some(studyset_id), Some(paused)) = (
+                    last_entity.and(self.config.last_studyset_id),
+                    &self.pending_resume,
+                ) {
+                    let folder_id = paused.folder_id;
+                    commands.push(Command::perform(
+                        get_studyset_folders(self.db.clone(), studyset_id),
+                        move |result| match result {
+                            Ok(studyset_folders)
+                                if studyset_folders
+                                    .iter()
+                                    .any(|folder| folder.id == Some(folder_id)) =>
+                            {
+                                message::app(Message::OfferSessionResume)
+                            }
+                            _ => message::app(Message::ForgetPausedSession),
+                        },
+                    ));
+                } else {
+                    self.pending_resume = None;
+                }
+
+                if last_page == Page::AllFlashcards {
+                    commands.push(self.update(Message::OpenAllFlashcards));
+                } else if last_page != Page::Folders {
+                    //The folder is only reopened if it still belongs to the restored studyset
+                    if let (Some(studyset_id), Some(folder_id)) = (
+                        last_entity.and(self.config.last_studyset_id),
+                        last_folder_id,
+                    ) {
+                        commands.push(Command::perform(
+                            get_studyset_folders(self.db.clone(), studyset_id),
+                            move |result| match result {
+                                Ok(studyset_folders)
+                                    if studyset_folders
+                                        .iter()
+                                        .any(|folder| folder.id == Some(folder_id)) =>
+                                {
+                                    message::app(Message::Folders(folders::Message::OpenFolder(
+                                        folder_id,
+                                    )))
+                                }
+                                _ => message::none(),
+                            },
+                        ));
+                    }
+                }
+            }
+            Message::OpenAllFlashcards => {
+                self.current_page = Page::AllFlashcards;
+                self.flashcards.leave_session();
+
+                let window_title = format!("Oboete - {}", fl!("all-flashcards"));
+                commands.push(self.set_window_title(window_title.clone()));
+                self.set_header_title(window_title);
+
+                commands.push(self.update(Message::AllFlashcards(
+                    all_flashcards::Message::LoadFlashcards,
+                )));
+            }
+            Message::StudyAllDue => {
+                let command =
+                    Command::perform(
+                        get_all_due_flashcards(self.db.clone()),
+                        |result| match result {
+                            Ok(flashcards) => message::app(Message::AllDueLoaded(flashcards)),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                commands.push(command);
+            }
+            Message::AllDueLoaded(flashcards) => {
+                let schedulers = flashcards
+                    .iter()
+                    .filter_map(|location| {
+                        let scheduler = self
+                            .config
+                            .studyset_schedulers
+                            .get(&location.studyset_id)
+                            .copied()
+                            .unwrap_or_default();
+                        let limits = self
+                            .config
+                            .studyset_interval_limits
+                            .get(&location.studyset_id)
+                            .copied()
+                            .unwrap_or_default();
+                        location.flashcard.id.map(|id| (id, (scheduler, limits)))
+                    })
+                    .collect();
+
+                self.flashcards.leave_session();
+                self.flashcards.study_labels = StudyLabels::default();
+                self.flashcards.typo_tolerance = self.config.answer_typo_tolerance;
+                self.flashcards.session_length = self.config.session_length;
+                self.flashcards
+                    .start_all_due_session(flashcards, schedulers);
+                self.current_page = Page::StudyFolderFlashcards;
+
+                let window_title = format!("Oboete - {}", fl!("study-all-due"));
+                commands.push(self.set_window_title(window_title.clone()));
+                self.set_header_title(window_title);
+            }
+            Message::AllFlashcards(message) => {
+                let all_flashcards_commands = self.all_flashcards.update(message);
+
+                for all_flashcards_command in all_flashcards_commands {
+                    match all_flashcards_command {
+                        all_flashcards::Command::LoadFlashcards => {
+                            let command =
+                                Command::perform(get_all_flashcards(self.db.clone()), |result| {
+                                    match result {
+                                        Ok(flashcards) => message::app(Message::AllFlashcards(
+                                            all_flashcards::Message::SetFlashcards(flashcards),
+                                        )),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    }
+                                });
+                            commands.push(command);
+                            commands.push(self.update(Message::FetchCardCounts));
+                        }
+                        //The flashcard is edited with the usual context page, inside of its own folder
+                        all_flashcards::Command::EditFlashcard(folder_id, flashcard) => {
+                            self.flashcards.current_folder_id = folder_id;
+                            commands.push(self.update(Message::Flashcards(
+                                flashcards::Message::ToggleCreatePage(Some(flashcard)),
+                            )));
+                        }
+                        all_flashcards::Command::ConfirmDelete(front) => {
+                            self.dialog_pages
+                                .push_back(DialogPage::DeleteFlashcard(front));
+                        }
+                        all_flashcards::Command::DeleteFlashcard(flashcard_id) => {
+                            if let Some(flashcard_id) = flashcard_id {
+                                let command = Command::perform(
+                                    delete_flashcard(self.db.clone(), flashcard_id),
+                                    |result| match result {
+                                        Ok(_) => message::app(Message::AllFlashcards(
+                                            all_flashcards::Message::LoadFlashcards,
+                                        )),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    },
+                                );
+                                commands.push(command);
+                            }
+                        }
+                    }
+                }
+            }
+            Message::OpenNewStudySetDialog => {
                 self.dialog_pages
                     .push_back(DialogPage::NewStudySet(String::new()));
                 return widget::text_input::focus(self.dialog_text_input.clone());
@@ -606,99 +1854,909 @@ impl Application for Oboete {
                     self.dialog_pages.push_back(DialogPage::DeleteStudySet);
                 }
             }
+            //The copy is added to the nav bar and opened
+            Message::DuplicateStudySet => {
+                if let Some(studyset_id) = self.active_studyset_id() {
+                    let db = self.db.clone();
+                    let command = Command::perform(
+                        async move {
+                            let copy_id = duplicate_studyset(db.clone(), studyset_id).await?;
+                            get_all_studysets(db)
+                                .await?
+                                .into_iter()
+                                .find(|set| set.id == Some(copy_id))
+                                .ok_or_else(|| {
+                                    OboeteError::NotFound(String::from(
+                                        "The studyset couldn't be duplicated",
+                                    ))
+                                })
+                        },
+                        |result| match result {
+                            Ok(set) => message::app(Message::AddStudySet(set)),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::BackupDatabase => commands.push(self.backup_database(true)),
+            Message::BackupCreated(path) => {
+                self.dialog_pages.push_back(DialogPage::BackupCreated(path));
+            }
+            //Counts the flashcards that would be reset before asking for confirmation
+            Message::ResetStudySetProgress => {
+                if let Some(studyset_id) = self.active_studyset_id() {
+                    let command = Command::perform(
+                        count_studyset_flashcards(self.db.clone(), studyset_id),
+                        move |result| match result {
+                            Ok(affected) => message::app(Message::RequestBulkAction(
+                                BulkAction::ResetStudySetFlashcards(studyset_id),
+                                affected,
+                            )),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ExportStudySet => {
+                if self.active_studyset_id().is_some() {
+                    let command = Command::perform(
+                        async move {
+                            let result = SelectedFiles::save_file()
+                                .title(fl!("export-studyset").as_str())
+                                .accept_label(fl!("save").as_str())
+                                .modal(true)
+                                .filter(FileFilter::new(fl!("json-file").as_str()).glob("*.json"))
+                                .send()
+                                .await
+                                .unwrap()
+                                .response();
+
+                            if let Ok(result) = result {
+                                result
+                                    .uris()
+                                    .iter()
+                                    .map(|file| file.path().to_string())
+                                    .collect::<Vec<String>>()
+                            } else {
+                                Vec::new()
+                            }
+                        },
+                        |files| message::app(Message::ExportStudySetResult(files)),
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ExportStudySetResult(files) => {
+                if let (Some(path), Some(studyset_id)) = (files.first(), self.active_studyset_id())
+                {
+                    let db = self.db.clone();
+                    let path = decode_file_path(path);
+                    let command = Command::perform(
+                        async move {
+                            let json = export_studyset(db, studyset_id).await?;
+                            std::fs::write(path, json)?;
+                            Ok(())
+                        },
+                        |result: Result<(), OboeteError>| match result {
+                            Ok(_) => message::none(),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ExportAll => {
+                let command = Command::perform(
+                    async move {
+                        let result = SelectedFiles::save_file()
+                            .title(fl!("export-all-studysets").as_str())
+                            .accept_label(fl!("save").as_str())
+                            .modal(true)
+                            .filter(FileFilter::new(fl!("json-file").as_str()).glob("*.json"))
+                            .send()
+                            .await
+                            .unwrap()
+                            .response();
+
+                        if let Ok(result) = result {
+                            result
+                                .uris()
+                                .iter()
+                                .map(|file| file.path().to_string())
+                                .collect::<Vec<String>>()
+                        } else {
+                            Vec::new()
+                        }
+                    },
+                    |files| message::app(Message::ExportAllResult(files)),
+                );
+                commands.push(command);
+            }
+            Message::ExportAllResult(files) => {
+                if let Some(path) = files.first() {
+                    let db = self.db.clone();
+                    let path = decode_file_path(path);
+                    let command = Command::perform(
+                        async move {
+                            let json = export_all(db).await?;
+                            std::fs::write(path, json)?;
+                            Ok(())
+                        },
+                        |result: Result<(), OboeteError>| match result {
+                            Ok(_) => message::none(),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ImportAll => {
+                let command = Command::perform(
+                    async move {
+                        let result = SelectedFiles::open_file()
+                            .title(fl!("import-studysets").as_str())
+                            .accept_label(fl!("open").as_str())
+                            .modal(true)
+                            .multiple(false)
+                            .filter(FileFilter::new(fl!("json-file").as_str()).glob("*.json"))
+                            .send()
+                            .await
+                            .unwrap()
+                            .response();
+
+                        if let Ok(result) = result {
+                            result
+                                .uris()
+                                .iter()
+                                .map(|file| file.path().to_string())
+                                .collect::<Vec<String>>()
+                        } else {
+                            Vec::new()
+                        }
+                    },
+                    |files| message::app(Message::ImportAllResult(files)),
+                );
+                commands.push(command);
+            }
+            Message::ImportAllResult(files) => {
+                if let Some(path) = files.first() {
+                    let db = self.db.clone();
+                    let path = decode_file_path(path);
+                    let command = Command::perform(
+                        async move {
+                            let json = std::fs::read_to_string(path)?;
+                            import_all(db, json).await
+                        },
+                        |result| match result {
+                            Ok(studysets) => message::app(Message::ImportedStudySets(studysets)),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ImportAnkiPackage => {
+                let command = Command::perform(
+                    async move {
+                        let result = SelectedFiles::open_file()
+                            .title(fl!("open-anki-package").as_str())
+                            .accept_label(fl!("open").as_str())
+                            .modal(true)
+                            .multiple(false)
+                            .filter(FileFilter::new(fl!("anki-package").as_str()).glob("*.apkg"))
+                            .send()
+                            .await
+                            .unwrap()
+                            .response();
+
+                        if let Ok(result) = result {
+                            result
+                                .uris()
+                                .iter()
+                                .map(|file| file.path().to_string())
+                                .collect::<Vec<String>>()
+                        } else {
+                            Vec::new()
+                        }
+                    },
+                    |files| message::app(Message::ImportAnkiPackageResult(files)),
+                );
+                commands.push(command);
+            }
+            Message::ImportAnkiPackageResult(files) => {
+                if let Some(path) = files.first() {
+                    let db = self.db.clone();
+                    let path = decode_file_path(path);
+                    let command = Command::perform(
+                        async move {
+                            let studysets = import_apkg(path).await?;
+                            import_studysets(db, studysets).await
+                        },
+                        |result| match result {
+                            Ok(studysets) => message::app(Message::ImportedStudySets(studysets)),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+            }
+            Message::ImportedStudySets(studysets) => {
+                for set in studysets {
+                    commands.push(self.update(Message::AddStudySet(set)));
+                }
+                commands.push(self.update(Message::FetchCardCounts));
+            }
             Message::DialogComplete => {
                 if let Some(dialog_page) = self.dialog_pages.pop_front() {
                     match dialog_page {
+                        //Submitting an invalid name keeps the dialog open with the error
                         DialogPage::NewStudySet(name) => {
-                            if name.is_empty() == false {
-                                let set = StudySet::new(name);
-                                commands.push(Command::perform(
-                                    upsert_studyset(self.db.clone(), set),
+                            match validate_new_name(&name, &self.studyset_names()) {
+                                Ok(name) => commands.push(Command::perform(
+                                    create_studyset(self.db.clone(), name),
                                     |result| match result {
                                         Ok(set) => message::app(Message::AddStudySet(set)),
-                                        Err(_) => message::none(),
+                                        Err(err) => message::app(Message::Error(err)),
                                     },
-                                ));
+                                )),
+                                Err(_) => {
+                                    self.dialog_pages.push_front(DialogPage::NewStudySet(name))
+                                }
                             }
                         }
+                        //The nav bar is only updated once the new name has been validated and saved
                         DialogPage::RenameStudySet { to: name } => {
-                            if name.is_empty() == false {
-                                let entity = self.nav.active();
-                                self.nav.text_set(entity, name.clone());
-                                if let Some(set) = self.nav.active_data_mut::<StudySet>() {
-                                    set.name = name.clone();
-                                    let command = Command::perform(
-                                        upsert_studyset(self.db.clone(), set.to_owned().clone()),
-                                        |_| message::none(),
-                                    );
-                                    commands.push(command);
-                                }
+                            if let Some(studyset_id) = self.active_studyset_id() {
+                                let command = Command::perform(
+                                    rename_studyset(self.db.clone(), studyset_id, name),
+                                    move |result| match result {
+                                        Ok(name) => message::app(Message::StudySetRenamed(
+                                            studyset_id,
+                                            name,
+                                        )),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    },
+                                );
+                                commands.push(command);
                             }
                         }
                         DialogPage::DeleteStudySet => {
                             commands.push(self.update(Message::DeleteStudySet));
                         }
-                        DialogPage::NewFolder(name) => {
-                            if name.is_empty() == false {
-                                let folder = Folder::new(name);
-                                commands.push(Command::perform(
-                                    upsert_folder(
-                                        self.db.clone(),
-                                        folder,
-                                        self.folders.current_studyset_id.unwrap(),
-                                    ),
-                                    |result| match result {
-                                        Ok(_folder_id) => message::app(Message::Folders(
-                                            folders::Message::Upserted,
-                                        )),
-                                        Err(_) => message::none(),
-                                    },
-                                ));
-                            }
+                        DialogPage::ConfirmBulkAction { action, .. } => {
+                            commands.push(self.update(Message::RunBulkAction(action)));
+                        }
+                        DialogPage::KeyboardShortcuts | DialogPage::BackupCreated(_) => {}
+                        //The flashcard waiting for confirmation is kept by the page that asked for it
+                        DialogPage::DeleteFlashcard(_) => {
+                            let message = if self.current_page == Page::AllFlashcards {
+                                Message::AllFlashcards(all_flashcards::Message::Delete(
+                                    self.all_flashcards.pending_delete,
+                                ))
+                            } else {
+                                Message::Flashcards(flashcards::Message::Delete(
+                                    self.flashcards.pending_delete,
+                                ))
+                            };
+                            commands.push(self.update(message));
+                        }
+                        //The moved flashcard disappears from the list once the folder is reloaded
+                        DialogPage::MoveFlashcard {
+                            flashcard_id,
+                            target: Some(target),
+                        } => {
+                            let command = Command::perform(
+                                move_flashcard(self.db.clone(), flashcard_id, target),
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Flashcards(
+                                        flashcards::Message::LoadFlashcards,
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        DialogPage::MoveFlashcard { target: None, .. } => {}
+                        DialogPage::MergeFolder {
+                            source_id,
+                            target: Some(target),
+                        } => {
+                            let command = Command::perform(
+                                merge_folders(self.db.clone(), source_id, target),
+                                |result| match result {
+                                    Ok(_) => message::app(Message::Folders(
+                                        folders::Message::LoadFolders,
+                                    )),
+                                    Err(err) => message::app(Message::Error(err)),
+                                },
+                            );
+                            commands.push(command);
+                        }
+                        DialogPage::MergeFolder { target: None, .. } => {}
+                        DialogPage::DuplicateFlashcard(_) => {
+                            commands.push(
+                                self.update(Message::Flashcards(flashcards::Message::AddDuplicate)),
+                            );
+                        }
+                        DialogPage::SyncConflict(direction) => {
+                            commands.push(self.update(Message::Sync(direction, true)));
+                        }
+                        //The session goes on once the flashcards of its folder are loaded
+                        DialogPage::ResumeSession => {
+                            if let Some(paused) = self.pending_resume.clone() {
+                                let folder_id = paused.folder_id;
+                                self.flashcards.resuming_session = Some(paused);
+                                commands.push(self.update(Message::Folders(
+                                    folders::Message::OpenFolder(folder_id),
+                                )));
+                            }
+                        }
+                        DialogPage::DiscardFlashcardEdit => {
+                            self.flashcards.discard_edit();
+                            if let Some(message) = self.pending_navigation.take() {
+                                commands.push(self.update(message));
+                            }
+                        }
+                        DialogPage::NewFolder(name) => {
+                            match validate_new_name(&name, &self.folder_names()) {
+                                Ok(name) => commands.push(Command::perform(
+                                    create_folder(
+                                        self.db.clone(),
+                                        name,
+                                        self.folders.current_studyset_id.unwrap(),
+                                    ),
+                                    |result| match result {
+                                        Ok(_folder_id) => message::app(Message::Folders(
+                                            folders::Message::Upserted,
+                                        )),
+                                        Err(err) => message::app(Message::Error(err)),
+                                    },
+                                )),
+                                Err(_) => self.dialog_pages.push_front(DialogPage::NewFolder(name)),
+                            }
+                        }
+                    }
+                }
+            }
+            Message::DialogUpdate(dialog_page) => {
+                self.dialog_pages[0] = dialog_page;
+            }
+            Message::DialogCancel => match self.dialog_pages.pop_front() {
+                Some(DialogPage::DeleteFlashcard(_)) => {
+                    self.flashcards.pending_delete = None;
+                    self.all_flashcards.pending_delete = None;
+                }
+                Some(DialogPage::DuplicateFlashcard(_)) => self.flashcards.pending_duplicate = None,
+                Some(DialogPage::DiscardFlashcardEdit) => self.pending_navigation = None,
+                Some(DialogPage::ResumeSession) => self.pending_resume = None,
+                _ => {}
+            },
+            Message::AddStudySet(set) => {
+                self.create_nav_item(set);
+                let Some(entity) = self.nav.iter().last() else {
+                    return Command::none();
+                };
+                let command = self.on_nav_select(entity);
+                commands.push(command);
+            }
+            Message::DeleteStudySet => {
+                if let Some(set) = self.nav.data::<StudySet>(self.nav.active()) {
+                    let command = Command::perform(
+                        delete_studyset(self.db.clone(), set.id.unwrap()),
+                        |result| match result {
+                            Ok(_) => message::none(),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+
+                    self.folders.current_studyset_id = None;
+                    self.folders.current_studyset_color = None;
+                    commands.push(self.update(Message::Folders(folders::Message::LoadFolders)));
+                    commands.push(command);
+                }
+                self.nav.remove(self.nav.active());
+            }
+            Message::OpenNewFolderDialog => {
+                self.dialog_pages
+                    .push_back(DialogPage::NewFolder(String::new()));
+                return widget::text_input::focus(self.dialog_text_input.clone());
+            }
+            //Small batches run instantly, big ones need to be confirmed first
+            Message::RequestBulkAction(action, affected) => {
+                if self.config.needs_bulk_confirmation(affected) {
+                    self.dialog_pages
+                        .push_back(DialogPage::ConfirmBulkAction { action, affected });
+                } else {
+                    commands.push(self.update(Message::RunBulkAction(action)));
+                }
+            }
+            Message::RunBulkAction(action) => match action {
+                BulkAction::DeleteSelectedFlashcards => {
+                    let flashcard_ids = self
+                        .flashcards
+                        .selected_flashcards
+                        .iter()
+                        .copied()
+                        .collect();
+                    let command = Command::perform(
+                        delete_flashcards(self.db.clone(), flashcard_ids),
+                        |result| match result {
+                            Ok(_) => message::app(Message::Flashcards(
+                                flashcards::Message::DeletedSelected,
+                            )),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+                BulkAction::ResetFolderFlashcards(folder_id) => {
+                    let command = Command::perform(
+                        reset_folder_flashcard_status(self.db.clone(), Some(folder_id)),
+                        |result| match result {
+                            Ok(_) => message::app(Message::Flashcards(
+                                flashcards::Message::LoadFlashcards,
+                            )),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+
+                    self.core.window.show_context = false;
+                    commands.push(command);
+                }
+                //The open folder (if any) is reloaded so its stats show everything as unseen
+                BulkAction::ResetStudySetFlashcards(studyset_id) => {
+                    let folder_open = self.current_page == Page::FolderFlashcards;
+                    let command = Command::perform(
+                        reset_studyset_flashcard_status(self.db.clone(), studyset_id),
+                        move |result| match result {
+                            Ok(_) if folder_open => message::app(Message::Flashcards(
+                                flashcards::Message::LoadFlashcards,
+                            )),
+                            Ok(_) => message::none(),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+                    commands.push(command);
+                }
+                BulkAction::SyncFolder {
+                    folder_id,
+                    delete_missing,
+                } => {
+                    let command = Command::perform(
+                        sync_folder_from_source(self.db.clone(), folder_id, delete_missing),
+                        |result| match result {
+                            Ok(_) => message::app(Message::Flashcards(
+                                flashcards::Message::LoadFlashcards,
+                            )),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    );
+
+                    self.core.window.show_context = false;
+                    commands.push(command);
+                }
+                BulkAction::DeleteFolder(folder_id) => {
+                    let command =
+                        Command::perform(delete_folder(self.db.clone(), folder_id), |result| {
+                            match result {
+                                Ok(_) => {
+                                    message::app(Message::Folders(folders::Message::LoadFolders))
+                                }
+                                Err(err) => message::app(Message::Error(err)),
+                            }
+                        });
+                    commands.push(command);
+                }
+            },
+            Message::UpdateBulkConfirmationThreshold(index) => {
+                if let Some(threshold) = BULK_CONFIRMATION_THRESHOLDS.get(index) {
+                    config_set!(self, bulk_confirmation_threshold, *threshold);
+                }
+            }
+            Message::UpdateAnswerTypoTolerance(index) => {
+                if let Some(tolerance) = ANSWER_TYPO_TOLERANCES.get(index) {
+                    config_set!(self, answer_typo_tolerance, *tolerance);
+                    self.flashcards.typo_tolerance = *tolerance;
+                }
+            }
+            Message::UpdateSessionLength(index) => {
+                if let Some(length) = SESSION_LENGTHS.get(index) {
+                    config_set!(self, session_length, *length);
+                }
+            }
+            Message::UpdateMasteredStreak(index) => {
+                if let Some(streak) = MASTERED_STREAKS.get(index) {
+                    config_set!(self, mastered_streak, *streak);
+                    self.flashcards.mastered_streak = *streak;
+                    commands.push(self.update(Message::FetchDueCount));
+                }
+            }
+            Message::UpdateIncludeMastered(value) => {
+                config_set!(self, include_mastered, value);
+                self.flashcards.include_mastered = value;
+                commands.push(self.update(Message::FetchDueCount));
+            }
+            Message::UpdateFlashcardLengthLimit(index) => {
+                if let Some(limit) = FLASHCARD_LENGTH_LIMITS.get(index) {
+                    config_set!(self, flashcard_length_limit, *limit);
+                    self.flashcards.length_limit = *limit;
+                }
+            }
+            Message::UpdateRenderMarkdown(value) => {
+                config_set!(self, render_markdown, value);
+                self.flashcards.render_markdown = value;
+            }
+            Message::UpdateMatchAllTags(value) => {
+                config_set!(self, match_all_tags, value);
+                self.flashcards.match_all_tags = value;
+            }
+            Message::UpdateStudyReminders(value) => {
+                config_set!(self, study_reminders, value);
+            }
+            Message::ToggleReminderWeekday(weekday) => {
+                let mut weekdays = self.config.reminder_weekdays;
+                if let Some(selected) = weekdays.get_mut(weekday) {
+                    *selected = !*selected;
+                    config_set!(self, reminder_weekdays, weekdays);
+                }
+            }
+            Message::UpdateReminderHour(hour) => {
+                config_set!(self, reminder_hour, hour as u32);
+            }
+            Message::UpdateQuietHours(value) => {
+                config_set!(self, quiet_hours, value);
+            }
+            Message::UpdateQuietHoursStart(hour) => {
+                config_set!(self, quiet_hours_start, hour as u32);
+            }
+            Message::UpdateQuietHoursEnd(hour) => {
+                config_set!(self, quiet_hours_end, hour as u32);
+            }
+            Message::CheckStudyReminder => {
+                //The missing database was already reported, it isn't reported again every check
+                if self.db.is_some() {
+                    commands.push(Command::perform(
+                        check_study_reminder(
+                            self.db.clone(),
+                            self.config.reminder_schedule(),
+                            self.config.last_reminder_day,
+                            if self.config.include_mastered {
+                                None
+                            } else {
+                                self.config.mastered_streak
+                            },
+                        ),
+                        |result| match result {
+                            Ok(Some(reminder)) => {
+                                message::app(Message::SendStudyReminder(reminder))
+                            }
+                            Ok(None) => message::none(),
+                            Err(err) => message::app(Message::Error(err)),
+                        },
+                    ));
+                }
+            }
+            Message::SendStudyReminder(reminder) => {
+                //Saved before sending, so a failing notification isn't retried every minute
+                config_set!(self, last_reminder_day, reminder.day);
+                commands.push(Command::perform(
+                    send_study_reminder(
+                        fl!("study-reminder-title"),
+                        fl!("study-reminder-body", count = reminder.due_count),
+                    ),
+                    |result| match result {
+                        Ok(()) => message::none(),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                ));
+            }
+            Message::UpdateStudyLabels(labels) => {
+                if let Some(studyset_id) = self.active_studyset_id() {
+                    let mut studyset_labels = self.config.studyset_labels.clone();
+                    if labels.is_empty() {
+                        studyset_labels.remove(&studyset_id);
+                    } else {
+                        studyset_labels.insert(studyset_id, labels.clone());
+                    }
+                    config_set!(self, studyset_labels, studyset_labels);
+                    self.flashcards.study_labels = labels;
+                }
+            }
+            Message::UpdateStudySetIcon(icon) => {
+                let entity = self.nav.active();
+                self.nav.icon_set(entity, IconCache::get(icon, 16));
+                if let Some(set) = self.nav.active_data_mut::<StudySet>() {
+                    set.icon = String::from(icon);
+                    if let Some(studyset_id) = set.id {
+                        let command = Command::perform(
+                            set_studyset_icon(self.db.clone(), studyset_id, String::from(icon)),
+                            |result| match result {
+                                Ok(_) => message::none(),
+                                Err(err) => message::app(Message::Error(err)),
+                            },
+                        );
+                        commands.push(command);
+                    }
+                }
+            }
+            Message::UpdateStudySetColor(color) => {
+                let color = color.map(String::from);
+                self.folders.current_studyset_color = color.clone();
+                if let Some(set) = self.nav.active_data_mut::<StudySet>() {
+                    set.color = color.clone();
+                    if let Some(studyset_id) = set.id {
+                        let command = Command::perform(
+                            set_studyset_color(self.db.clone(), studyset_id, color),
+                            |result| match result {
+                                Ok(_) => message::none(),
+                                Err(err) => message::app(Message::Error(err)),
+                            },
+                        );
+                        commands.push(command);
+                    }
+                }
+            }
+            Message::UpdateStudySetScheduler(index) => {
+                if let (Some(studyset_id), Some(kind)) =
+                    (self.active_studyset_id(), SCHEDULER_KINDS.get(index))
+                {
+                    let mut studyset_schedulers = self.config.studyset_schedulers.clone();
+                    if *kind == SchedulerKind::default() {
+                        studyset_schedulers.remove(&studyset_id);
+                    } else {
+                        studyset_schedulers.insert(studyset_id, *kind);
+                    }
+                    config_set!(self, studyset_schedulers, studyset_schedulers);
+                    self.flashcards.scheduler = *kind;
+                }
+            }
+            Message::UpdateStudySetMinInterval(index) => {
+                if let Some(days) = MIN_INTERVALS.get(index) {
+                    let limits = IntervalLimits {
+                        min_interval_days: *days,
+                        ..self.active_interval_limits()
+                    };
+                    self.set_interval_limits(limits);
+                }
+            }
+            Message::UpdateStudySetMaxInterval(index) => {
+                if let Some(days) = MAX_INTERVALS.get(index) {
+                    let limits = IntervalLimits {
+                        max_interval_days: *days,
+                        ..self.active_interval_limits()
+                    };
+                    self.set_interval_limits(limits);
+                }
+            }
+            Message::UpdateSyncUrl(url) => {
+                config_set!(self, sync_url, url);
+            }
+            Message::UpdateSyncUsername(username) => {
+                config_set!(self, sync_username, username);
+            }
+            Message::UpdateSyncPassword(password) => {
+                config_set!(self, sync_password, password);
+            }
+            Message::Sync(direction, force) => {
+                let db = self.db.clone();
+                let config = self.config.sync_config();
+                let command = Command::perform(
+                    async move {
+                        match direction {
+                            SyncDirection::Push => push_backup(db, config, force).await,
+                            SyncDirection::Pull => pull_backup(db, config, force).await,
+                        }
+                    },
+                    move |result| match result {
+                        Ok(outcome) => message::app(Message::SyncFinished(direction, outcome)),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                );
+                commands.push(command);
+            }
+            Message::SyncFinished(direction, outcome) => match outcome {
+                SyncOutcome::Conflict => {
+                    self.dialog_pages
+                        .push_back(DialogPage::SyncConflict(direction));
+                }
+                SyncOutcome::Synced(modified_at) => {
+                    config_set!(self, last_sync, modified_at);
+                    //The pulled studysets replace the ones in the nav bar
+                    if direction == SyncDirection::Pull {
+                        while let Some(entity) = self.nav.iter().next() {
+                            self.nav.remove(entity);
                         }
+                        commands.push(self.update(Message::FetchStudySets));
+                        commands.push(self.update(Message::FetchDueCount));
                     }
                 }
+            },
+            Message::Error(err) => {
+                eprintln!("Error: {}", err);
+                self.error_banner = Some(error_message(&err));
             }
-            Message::DialogUpdate(dialog_page) => {
-                self.dialog_pages[0] = dialog_page;
+            Message::DismissError => {
+                self.error_banner = None;
             }
-            Message::DialogCancel => {
-                self.dialog_pages.pop_front();
+            Message::OfferSessionResume => {
+                self.dialog_pages.push_back(DialogPage::ResumeSession);
             }
-            Message::AddStudySet(set) => {
-                self.create_nav_item(set);
-                let Some(entity) = self.nav.iter().last() else {
-                    return Command::none();
-                };
-                let command = self.on_nav_select(entity);
-                commands.push(command);
+            Message::ForgetPausedSession => {
+                self.pending_resume = None;
             }
-            Message::DeleteStudySet => {
-                if let Some(set) = self.nav.data::<StudySet>(self.nav.active()) {
-                    let command = Command::perform(
-                        delete_studyset(self.db.clone(), set.id.unwrap()),
-                        |result| match result {
-                            Ok(_) => message::none(),
-                            Err(_) => message::none(),
+            Message::FetchDueCount => {
+                commands.push(Command::perform(
+                    get_due_count(
+                        self.db.clone(),
+                        if self.config.include_mastered {
+                            None
+                        } else {
+                            self.config.mastered_streak
                         },
-                    );
+                    ),
+                    |result| match result {
+                        Ok(count) => message::app(Message::SetDueCount(count)),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                ));
+            }
+            Message::SetDueCount(count) => {
+                self.due_count = count;
+            }
+            Message::FetchCardCounts => {
+                commands.push(Command::perform(
+                    count_flashcards_by_studyset(self.db.clone()),
+                    |result| match result {
+                        Ok(counts) => message::app(Message::SetCardCounts(counts)),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                ));
+            }
+            Message::SetCardCounts(counts) => {
+                self.studyset_card_counts = counts;
+                let entities: Vec<_> = self.nav.iter().collect();
+                for entity in entities {
+                    if let Some(label) = self
+                        .nav
+                        .data::<StudySet>(entity)
+                        .map(|set| self.nav_label(set))
+                    {
+                        self.nav.text_set(entity, label);
+                    }
+                }
+            }
+            Message::FetchStreak => {
+                commands.push(Command::perform(
+                    get_current_streak(self.db.clone()),
+                    |result| match result {
+                        Ok(streak) => message::app(Message::SetStreak(streak)),
+                        Err(err) => message::app(Message::Error(err)),
+                    },
+                ));
+            }
+            Message::SetStreak(streak) => {
+                self.streak = streak;
+            }
+            Message::SelectNavItem(entity) => {
+                commands.push(self.on_nav_select(entity));
+            }
+            Message::StudySetRenamed(studyset_id, name) => {
+                let renamed_entity = self.nav.iter().find(|entity| {
+                    self.nav
+                        .data::<StudySet>(*entity)
+                        .is_some_and(|set| set.id == Some(studyset_id))
+                });
+
+                if let Some(entity) = renamed_entity {
+                    if let Some(set) = self.nav.data_mut::<StudySet>(entity) {
+                        set.name = name.clone();
+                    }
+                    if let Some(label) = self
+                        .nav
+                        .data::<StudySet>(entity)
+                        .map(|set| self.nav_label(set))
+                    {
+                        self.nav.text_set(entity, label);
+                    }
 
-                    self.folders.current_studyset_id = None;
-                    commands.push(self.update(Message::Folders(folders::Message::LoadFolders)));
-                    commands.push(command);
+                    if self.nav.active() == entity && self.current_page != Page::AllFlashcards {
+                        let window_title = format!("Oboete - {}", name);
+                        commands.push(self.set_window_title(window_title.clone()));
+                        self.set_header_title(window_title);
+                    }
                 }
-                self.nav.remove(self.nav.active());
             }
-            Message::OpenNewFolderDialog => {
-                self.dialog_pages
-                    .push_back(DialogPage::NewFolder(String::new()));
-                return widget::text_input::focus(self.dialog_text_input.clone());
+            Message::Key(modifiers, key) => {
+                //Escape closes the open dialog as if it was cancelled
+                if key == Key::Named(Named::Escape) && !self.dialog_pages.is_empty() {
+                    return self.update(Message::DialogCancel);
+                }
+
+                let action = self
+                    .key_binds
+                    .iter()
+                    .find(|(key_bind, _action)| key_bind.matches(modifiers, &key))
+                    .map(|(_key_bind, action)| *action);
+
+                if let Some(action) = action {
+                    return self.update(menu::action::MenuAction::message(&action));
+                }
+            }
+            //The study shortcuts only work while a flashcard is being studied in the classic mode
+            Message::GradeStudiedFlashcard(action) => {
+                if let (true, Some(flashcard)) = (
+                    self.study_shortcuts_active() && self.flashcards.browsing == false,
+                    self.flashcards.currently_studying_flashcard.clone(),
+                ) {
+                    commands.push(self.update(Message::Flashcards(
+                        flashcards::Message::UpdateFlashcardStatus(flashcard, action),
+                    )));
+                }
+            }
+            Message::FlipStudiedFlashcard => {
+                if self.study_shortcuts_active() {
+                    commands.push(
+                        self.update(Message::Flashcards(flashcards::Message::SwapFlashcardSide)),
+                    );
+                }
+            }
+            Message::BrowseStudiedFlashcards(forward) => {
+                if self.study_shortcuts_active() && self.flashcards.browsing {
+                    let message = if forward {
+                        flashcards::Message::BrowseNext
+                    } else {
+                        flashcards::Message::BrowsePrevious
+                    };
+                    commands.push(self.update(Message::Flashcards(message)));
+                }
+            }
+            //Grades can be undone in every study mode
+            Message::UndoStudiedGrade => {
+                if self.current_page == Page::StudyFolderFlashcards && self.dialog_pages.is_empty()
+                {
+                    commands
+                        .push(self.update(Message::Flashcards(flashcards::Message::UndoLastGrade)));
+                }
+            }
+            Message::OpenKeyboardShortcutsDialog => {
+                if !self.dialog_pages.contains(&DialogPage::KeyboardShortcuts) {
+                    self.dialog_pages.push_back(DialogPage::KeyboardShortcuts);
+                }
+            }
+            Message::WindowResized(width, height) => {
+                config_set!(self, window_width, width);
+                config_set!(self, window_height, height);
             }
         }
 
+        self.save_state();
         Command::batch(commands)
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let events = event::listen_with(|event, status| match event {
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => match status {
+                event::Status::Ignored => Some(Message::Key(modifiers, key)),
+                event::Status::Captured => None,
+            },
+            Event::Window(_id, window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            _ => None,
+        });
+
+        if self.config.study_reminders {
+            Subscription::batch([
+                events,
+                time::every(REMINDER_CHECK_INTERVAL).map(|_| Message::CheckStudyReminder),
+            ])
+        } else {
+            events
+        }
+    }
+
     /// Display a context drawer if the context page is requested.
     fn context_drawer(&self) -> Option<Element<Self::Message>> {
         if !self.core.window.show_context {
@@ -707,7 +2765,7 @@ impl Application for Oboete {
 
         Some(match self.context_page {
             ContextPage::About => self.about(),
-            ContextPage::EditFolder => self.folders.edit_folder_contextpage().map(Message::Folders),
+            ContextPage::Settings => self.settings(),
             ContextPage::CreateEditFlashcard => self
                 .flashcards
                 .create_edit_flashcard_contextpage()
@@ -716,6 +2774,15 @@ impl Application for Oboete {
                 .flashcards
                 .flashcard_options_contextpage()
                 .map(Message::Flashcards),
+            ContextPage::FolderOutline => self.folders.outline_contextpage().map(Message::Folders),
+            ContextPage::PasteImport => self
+                .flashcards
+                .paste_import_contextpage()
+                .map(Message::Flashcards),
+            ContextPage::FlashcardHistory => self
+                .flashcards
+                .flashcard_history_contextpage()
+                .map(Message::Flashcards),
         })
     }
 
@@ -728,7 +2795,33 @@ impl Application for Oboete {
         let spacing = theme::active().cosmic().spacing;
 
         let dialog = match dialog_page {
-            DialogPage::NewStudySet(name) => widget::dialog(fl!("create-studyset"))
+            DialogPage::NewStudySet(name) => {
+                let validation = validate_new_name(name, &self.studyset_names());
+
+                widget::dialog(fl!("create-studyset"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save"))
+                            .on_press_maybe(validation.is_ok().then_some(Message::DialogComplete)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("studyset-name")).into(),
+                            widget::text_input("", name.as_str())
+                                .id(self.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    Message::DialogUpdate(DialogPage::NewStudySet(name))
+                                })
+                                .on_submit(Message::DialogComplete)
+                                .into(),
+                            name_error(name, validation),
+                        ])
+                        .spacing(spacing.space_xxs),
+                    )
+            }
+            DialogPage::RenameStudySet { to: name } => widget::dialog(fl!("rename-studyset"))
                 .primary_action(
                     widget::button::suggested(fl!("save"))
                         .on_press_maybe(Some(Message::DialogComplete)),
@@ -742,64 +2835,234 @@ impl Application for Oboete {
                         widget::text_input("", name.as_str())
                             .id(self.dialog_text_input.clone())
                             .on_input(move |name| {
-                                Message::DialogUpdate(DialogPage::NewStudySet(name))
+                                Message::DialogUpdate(DialogPage::RenameStudySet { to: name })
                             })
                             .on_submit(Message::DialogComplete)
                             .into(),
                     ])
                     .spacing(spacing.space_xxs),
                 ),
-            DialogPage::RenameStudySet { to: name } => widget::dialog(fl!("rename-studyset"))
+            DialogPage::DeleteStudySet => widget::dialog(fl!("delete-studyset"))
+                .body(fl!("confirm-delete"))
                 .primary_action(
-                    widget::button::suggested(fl!("save"))
+                    widget::button::suggested(fl!("ok"))
                         .on_press_maybe(Some(Message::DialogComplete)),
                 )
                 .secondary_action(
                     widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
-                )
-                .control(
-                    widget::column::with_children(vec![
-                        widget::text::body(fl!("studyset-name")).into(),
-                        widget::text_input("", name.as_str())
-                            .id(self.dialog_text_input.clone())
-                            .on_input(move |name| {
-                                Message::DialogUpdate(DialogPage::RenameStudySet { to: name })
-                            })
-                            .on_submit(Message::DialogComplete)
-                            .into(),
-                    ])
-                    .spacing(spacing.space_xxs),
                 ),
-            DialogPage::DeleteStudySet => widget::dialog(fl!("delete-studyset"))
-                .body(fl!("confirm-delete"))
+            DialogPage::ConfirmBulkAction { action, affected } => widget::dialog(match action {
+                BulkAction::ResetFolderFlashcards(_) => fl!("reset-folder-flashcards-title"),
+                BulkAction::ResetStudySetFlashcards(_) => fl!("reset-studyset-progress"),
+                BulkAction::DeleteFolder(_) => fl!("delete-folder"),
+                BulkAction::SyncFolder { .. } => fl!("sync-source-title"),
+                BulkAction::DeleteSelectedFlashcards => fl!("delete-selected"),
+            })
+            .body(fl!("confirm-bulk-action", affected = affected))
+            .primary_action(
+                widget::button::suggested(fl!("ok")).on_press_maybe(Some(Message::DialogComplete)),
+            )
+            .secondary_action(
+                widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+            ),
+            DialogPage::KeyboardShortcuts => {
+                //Built from the key binds so it's always in sync with them
+                let mut key_binds: Vec<(String, String)> = self
+                    .key_binds
+                    .iter()
+                    .map(|(key_bind, action)| (action.title(), key_bind.to_string()))
+                    .collect();
+                key_binds.sort();
+
+                let mut shortcuts = widget::list::list_column();
+                for (title, key_bind) in key_binds {
+                    shortcuts = shortcuts.add(
+                        widget::row::with_capacity(2)
+                            .align_items(Alignment::Center)
+                            .spacing(spacing.space_xxs)
+                            .push(widget::text(title).width(Length::Fill))
+                            .push(widget::text::body(key_bind)),
+                    );
+                }
+
+                widget::dialog(fl!("keyboard-shortcuts"))
+                    .primary_action(
+                        widget::button::suggested(fl!("ok"))
+                            .on_press_maybe(Some(Message::DialogComplete)),
+                    )
+                    .control(shortcuts)
+            }
+            DialogPage::BackupCreated(path) => widget::dialog(fl!("backup-database"))
+                .body(fl!("backup-created", path = path.as_str()))
                 .primary_action(
                     widget::button::suggested(fl!("ok"))
                         .on_press_maybe(Some(Message::DialogComplete)),
+                ),
+            DialogPage::DeleteFlashcard(front) => widget::dialog(fl!("delete-flashcard"))
+                .body(fl!("confirm-delete-flashcard", front = front.as_str()))
+                .primary_action(
+                    widget::button::destructive(fl!("delete"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
                 )
                 .secondary_action(
                     widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                 ),
-            DialogPage::NewFolder(name) => widget::dialog(fl!("create-folder"))
+            DialogPage::DuplicateFlashcard(front) => widget::dialog(fl!("duplicate-flashcard"))
+                .body(fl!("confirm-duplicate-flashcard", front = front.as_str()))
                 .primary_action(
-                    widget::button::suggested(fl!("save"))
+                    widget::button::suggested(fl!("add-anyway"))
                         .on_press_maybe(Some(Message::DialogComplete)),
                 )
                 .secondary_action(
                     widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::SyncConflict(direction) => widget::dialog(fl!("sync-conflict"))
+                .body(match direction {
+                    SyncDirection::Push => fl!("sync-conflict-push"),
+                    SyncDirection::Pull => fl!("sync-conflict-pull"),
+                })
+                .primary_action(
+                    widget::button::destructive(fl!("sync-overwrite"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
                 )
-                .control(
-                    widget::column::with_children(vec![
-                        widget::text::body(fl!("folder-name")).into(),
-                        widget::text_input("", name.as_str())
-                            .id(self.dialog_text_input.clone())
-                            .on_input(move |name| {
-                                Message::DialogUpdate(DialogPage::NewFolder(name))
-                            })
-                            .on_submit(Message::DialogComplete)
-                            .into(),
-                    ])
-                    .spacing(spacing.space_xxs),
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::ResumeSession => {
+                let (studied, total) = self
+                    .pending_resume
+                    .as_ref()
+                    .map(|paused| (paused.studied_count, paused.session_size))
+                    .unwrap_or_default();
+                widget::dialog(fl!("resume-session"))
+                    .body(fl!("resume-session-body", studied = studied, total = total))
+                    .primary_action(
+                        widget::button::suggested(fl!("resume"))
+                            .on_press_maybe(Some(Message::DialogComplete)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("abandon-session"))
+                            .on_press(Message::DialogCancel),
+                    )
+            }
+            DialogPage::DiscardFlashcardEdit => widget::dialog(fl!("discard-changes"))
+                .body(fl!("confirm-discard-changes"))
+                .primary_action(
+                    widget::button::destructive(fl!("discard"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                 ),
+            //Every folder of the studyset but the one the flashcard is already in
+            DialogPage::MoveFlashcard {
+                flashcard_id,
+                target,
+            } => {
+                let flashcard_id = *flashcard_id;
+                let mut folders = widget::list::list_column();
+                for folder in &self.folders.folders {
+                    let Some(folder_id) = folder.id else {
+                        continue;
+                    };
+                    if folder_id == self.flashcards.current_folder_id {
+                        continue;
+                    }
+
+                    let style = if *target == Some(folder_id) {
+                        theme::Button::Suggested
+                    } else {
+                        theme::Button::Standard
+                    };
+                    folders = folders.add(
+                        widget::button(widget::text(folder.name.clone()).width(Length::Fill))
+                            .on_press(Message::DialogUpdate(DialogPage::MoveFlashcard {
+                                flashcard_id,
+                                target: Some(folder_id),
+                            }))
+                            .style(style)
+                            .padding(spacing.space_xxs)
+                            .width(Length::Fill),
+                    );
+                }
+
+                widget::dialog(fl!("move-flashcard"))
+                    .body(fl!("move-flashcard-destination"))
+                    .primary_action(
+                        widget::button::suggested(fl!("move"))
+                            .on_press_maybe(target.map(|_| Message::DialogComplete)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(folders)
+            }
+            //Every folder of the studyset but the merged one
+            DialogPage::MergeFolder { source_id, target } => {
+                let source_id = *source_id;
+                let mut folders = widget::list::list_column();
+                for folder in &self.folders.folders {
+                    let Some(folder_id) = folder.id else {
+                        continue;
+                    };
+                    if folder_id == source_id {
+                        continue;
+                    }
+
+                    let style = if *target == Some(folder_id) {
+                        theme::Button::Suggested
+                    } else {
+                        theme::Button::Standard
+                    };
+                    folders = folders.add(
+                        widget::button(widget::text(folder.name.clone()).width(Length::Fill))
+                            .on_press(Message::DialogUpdate(DialogPage::MergeFolder {
+                                source_id,
+                                target: Some(folder_id),
+                            }))
+                            .style(style)
+                            .padding(spacing.space_xxs)
+                            .width(Length::Fill),
+                    );
+                }
+
+                widget::dialog(fl!("merge-folder"))
+                    .body(fl!("merge-folder-destination"))
+                    .primary_action(
+                        widget::button::suggested(fl!("merge"))
+                            .on_press_maybe(target.map(|_| Message::DialogComplete)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(folders)
+            }
+            DialogPage::NewFolder(name) => {
+                let validation = validate_new_name(name, &self.folder_names());
+
+                widget::dialog(fl!("create-folder"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save"))
+                            .on_press_maybe(validation.is_ok().then_some(Message::DialogComplete)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("folder-name")).into(),
+                            widget::text_input("", name.as_str())
+                                .id(self.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    Message::DialogUpdate(DialogPage::NewFolder(name))
+                                })
+                                .on_submit(Message::DialogComplete)
+                                .into(),
+                            name_error(name, validation),
+                        ])
+                        .spacing(spacing.space_xxs),
+                    )
+            }
         };
 
         Some(dialog.into())
@@ -810,13 +3073,20 @@ impl Application for Oboete {
         &mut self,
         entity: segmented_button::Entity,
     ) -> Command<CosmicMessage<Self::Message>> {
+        if self.has_unsaved_edit() {
+            self.confirm_discard_edit(Message::SelectNavItem(entity));
+            return Command::none();
+        }
+
         let mut commands = vec![];
         self.nav.activate(entity);
         let location_opt = self.nav.data::<StudySet>(entity);
 
         if let Some(set) = location_opt {
             self.current_page = Page::Folders;
+            self.flashcards.leave_session();
             self.folders.current_studyset_id = set.id;
+            self.folders.current_studyset_color = set.color.clone();
 
             let message = Message::Folders(folders::Message::LoadFolders);
             let window_title = format!("Oboete - {}", set.name);
@@ -855,10 +3125,633 @@ impl Oboete {
             .into()
     }
 
+    /// The settings page for this app.
+    pub fn settings(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+        let selected_threshold = BULK_CONFIRMATION_THRESHOLDS
+            .iter()
+            .position(|threshold| *threshold == self.config.bulk_confirmation_threshold);
+
+        let mut sections: Vec<Element<Message>> =
+            vec![widget::settings::view_section(fl!("bulk-operations"))
+                .add(widget::settings::item(
+                    fl!("bulk-confirmation-threshold"),
+                    widget::dropdown(
+                        &self.bulk_threshold_labels,
+                        selected_threshold,
+                        Message::UpdateBulkConfirmationThreshold,
+                    ),
+                ))
+                .into()];
+
+        let selected_tolerance = ANSWER_TYPO_TOLERANCES
+            .iter()
+            .position(|tolerance| *tolerance == self.config.answer_typo_tolerance);
+        let selected_limit = FLASHCARD_LENGTH_LIMITS
+            .iter()
+            .position(|limit| *limit == self.config.flashcard_length_limit);
+        sections.push(
+            widget::settings::view_section(fl!("flashcard-display"))
+                .add(widget::settings::item(
+                    fl!("render-markdown"),
+                    widget::toggler(
+                        None,
+                        self.config.render_markdown,
+                        Message::UpdateRenderMarkdown,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("flashcard-length-limit"),
+                    widget::dropdown(
+                        &self.length_limit_labels,
+                        selected_limit,
+                        Message::UpdateFlashcardLengthLimit,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("match-all-tags"),
+                    widget::toggler(
+                        None,
+                        self.config.match_all_tags,
+                        Message::UpdateMatchAllTags,
+                    ),
+                ))
+                .into(),
+        );
+        sections.push(
+            widget::settings::view_section(fl!("typed-answers"))
+                .add(widget::settings::item(
+                    fl!("answer-typo-tolerance"),
+                    widget::dropdown(
+                        &self.typo_tolerance_labels,
+                        selected_tolerance,
+                        Message::UpdateAnswerTypoTolerance,
+                    ),
+                ))
+                .into(),
+        );
+        let selected_length = SESSION_LENGTHS
+            .iter()
+            .position(|length| *length == self.config.session_length);
+        let selected_streak = MASTERED_STREAKS
+            .iter()
+            .position(|streak| *streak == self.config.mastered_streak);
+        sections.push(
+            widget::settings::view_section(fl!("study-sessions"))
+                .add(widget::settings::item(
+                    fl!("session-length"),
+                    widget::dropdown(
+                        &self.session_length_labels,
+                        selected_length,
+                        Message::UpdateSessionLength,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("mastered-streak"),
+                    widget::dropdown(
+                        &self.mastered_streak_labels,
+                        selected_streak,
+                        Message::UpdateMasteredStreak,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("include-mastered"),
+                    widget::toggler(
+                        None,
+                        self.config.include_mastered,
+                        Message::UpdateIncludeMastered,
+                    ),
+                ))
+                .into(),
+        );
+
+        let mut reminders =
+            widget::settings::view_section(fl!("study-reminders")).add(widget::settings::item(
+                fl!("study-reminders-enabled"),
+                widget::toggler(
+                    None,
+                    self.config.study_reminders,
+                    Message::UpdateStudyReminders,
+                ),
+            ));
+        if self.config.study_reminders {
+            let mut weekdays = widget::row::with_capacity(7).spacing(spacing.space_xxs);
+            //Shown from Monday, the config starts on Sunday like SQLite
+            for weekday in [1, 2, 3, 4, 5, 6, 0] {
+                weekdays = weekdays.push(
+                    widget::button(widget::text(weekday_label(weekday)))
+                        .padding(spacing.space_xxs)
+                        .style(if self.config.reminder_weekdays[weekday] {
+                            theme::Button::Suggested
+                        } else {
+                            theme::Button::Standard
+                        })
+                        .on_press(Message::ToggleReminderWeekday(weekday)),
+                );
+            }
+            reminders = reminders
+                .add(widget::settings::item(fl!("reminder-days"), weekdays))
+                .add(widget::settings::item(
+                    fl!("reminder-hour"),
+                    widget::dropdown(
+                        &self.hour_labels,
+                        Some(self.config.reminder_hour as usize),
+                        Message::UpdateReminderHour,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("quiet-hours"),
+                    widget::toggler(None, self.config.quiet_hours, Message::UpdateQuietHours),
+                ));
+            if self.config.quiet_hours {
+                reminders = reminders
+                    .add(widget::settings::item(
+                        fl!("quiet-hours-start"),
+                        widget::dropdown(
+                            &self.hour_labels,
+                            Some(self.config.quiet_hours_start as usize),
+                            Message::UpdateQuietHoursStart,
+                        ),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("quiet-hours-end"),
+                        widget::dropdown(
+                            &self.hour_labels,
+                            Some(self.config.quiet_hours_end as usize),
+                            Message::UpdateQuietHoursEnd,
+                        ),
+                    ));
+            }
+        }
+        sections.push(reminders.into());
+
+        let sync_configured = self.config.sync_config().is_configured();
+        sections.push(
+            widget::settings::view_section(fl!("sync"))
+                .add(widget::settings::item(
+                    fl!("sync-url"),
+                    widget::text_input(fl!("sync-url-placeholder"), &self.config.sync_url)
+                        .on_input(Message::UpdateSyncUrl),
+                ))
+                .add(widget::settings::item(
+                    fl!("sync-username"),
+                    widget::text_input(fl!("sync-username"), &self.config.sync_username)
+                        .on_input(Message::UpdateSyncUsername),
+                ))
+                .add(widget::settings::item(
+                    fl!("sync-password"),
+                    widget::secure_input(
+                        fl!("sync-password"),
+                        &self.config.sync_password,
+                        None,
+                        true,
+                    )
+                    .on_input(Message::UpdateSyncPassword),
+                ))
+                .add(
+                    widget::row::with_capacity(2)
+                        .push(widget::button::standard(fl!("sync-push")).on_press_maybe(
+                            sync_configured.then_some(Message::Sync(SyncDirection::Push, false)),
+                        ))
+                        .push(widget::button::standard(fl!("sync-pull")).on_press_maybe(
+                            sync_configured.then_some(Message::Sync(SyncDirection::Pull, false)),
+                        ))
+                        .spacing(spacing.space_xxs),
+                )
+                .into(),
+        );
+
+        //The icon and study labels are set for the currently selected studyset
+        if let Some(set) = self.nav.data::<StudySet>(self.nav.active()) {
+            let mut icons = widget::row::with_capacity(STUDYSET_ICONS.len())
+                .spacing(spacing.space_xxs)
+                .padding([spacing.space_none, spacing.space_xxs]);
+            for icon in STUDYSET_ICONS {
+                icons = icons.push(
+                    widget::button(IconCache::get(icon, 18))
+                        .padding(spacing.space_xxs)
+                        .style(if set.icon_name() == icon {
+                            theme::Button::Suggested
+                        } else {
+                            theme::Button::Standard
+                        })
+                        .on_press(Message::UpdateStudySetIcon(icon)),
+                );
+            }
+
+            sections.push(
+                widget::settings::view_section(fl!("studyset-icon"))
+                    .add(icons)
+                    .into(),
+            );
+
+            let mut colors = widget::row::with_capacity(STUDYSET_COLORS.len() + 1)
+                .align_items(Alignment::Center)
+                .spacing(spacing.space_xxs)
+                .padding([spacing.space_none, spacing.space_xxs])
+                .push(
+                    widget::button(widget::text(fl!("studyset-color-default")))
+                        .padding(spacing.space_xxs)
+                        .style(if set.color.is_none() {
+                            theme::Button::Suggested
+                        } else {
+                            theme::Button::Standard
+                        })
+                        .on_press(Message::UpdateStudySetColor(None)),
+                );
+            for color in STUDYSET_COLORS {
+                colors = colors.push(
+                    folders::color_swatch(color, set.color.as_deref() == Some(color))
+                        .on_press(Message::UpdateStudySetColor(Some(color))),
+                );
+            }
+
+            sections.push(
+                widget::settings::view_section(fl!("studyset-color"))
+                    .add(colors)
+                    .into(),
+            );
+        }
+
+        if let Some(studyset_id) = self.active_studyset_id() {
+            let selected_scheduler = SCHEDULER_KINDS
+                .iter()
+                .position(|kind| *kind == self.active_scheduler());
+            let limits = self.active_interval_limits();
+            let selected_min = MIN_INTERVALS
+                .iter()
+                .position(|days| *days == limits.min_interval_days);
+            let selected_max = MAX_INTERVALS
+                .iter()
+                .position(|days| *days == limits.max_interval_days);
+            sections.push(
+                widget::settings::view_section(fl!("studyset-scheduler"))
+                    .add(widget::settings::item(
+                        fl!("scheduler"),
+                        widget::dropdown(
+                            &self.scheduler_labels,
+                            selected_scheduler,
+                            Message::UpdateStudySetScheduler,
+                        ),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("min-interval"),
+                        widget::dropdown(
+                            &self.min_interval_labels,
+                            selected_min,
+                            Message::UpdateStudySetMinInterval,
+                        ),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("max-interval"),
+                        widget::dropdown(
+                            &self.max_interval_labels,
+                            selected_max,
+                            Message::UpdateStudySetMaxInterval,
+                        ),
+                    ))
+                    .into(),
+            );
+
+            let current_labels = self.config.studyset_labels.get(&studyset_id);
+            let labels = self.active_study_labels();
+            let (bad_labels, ok_labels, good_labels) = (labels.clone(), labels.clone(), labels);
+
+            sections.push(
+                widget::settings::view_section(fl!("study-labels"))
+                    .add(widget::settings::item(
+                        fl!("bad-status"),
+                        widget::text_input(
+                            fl!("bad-status"),
+                            current_labels.map_or("", |labels| labels.bad.as_str()),
+                        )
+                        .on_input(move |value| {
+                            Message::UpdateStudyLabels(StudyLabels {
+                                bad: value,
+                                ..bad_labels.clone()
+                            })
+                        }),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("ok-status"),
+                        widget::text_input(
+                            fl!("ok-status"),
+                            current_labels.map_or("", |labels| labels.ok.as_str()),
+                        )
+                        .on_input(move |value| {
+                            Message::UpdateStudyLabels(StudyLabels {
+                                ok: value,
+                                ..ok_labels.clone()
+                            })
+                        }),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("good-status"),
+                        widget::text_input(
+                            fl!("good-status"),
+                            current_labels.map_or("", |labels| labels.good.as_str()),
+                        )
+                        .on_input(move |value| {
+                            Message::UpdateStudyLabels(StudyLabels {
+                                good: value,
+                                ..good_labels.clone()
+                            })
+                        }),
+                    ))
+                    .into(),
+            );
+        }
+
+        widget::settings::view_column(sections).into()
+    }
+
+    /// Banner shown above the page when a background operation fails
+    fn error_banner<'a>(&self, error: &'a str) -> Element<'a, Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        widget::container(
+            widget::row::with_capacity(3)
+                .push(IconCache::get("dialog-error-symbolic", 16))
+                .push(widget::text::body(error).width(Length::Fill))
+                .push(
+                    widget::button(IconCache::get("window-close-symbolic", 16))
+                        .padding(spacing.space_xxs)
+                        .style(theme::Button::Standard)
+                        .on_press(Message::DismissError),
+                )
+                .align_items(Alignment::Center)
+                .spacing(spacing.space_xs),
+        )
+        .padding([spacing.space_xxs, spacing.space_s])
+        .style(theme::Container::Card)
+        .into()
+    }
+
+    /// Whether the create/edit context page is open with changes that haven't been saved
+    fn has_unsaved_edit(&self) -> bool {
+        self.core.window.show_context
+            && self.context_page == ContextPage::CreateEditFlashcard
+            && self.flashcards.new_edit_flashcard.is_dirty()
+    }
+
+    /// Keeps the navigation until the changes of the flashcard being edited are discarded
+    fn confirm_discard_edit(&mut self, message: Message) {
+        self.pending_navigation = Some(message);
+        self.dialog_pages
+            .push_back(DialogPage::DiscardFlashcardEdit);
+    }
+
+    /// Moves the study direction, filter and order of the folders to the database, they used to
+    /// be saved in the config. They're removed from the config once they've been moved
+    fn move_folder_study_preferences(&mut self) -> Command<CosmicMessage<Message>> {
+        let mut preferences: HashMap<i32, FolderStudyPreferences> = HashMap::new();
+        for (folder_id, direction) in &self.config.folder_study_directions {
+            preferences.entry(*folder_id).or_default().direction = *direction;
+        }
+        for (folder_id, filter) in &self.config.folder_study_filters {
+            preferences.entry(*folder_id).or_default().filter = *filter;
+        }
+        for (folder_id, order) in &self.config.folder_study_orders {
+            preferences.entry(*folder_id).or_default().order = *order;
+        }
+
+        if preferences.is_empty() {
+            return Command::none();
+        }
+
+        config_set!(self, folder_study_directions, HashMap::new());
+        config_set!(self, folder_study_filters, HashMap::new());
+        config_set!(self, folder_study_orders, HashMap::new());
+
+        let db = self.db.clone();
+        Command::perform(
+            async move {
+                for (folder_id, preferences) in preferences {
+                    set_folder_study_preferences(db.clone(), folder_id, preferences).await?;
+                }
+                Ok(())
+            },
+            |result: Result<(), OboeteError>| match result {
+                Ok(_) => message::none(),
+                Err(err) => message::app(Message::Error(err)),
+            },
+        )
+    }
+
+    /// Backs up the database into the data directory of the app, a failed backup is only reported.
+    /// Automatic backups (on startup) don't say anything when they succeed
+    fn backup_database(&self, manual: bool) -> Command<CosmicMessage<Message>> {
+        let backup_dir = app_data_dir(Self::APP_ID).map(|dir| dir.join("backups"));
+        let db = self.db.clone();
+
+        Command::perform(
+            async move { backup_database(db, backup_dir?).await },
+            move |result| match result {
+                Ok(path) if manual => {
+                    message::app(Message::BackupCreated(path.to_string_lossy().to_string()))
+                }
+                Ok(_) => message::none(),
+                Err(err) => message::app(Message::Error(err)),
+            },
+        )
+    }
+
+    /// Id of the studyset selected in the nav bar
+    fn active_studyset_id(&self) -> Option<i32> {
+        self.nav
+            .data::<StudySet>(self.nav.active())
+            .and_then(|set| set.id)
+    }
+
+    /// Names of the studysets in the nav bar
+    fn studyset_names(&self) -> Vec<String> {
+        self.nav
+            .iter()
+            .filter_map(|entity| self.nav.data::<StudySet>(entity))
+            .map(|set| set.name.clone())
+            .collect()
+    }
+
+    /// Text of the studyset in the nav bar, its name and how many flashcards it has
+    fn nav_label(&self, studyset: &StudySet) -> String {
+        let count = studyset
+            .id
+            .and_then(|id| self.studyset_card_counts.get(&id))
+            .copied()
+            .unwrap_or(0);
+        fl!(
+            "studyset-nav-label",
+            name = studyset.name.as_str(),
+            count = count
+        )
+    }
+
+    /// Names of the folders of the studyset that is open
+    fn folder_names(&self) -> Vec<String> {
+        self.folders
+            .folders
+            .iter()
+            .map(|folder| folder.name.clone())
+            .collect()
+    }
+
+    /// True while the study page is shown without any dialog on top of it
+    fn study_shortcuts_active(&self) -> bool {
+        self.current_page == Page::StudyFolderFlashcards
+            && self.dialog_pages.is_empty()
+            && (self.flashcards.study_mode == StudyMode::Flashcards || self.flashcards.browsing)
+    }
+
+    /// Study labels of the studyset selected in the nav bar
+    fn active_study_labels(&self) -> StudyLabels {
+        self.active_studyset_id()
+            .and_then(|studyset_id| self.config.studyset_labels.get(&studyset_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Scheduler of the studyset selected in the nav bar
+    fn active_scheduler(&self) -> SchedulerKind {
+        self.active_studyset_id()
+            .and_then(|studyset_id| self.config.studyset_schedulers.get(&studyset_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Interval limits of the studyset selected in the nav bar
+    fn active_interval_limits(&self) -> IntervalLimits {
+        self.active_studyset_id()
+            .and_then(|studyset_id| self.config.studyset_interval_limits.get(&studyset_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Saves the interval limits of the studyset selected in the nav bar, the default ones
+    /// aren't stored
+    fn set_interval_limits(&mut self, limits: IntervalLimits) {
+        let Some(studyset_id) = self.active_studyset_id() else {
+            return;
+        };
+
+        let mut studyset_interval_limits = self.config.studyset_interval_limits.clone();
+        if limits == IntervalLimits::default() {
+            studyset_interval_limits.remove(&studyset_id);
+        } else {
+            studyset_interval_limits.insert(studyset_id, limits);
+        }
+        config_set!(self, studyset_interval_limits, studyset_interval_limits);
+        self.flashcards.interval_limits = limits;
+    }
+
+    /// Persists the current page, nav selection and context drawer state
+    fn save_state(&mut self) {
+        if !self.state_restored {
+            return;
+        }
+
+        //The study all due session can't be restored, the folders page is restored instead
+        let current_page = if self.flashcards.studying_all_due {
+            Page::Folders
+        } else {
+            self.current_page
+        };
+        let studyset_id = self.active_studyset_id();
+        let folder_id = match current_page {
+            Page::Folders | Page::AllFlashcards => None,
+            Page::FolderFlashcards | Page::StudyFolderFlashcards => {
+                Some(self.flashcards.current_folder_id)
+            }
+        };
+        let context_page = self.context_page;
+        let context_drawer_open = self.core.window.show_context;
+
+        config_set!(self, last_page, current_page);
+        config_set!(self, last_studyset_id, studyset_id);
+        config_set!(self, last_folder_id, folder_id);
+        config_set!(self, context_page, context_page);
+        config_set!(self, context_drawer_open, context_drawer_open);
+
+        //Closing the app mid-session keeps its progress until it's resumed or abandoned
+        let paused_session = match self.current_page {
+            Page::StudyFolderFlashcards => self.flashcards.paused_session(),
+            _ => None,
+        }
+        .or_else(|| self.pending_resume.clone());
+        config_set!(self, paused_session, paused_session);
+    }
+
     fn create_nav_item(&mut self, studyset: StudySet) -> EntityMut<SingleSelect> {
         self.nav
             .insert()
-            .text(studyset.name.clone())
+            .text(self.nav_label(&studyset))
+            .icon(IconCache::get(studyset.icon_name(), 16))
             .data(studyset.clone())
     }
 }
+
+/// Trims the name of a new studyset or folder, it can't be empty or one of the existing names
+fn validate_new_name(name: &str, existing: &[String]) -> Result<String, OboeteError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(OboeteError::EmptyName);
+    }
+    if existing.iter().any(|existing| existing.trim() == name) {
+        return Err(OboeteError::NameTaken(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+/// The description of the error shown to the user, localized when the app knows the error.
+/// The rest (parse and network errors, the validations that are already localized...) are
+/// shown as they are
+/// Short name of the day of the week, 0 is Sunday
+fn weekday_label(weekday: usize) -> String {
+    match weekday {
+        0 => fl!("weekday-sunday"),
+        1 => fl!("weekday-monday"),
+        2 => fl!("weekday-tuesday"),
+        3 => fl!("weekday-wednesday"),
+        4 => fl!("weekday-thursday"),
+        5 => fl!("weekday-friday"),
+        _ => fl!("weekday-saturday"),
+    }
+}
+
+fn error_message(err: &OboeteError) -> String {
+    match err {
+        OboeteError::NoDatabase => fl!("error-database", error = err.to_string()),
+        OboeteError::Db(db_err) => fl!("error-database", error = db_err.to_string()),
+        OboeteError::Io(io_err) => fl!("error-file", error = io_err.to_string()),
+        OboeteError::NewerSchema(version) => fl!("error-newer-database", version = *version),
+        OboeteError::EmptyName => fl!("name-empty"),
+        OboeteError::NameTaken(name) => fl!("name-taken", name = name.as_str()),
+        OboeteError::FlashcardNotFound => fl!("error-flashcard-not-found"),
+        OboeteError::FolderNotFound => fl!("error-folder-not-found"),
+        _ => err.to_string(),
+    }
+}
+
+/// Why the name typed in a create dialog can't be used, nothing until something is typed
+fn name_error<'a>(name: &str, validation: Result<String, OboeteError>) -> Element<'a, Message> {
+    match validation {
+        Err(err) if name.is_empty() == false => widget::text::caption(error_message(&err)).into(),
+        _ => widget::Space::with_height(Length::Fixed(0.0)).into(),
+    }
+}
+
+/// Messages that close the create/edit context page or leave the page it belongs to
+fn leaves_flashcard_edit(message: &Message) -> bool {
+    matches!(
+        message,
+        Message::ToggleContextPage(_)
+            | Message::OpenAllFlashcards
+            | Message::StudyAllDue
+            | Message::Flashcards(
+                flashcards::Message::ToggleCreatePage(_)
+                    | flashcards::Message::CreateWhileStudying
+                    | flashcards::Message::ToggleOptionsPage
+                    | flashcards::Message::StudyFlashcards
+            )
+    )
+}
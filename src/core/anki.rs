@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io;
+use std::path::PathBuf;
+
+use futures::TryStreamExt;
+use sqlx::{sqlite::SqliteConnectOptions, Row, SqlitePool};
+
+use crate::{
+    core::import::strip_html,
+    models::{Flashcard, Folder, StudySet},
+    utils::OboeteError,
+};
+
+/// Collections inside of an .apkg, newest first (collection.anki21b is zstd compressed and not supported)
+const COLLECTION_NAMES: [&str; 2] = ["collection.anki21", "collection.anki2"];
+
+/// Separator between the fields of an Anki note
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Reads an Anki package, every top level deck becomes a studyset and every deck a folder of it.
+/// Only the first two fields of each note are used (front and back), media is ignored
+pub async fn import_apkg(path: String) -> Result<Vec<StudySet>, OboeteError> {
+    let collection_path = extract_collection(&path)?;
+    let result = read_collection(&collection_path).await;
+    let _ = fs::remove_file(&collection_path);
+    result
+}
+
+/// Extracts the collection database of the package into a temporary file
+fn extract_collection(path: &str) -> Result<PathBuf, OboeteError> {
+    let file = File::open(path)?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|err| OboeteError::Parse(err.to_string()))?;
+
+    let Some(name) = COLLECTION_NAMES
+        .iter()
+        .find(|name| archive.by_name(name).is_ok())
+    else {
+        return Err(OboeteError::Parse(String::from(
+            "The Anki package doesn't contain a supported collection",
+        )));
+    };
+
+    let collection_path =
+        std::env::temp_dir().join(format!("oboete-import-{}.anki2", std::process::id()));
+    let mut collection = archive
+        .by_name(name)
+        .map_err(|err| OboeteError::Parse(err.to_string()))?;
+    let mut output = File::create(&collection_path)?;
+    io::copy(&mut collection, &mut output)?;
+
+    Ok(collection_path)
+}
+
+async fn read_collection(collection_path: &PathBuf) -> Result<Vec<StudySet>, OboeteError> {
+    let pool = SqlitePool::connect_with(
+        SqliteConnectOptions::new()
+            .filename(collection_path)
+            .read_only(true),
+    )
+    .await?;
+
+    let deck_names = read_deck_names(&pool).await?;
+
+    //Every note goes to the deck of its first card
+    let mut note_decks: HashMap<i64, i64> = HashMap::new();
+    let mut rows = sqlx::query("SELECT nid, did FROM cards ORDER BY ord ASC").fetch(&pool);
+    while let Some(row) = rows.try_next().await? {
+        note_decks.entry(row.get("nid")).or_insert(row.get("did"));
+    }
+    drop(rows);
+
+    //Studyset name -> Folder name -> Flashcards
+    let mut decks: BTreeMap<String, BTreeMap<String, Vec<Flashcard>>> = BTreeMap::new();
+    let mut rows = sqlx::query("SELECT id, flds FROM notes ORDER BY id ASC").fetch(&pool);
+    while let Some(row) = rows.try_next().await? {
+        let note_id: i64 = row.get("id");
+        let fields: String = row.get("flds");
+        let mut fields = fields.split(FIELD_SEPARATOR);
+
+        let (Some(front), Some(back)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let (front, back) = (strip_html(front), strip_html(back));
+        if front.is_empty() && back.is_empty() {
+            continue;
+        }
+
+        let deck_name = note_decks
+            .get(&note_id)
+            .and_then(|deck_id| deck_names.get(deck_id))
+            .cloned()
+            .unwrap_or_else(|| String::from("Default"));
+        let studyset_name = deck_name
+            .split("::")
+            .next()
+            .unwrap_or(&deck_name)
+            .to_string();
+
+        decks
+            .entry(studyset_name)
+            .or_default()
+            .entry(deck_name)
+            .or_default()
+            .push(Flashcard::new(front, back));
+    }
+    drop(rows);
+    pool.close().await;
+
+    Ok(decks
+        .into_iter()
+        .map(|(studyset_name, folders)| {
+            let mut studyset = StudySet::new(studyset_name);
+            studyset.folders = folders
+                .into_iter()
+                .map(|(folder_name, flashcards)| {
+                    let mut folder = Folder::new(folder_name);
+                    folder.flashcards = flashcards;
+                    folder
+                })
+                .collect();
+            studyset
+        })
+        .collect())
+}
+
+/// Newer collections have a decks table, older ones keep the decks as JSON inside of the col table
+async fn read_deck_names(pool: &SqlitePool) -> Result<HashMap<i64, String>, OboeteError> {
+    let mut deck_names = HashMap::new();
+
+    match sqlx::query("SELECT id, name FROM decks")
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => {
+            for row in rows {
+                let name: String = row.get("name");
+                deck_names.insert(row.get("id"), name.replace(FIELD_SEPARATOR, "::"));
+            }
+        }
+        Err(_) => {
+            let row = sqlx::query("SELECT decks FROM col").fetch_one(pool).await?;
+            let decks: String = row.get("decks");
+            let decks: HashMap<String, serde_json::Value> = serde_json::from_str(&decks)?;
+
+            for (id, deck) in decks {
+                if let (Ok(id), Some(name)) = (id.parse::<i64>(), deck["name"].as_str()) {
+                    deck_names.insert(id, name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(deck_names)
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Public interface of the core, grouped by what it works on. The app (and anything embedding
+//! the library) calls these, every function takes the database and returns the model types,
+//! so the queries behind them can change without touching the callers
+
+pub use crate::core::database::OboeteDb;
+
+/// Studysets and their appearance
+pub mod studysets {
+    pub use crate::core::database::{
+        count_flashcards_by_studyset, count_studyset_flashcards, create_studyset, delete_studyset,
+        duplicate_studyset, get_all_studysets, rename_studyset, reset_studyset_flashcard_status,
+        set_studyset_color, set_studyset_icon,
+    };
+}
+
+/// Folders of a studyset, their stats and how they're studied
+pub mod folders {
+    pub use crate::core::database::{
+        count_folder_flashcards, create_folder, create_folders, delete_folder, get_folder_stats,
+        get_folder_study_preferences, get_single_folder, get_studyset_folders, merge_folders,
+        move_folder, rename_folder, reset_folder_flashcard_status, set_folder_source_path,
+        set_folder_study_preferences, sync_folder_from_source,
+    };
+}
+
+/// Creating, finding and editing flashcards
+pub mod cards {
+    pub use crate::core::database::{
+        delete_flashcard, delete_flashcards, find_replace_flashcards, flashcard_exists,
+        get_all_flashcards, get_flashcards_by_tag, get_folder_flashcards, get_single_flashcard,
+        move_flashcard, reset_single_flashcard_status, search_flashcards, set_flashcard_favorite,
+        set_flashcard_tags, upsert_flashcard,
+    };
+}
+
+/// Grading, due flashcards, streaks and reminders
+pub mod study {
+    pub use crate::core::database::{
+        get_all_due_flashcards, get_current_streak, get_due_count, get_flashcard_history,
+        grade_flashcard, log_study_session, undo_flashcard_grade,
+    };
+    pub use crate::core::reminders::{check_study_reminder, ReminderSchedule, StudyReminder};
+}
+
+/// Imports, exports and backups, from and to files, other apps and the sync server
+pub mod transfer {
+    pub use crate::core::anki::import_apkg;
+    pub use crate::core::database::{
+        backup_database, export_all, export_studyset, import_all, import_flashcards,
+        import_flashcards_csv, import_quizlet, import_studysets, import_tsv,
+    };
+    pub use crate::core::pdf::{export_folder_pdf, PdfLayout};
+    pub use crate::core::sync::{pull_backup, push_backup, SyncConfig, SyncDirection, SyncOutcome};
+}
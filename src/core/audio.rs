@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use rodio::{Decoder, OutputStream, Sink};
+
+use crate::utils::OboeteError;
+
+/// How often the playback thread checks if it has to stop
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An audio clip being played on its own thread, playback stops when this is dropped
+pub struct AudioPlayback {
+    stop: Arc<AtomicBool>,
+}
+
+impl AudioPlayback {
+    /// Starts playing the file, fails if there's no audio output or the file can't be decoded
+    pub fn play(path: &str) -> Result<AudioPlayback, OboeteError> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (result_sender, result_receiver) = mpsc::channel();
+        let path = path.to_string();
+        let thread_stop = stop.clone();
+
+        //The output stream can't be sent between threads, so it lives on the playback thread
+        thread::spawn(move || {
+            let setup = || -> Result<(OutputStream, Sink), OboeteError> {
+                let (stream, handle) = OutputStream::try_default()
+                    .map_err(|err| OboeteError::Audio(err.to_string()))?;
+                let sink =
+                    Sink::try_new(&handle).map_err(|err| OboeteError::Audio(err.to_string()))?;
+                let source = Decoder::new(BufReader::new(File::open(&path)?))
+                    .map_err(|err| OboeteError::Audio(err.to_string()))?;
+                sink.append(source);
+                Ok((stream, sink))
+            };
+
+            match setup() {
+                Ok((_stream, sink)) => {
+                    let _ = result_sender.send(Ok(()));
+                    while !sink.empty() && !thread_stop.load(Ordering::Relaxed) {
+                        thread::sleep(STOP_POLL_INTERVAL);
+                    }
+                    sink.stop();
+                }
+                Err(err) => {
+                    let _ = result_sender.send(Err(err));
+                }
+            }
+        });
+
+        match result_receiver.recv() {
+            Ok(Ok(())) => Ok(AudioPlayback { stop }),
+            Ok(Err(err)) => Err(err),
+            Err(err) => Err(OboeteError::Audio(err.to_string())),
+        }
+    }
+}
+
+impl Drop for AudioPlayback {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use cosmic::{
+    cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry},
+    Application,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{ContextPage, Oboete, Page},
+    core::{
+        api::{study::ReminderSchedule, transfer::SyncConfig},
+        scheduler::{IntervalLimits, SchedulerKind},
+    },
+    fl,
+    flashcards::PausedSession,
+    models::{StudyDirection, StudyFilter, StudyOrder},
+};
+
+pub const CONFIG_VERSION: u64 = 1;
+
+/// Selectable values for the bulk operations confirmation threshold
+pub const BULK_CONFIRMATION_THRESHOLDS: [usize; 5] = [1, 10, 50, 100, 500];
+
+/// Selectable values for the typos accepted in typed answers
+pub const ANSWER_TYPO_TOLERANCES: [usize; 3] = [0, 1, 2];
+
+/// Selectable values for the maximum flashcards of a study session, None is unlimited
+pub const SESSION_LENGTHS: [Option<usize>; 4] = [Some(10), Some(20), Some(50), None];
+
+/// Selectable values for the Good grades in a row that make a flashcard mastered, None is never
+pub const MASTERED_STREAKS: [Option<usize>; 4] = [Some(3), Some(5), Some(10), None];
+
+/// Selectable values for the shortest interval (in days) of the flashcards of a studyset
+pub const MIN_INTERVALS: [i32; 4] = [1, 2, 3, 7];
+
+/// Selectable values for the longest interval (in days) of the flashcards of a studyset
+pub const MAX_INTERVALS: [i32; 6] = [30, 90, 180, 365, 730, 1825];
+
+/// Selectable values for the maximum characters of each side of a flashcard, None is unlimited
+pub const FLASHCARD_LENGTH_LIMITS: [Option<usize>; 4] = [Some(200), Some(500), Some(1000), None];
+
+#[derive(Clone, CosmicConfigEntry, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OboeteConfig {
+    /// Minimum number of affected flashcards that makes a bulk operation ask for confirmation
+    pub bulk_confirmation_threshold: usize,
+    /// Typos accepted when a typed answer is compared with the back of the flashcard
+    pub answer_typo_tolerance: usize,
+    /// Maximum flashcards graded in a study session, None studies every flashcard available
+    pub session_length: Option<usize>,
+    /// Good grades in a row that make a flashcard mastered, None never masters them
+    pub mastered_streak: Option<usize>,
+    /// Whether the mastered flashcards are studied with the due ones anyway
+    pub include_mastered: bool,
+    /// Whether the flashcards are rendered as Markdown (headings, lists, bold and code blocks)
+    pub render_markdown: bool,
+    /// Whether the tag filter of the flashcard list needs every selected tag (AND) instead of
+    /// any of them (OR)
+    pub match_all_tags: bool,
+    /// Maximum characters of the front and back of a flashcard, longer ones can't be saved
+    pub flashcard_length_limit: Option<usize>,
+    /// Width of the main window
+    pub window_width: u32,
+    /// Height of the main window
+    pub window_height: u32,
+    /// Whether the context drawer was open
+    pub context_drawer_open: bool,
+    /// Page shown inside of the context drawer
+    pub context_page: ContextPage,
+    /// Last page that was open
+    pub last_page: Page,
+    /// Last studyset selected in the nav bar
+    pub last_studyset_id: Option<i32>,
+    /// Last folder that was open
+    pub last_folder_id: Option<i32>,
+    /// Study session the app was closed in the middle of, offered to be resumed on launch
+    pub paused_session: Option<PausedSession>,
+    /// Custom study button labels of each studyset (by studyset id)
+    pub studyset_labels: HashMap<i32, StudyLabels>,
+    /// Scheduler of each studyset (by studyset id), missing studysets use SM-2
+    pub studyset_schedulers: HashMap<i32, SchedulerKind>,
+    /// Interval limits of each studyset (by studyset id), missing studysets use the defaults
+    pub studyset_interval_limits: HashMap<i32, IntervalLimits>,
+    /// Study direction of each folder (by folder id) from before the study preferences of the
+    /// folders were saved in the database, they're moved there on launch
+    pub folder_study_directions: HashMap<i32, StudyDirection>,
+    /// Study filter of each folder (by folder id), moved to the database like the directions
+    pub folder_study_filters: HashMap<i32, StudyFilter>,
+    /// Study order of each folder (by folder id), moved to the database like the directions
+    pub folder_study_orders: HashMap<i32, StudyOrder>,
+    /// WebDAV folder the backup is synced to, empty if syncing isn't set up
+    pub sync_url: String,
+    pub sync_username: String,
+    /// Saved in plain text like the rest of the config
+    pub sync_password: String,
+    /// When the backup on the remote folder was last pushed or pulled by this device, 0 if never
+    pub last_sync: i64,
+    /// Whether a notification is sent when there are flashcards due and nothing was studied today
+    pub study_reminders: bool,
+    /// Days of the week the reminder is sent on, starting on Sunday
+    pub reminder_weekdays: [bool; 7],
+    /// Hour of the day from which the reminder is sent
+    pub reminder_hour: u32,
+    /// Whether no reminders are sent between the start and the end of the quiet hours
+    pub quiet_hours: bool,
+    pub quiet_hours_start: u32,
+    pub quiet_hours_end: u32,
+    /// Julian day number of the last reminder sent, 0 if never
+    pub last_reminder_day: i64,
+}
+
+impl Default for OboeteConfig {
+    fn default() -> Self {
+        Self {
+            bulk_confirmation_threshold: 10,
+            answer_typo_tolerance: 1,
+            session_length: None,
+            mastered_streak: Some(5),
+            include_mastered: false,
+            render_markdown: false,
+            match_all_tags: false,
+            flashcard_length_limit: Some(1000),
+            window_width: 1200,
+            window_height: 800,
+            context_drawer_open: false,
+            context_page: ContextPage::default(),
+            last_page: Page::default(),
+            last_studyset_id: None,
+            last_folder_id: None,
+            paused_session: None,
+            studyset_labels: HashMap::new(),
+            studyset_schedulers: HashMap::new(),
+            studyset_interval_limits: HashMap::new(),
+            folder_study_directions: HashMap::new(),
+            folder_study_filters: HashMap::new(),
+            folder_study_orders: HashMap::new(),
+            sync_url: String::new(),
+            sync_username: String::new(),
+            sync_password: String::new(),
+            last_sync: 0,
+            study_reminders: false,
+            reminder_weekdays: [true; 7],
+            reminder_hour: 18,
+            quiet_hours: true,
+            quiet_hours_start: 22,
+            quiet_hours_end: 8,
+            last_reminder_day: 0,
+        }
+    }
+}
+
+impl OboeteConfig {
+    pub fn config_handler() -> Option<cosmic_config::Config> {
+        cosmic_config::Config::new(Oboete::APP_ID, CONFIG_VERSION).ok()
+    }
+
+    pub fn config() -> OboeteConfig {
+        match Self::config_handler() {
+            Some(config_handler) => OboeteConfig::get_entry(&config_handler)
+                .map_err(|(errors, config)| {
+                    for error in errors {
+                        eprintln!("Error loading config: {error}");
+                    }
+                    config
+                })
+                .unwrap_or_else(|config| config),
+            None => OboeteConfig::default(),
+        }
+    }
+
+    /// Where the backup is synced to
+    pub fn sync_config(&self) -> SyncConfig {
+        SyncConfig {
+            url: self.sync_url.clone(),
+            username: self.sync_username.clone(),
+            password: self.sync_password.clone(),
+            last_sync: self.last_sync,
+        }
+    }
+
+    /// When the study reminders are sent
+    pub fn reminder_schedule(&self) -> ReminderSchedule {
+        ReminderSchedule {
+            weekdays: self.reminder_weekdays,
+            hour: self.reminder_hour,
+            quiet_hours: self
+                .quiet_hours
+                .then_some((self.quiet_hours_start, self.quiet_hours_end)),
+        }
+    }
+
+    /// Returns true if a bulk operation affecting the given amount of flashcards needs confirmation
+    pub fn needs_bulk_confirmation(&self, affected: usize) -> bool {
+        affected >= self.bulk_confirmation_threshold
+    }
+}
+
+/// Custom labels for the study buttons, an empty label means the localized default is used
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StudyLabels {
+    pub bad: String,
+    pub ok: String,
+    pub good: String,
+}
+
+impl StudyLabels {
+    pub fn bad(&self) -> String {
+        Self::label_or(&self.bad, fl!("bad-status"))
+    }
+
+    pub fn ok(&self) -> String {
+        Self::label_or(&self.ok, fl!("ok-status"))
+    }
+
+    pub fn good(&self) -> String {
+        Self::label_or(&self.good, fl!("good-status"))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bad.trim().is_empty() && self.ok.trim().is_empty() && self.good.trim().is_empty()
+    }
+
+    fn label_or(label: &str, default: String) -> String {
+        if label.trim().is_empty() {
+            default
+        } else {
+            label.to_string()
+        }
+    }
+}
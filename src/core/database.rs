@@ -1,88 +1,142 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use futures::TryStreamExt;
-use sqlx::{sqlite::SqlitePool, Pool, Row, Sqlite};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteQueryResult, SqliteRow},
+    Pool, Row, Sqlite, SqliteConnection,
+};
 
 use crate::{
-    models::{Flashcard, Folder, StudySet},
-    utils::OboeteError,
+    core::{
+        migrations,
+        reminders::LocalTime,
+        scheduler::{self, DEFAULT_EASE_FACTOR, LEITNER_FIRST_BOX},
+    },
+    models::{
+        CardType, ExportDocument, Flashcard, FlashcardLocation, FlashcardSides, Folder,
+        FolderStats, FolderStudyPreferences, ImportSummary, MoveDirection, Review, StudyDirection,
+        StudyFilter, StudyMode, StudyOrder, StudyScope, StudySet, EXPORT_SCHEMA_VERSION,
+        STUDYSET_ICONS,
+    },
+    utils::{
+        app_data_dir, front_key, normalize_for_search, parse_csv_flashcards, parse_import_content,
+        parse_pasted_tsv, parse_tsv_file, replace_flashcard_text, same_front, OboeteError,
+    },
 };
 
 const DB_NAME: &str = "oboete.db";
 
+/// Start of the file name of every backup, followed by its timestamp
+const BACKUP_PREFIX: &str = "oboete-backup-";
+
+/// Backups kept by backup_database, older ones are deleted
+const BACKUPS_KEPT: usize = 5;
+
+/// Handle to the database of the app. The sqlx pool is reference counted,
+/// so cloning it for every operation only shares the same pool
 #[derive(Debug, Clone)]
 pub struct OboeteDb {
     db_pool: Pool<Sqlite>,
 }
 
 impl OboeteDb {
-    pub async fn init(app_id: &str) -> OboeteDb {
-        let db_path = dirs::data_dir()
-            .unwrap()
-            .join(app_id)
-            .join("database")
-            .join(DB_NAME);
+    /// Opens (and creates if needed) the database of the app and runs the migrations,
+    /// see app_data_dir for where it's stored
+    pub async fn init(app_id: &str) -> Result<OboeteDb, OboeteError> {
+        let db_path = app_data_dir(app_id)?.join("database").join(DB_NAME);
         if let Some(parent) = db_path.parent() {
-            fs::create_dir_all(parent).expect("Failed to create directories for database file");
+            fs::create_dir_all(parent)?;
         }
 
-        if !db_path.exists() {
-            fs::File::create(&db_path).expect("Failed to create the database file");
-        }
+        OboeteDb::open(&db_path).await
+    }
 
-        let pool = SqlitePool::connect(db_path.into_os_string().to_str().unwrap())
-            .await
-            .expect("Error creating database");
+    /// Opens (and creates if needed) the database file and runs the migrations
+    pub async fn open(db_path: &Path) -> Result<OboeteDb, OboeteError> {
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await?;
 
-        let migrations = Self::migrate_database(&pool).await;
-        match migrations {
-            Ok(_) => println!("Migrations SUCCESSFUL"),
-            Err(_) => println!("Error running migrations"),
-        }
+        migrations::run(&pool).await?;
 
-        OboeteDb { db_pool: pool }
+        Ok(OboeteDb { db_pool: pool })
     }
 
-    async fn migrate_database(db_pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS studysets (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS folders (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                studyset_id INTEGER NOT NULL,
-                FOREIGN KEY (studyset_id) REFERENCES studysets(id) ON DELETE CASCADE
-            );
-
-            CREATE TABLE IF NOT EXISTS flashcards (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                front TEXT NOT NULL,
-                back TEXT NOT NULL,
-                status INTEGER NOT NULL,
-                folder_id INTEGER NOT NULL,
-                FOREIGN KEY (folder_id) REFERENCES folders(id) ON DELETE CASCADE
-            );
-            "#,
-        )
-        .execute(db_pool)
-        .await?;
+    /// Opens an empty database that only lives in memory with every migration applied, for tests
+    pub async fn in_memory() -> Result<OboeteDb, OboeteError> {
+        //Every connection to an in-memory database gets its own one,
+        //so the pool keeps a single connection open for as long as it exists
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?;
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        migrations::run(&pool).await?;
+
+        Ok(OboeteDb { db_pool: pool })
+    }
+}
+
+/// Saves a copy of the database in the directory, named after the current time, and deletes
+/// the oldest backups so only the last ones are kept. Returns the path of the new backup
+pub async fn backup_database(
+    db: Option<OboeteDb>,
+    dest_dir: PathBuf,
+) -> Result<PathBuf, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
 
-        Ok(())
+    fs::create_dir_all(&dest_dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let backup_path = dest_dir.join(format!("{}{}.db", BACKUP_PREFIX, timestamp));
+
+    //VACUUM INTO writes a consistent copy even while the database is in use,
+    //it fails if the file exists (a backup from this same second)
+    if !backup_path.exists() {
+        sqlx::query("VACUUM INTO ?")
+            .bind(backup_path.to_string_lossy().to_string())
+            .execute(&pool.db_pool)
+            .await?;
     }
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dest_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(BACKUP_PREFIX) && name.ends_with(".db"))
+        })
+        .collect();
+    //The timestamps have the same length, so sorting by name sorts them from oldest to newest
+    backups.sort();
+    let outdated = backups.len().saturating_sub(BACKUPS_KEPT);
+    for backup in &backups[..outdated] {
+        fs::remove_file(backup)?;
+    }
+
+    Ok(backup_path)
 }
 
+/// Returns every studyset
 pub async fn get_all_studysets(db: Option<OboeteDb>) -> Result<Vec<StudySet>, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
     let mut rows = sqlx::query("SELECT * FROM studysets ORDER BY id ASC").fetch(&pool.db_pool);
@@ -92,11 +146,15 @@ pub async fn get_all_studysets(db: Option<OboeteDb>) -> Result<Vec<StudySet>, Ob
     while let Some(row) = rows.try_next().await? {
         let id = row.try_get("id").unwrap_or(0);
         let name = row.try_get("name").unwrap_or("Error");
+        let icon = row.try_get("icon").unwrap_or(STUDYSET_ICONS[0]);
+        let color = row.try_get("color").unwrap_or_default();
 
         let studyset = StudySet {
             id: Some(id),
             name: String::from(name),
             folders: Vec::<Folder>::new(),
+            icon: String::from(icon),
+            color,
         };
 
         if let Some(_id) = studyset.id {
@@ -107,20 +165,17 @@ pub async fn get_all_studysets(db: Option<OboeteDb>) -> Result<Vec<StudySet>, Ob
     Ok(result)
 }
 
+/// Creates the studyset when it has no id, otherwise renames it
 pub async fn upsert_studyset(
     db: Option<OboeteDb>,
     studyset: StudySet,
 ) -> Result<StudySet, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = if studyset.id.is_some() {
+    let command = if let Some(id) = studyset.id {
         sqlx::query(
             "UPDATE studysets
                 SET
@@ -130,17 +185,21 @@ pub async fn upsert_studyset(
             ",
         )
         .bind(studyset.name)
-        .bind(studyset.id.unwrap())
+        .bind(id)
         .execute(&pool.db_pool)
         .await
     } else {
         sqlx::query(
             "INSERT INTO studysets (
-                name
+                name,
+                icon,
+                color
             )
-            VALUES (?)",
+            VALUES (?, ?, ?)",
         )
         .bind(studyset.name)
+        .bind(studyset.icon)
+        .bind(studyset.color)
         .execute(&pool.db_pool)
         .await
     };
@@ -160,6 +219,8 @@ pub async fn upsert_studyset(
                         id: row.get("id"),
                         name: row.get("name"),
                         folders: Vec::new(),
+                        icon: row.get("icon"),
+                        color: row.get("color"),
                     };
                     Ok(set)
                 }
@@ -170,33 +231,299 @@ pub async fn upsert_studyset(
     }
 }
 
+/// Creates a studyset with the trimmed name, empty names and names already used
+/// by another studyset are rejected
+pub async fn create_studyset(db: Option<OboeteDb>, name: String) -> Result<StudySet, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(OboeteError::EmptyName);
+    }
+
+    let duplicated: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM studysets WHERE name = $1")
+        .bind(&name)
+        .fetch_one(&pool.db_pool)
+        .await?;
+    if duplicated > 0 {
+        return Err(OboeteError::NameTaken(name));
+    }
+
+    upsert_studyset(Some(pool), StudySet::new(name)).await
+}
+
+/// Renames the studyset and returns the saved name, empty names and names
+/// already used by another studyset are rejected
+pub async fn rename_studyset(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+    new_name: String,
+) -> Result<String, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err(OboeteError::EmptyName);
+    }
+
+    let duplicated: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM studysets WHERE name = $1 AND id != $2")
+            .bind(&new_name)
+            .bind(studyset_id)
+            .fetch_one(&pool.db_pool)
+            .await?;
+    if duplicated > 0 {
+        return Err(OboeteError::NameTaken(new_name));
+    }
+
+    let command = sqlx::query(
+        "UPDATE studysets
+             SET
+                 name = $1
+             WHERE
+                 id = $2",
+    )
+    .bind(&new_name)
+    .bind(studyset_id)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(new_name),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Serializes the studyset, with all its folders and flashcards, to a JSON export document
+pub async fn export_studyset(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+) -> Result<String, OboeteError> {
+    let document = ExportDocument::new(vec![get_full_studyset(db, studyset_id).await?]);
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Serializes every studyset, with all their folders and flashcards, to a single JSON export document
+pub async fn export_all(db: Option<OboeteDb>) -> Result<String, OboeteError> {
+    let document = ExportDocument::new(get_all_full_studysets(db).await?);
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Returns every studyset with all their folders and flashcards
+pub async fn get_all_full_studysets(db: Option<OboeteDb>) -> Result<Vec<StudySet>, OboeteError> {
+    let mut studysets = Vec::new();
+
+    for studyset in get_all_studysets(db.clone()).await? {
+        if let Some(studyset_id) = studyset.id {
+            studysets.push(get_full_studyset(db.clone(), studyset_id).await?);
+        }
+    }
+
+    Ok(studysets)
+}
+
+/// Replaces every studyset with the given ones in a single transaction,
+/// so nothing is lost if the import fails
+pub async fn replace_all_studysets(
+    db: Option<OboeteDb>,
+    studysets: Vec<StudySet>,
+) -> Result<Vec<StudySet>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    //Their folders and flashcards are deleted with them
+    sqlx::query("DELETE FROM studysets")
+        .execute(&mut *transaction)
+        .await?;
+    let created = insert_studysets(&mut transaction, studysets).await?;
+
+    transaction.commit().await?;
+    Ok(created)
+}
+
+/// Recreates the studysets of an export document, they're always inserted
+/// as new rows so the ids in the document never collide with the existing ones
+pub async fn import_all(db: Option<OboeteDb>, json: String) -> Result<Vec<StudySet>, OboeteError> {
+    let document = parse_export_document(&json)?;
+
+    import_studysets(db, document.studysets).await
+}
+
+/// Reads an export document of any version up to the current one, the older ones are migrated.
+/// Documents of a newer version are refused, as this version of the app could lose part of them
+pub fn parse_export_document(json: &str) -> Result<ExportDocument, OboeteError> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+
+    let version = match value.get("schema_version") {
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| OboeteError::Parse(String::from("The schema version isn't a number")))?,
+        None => 0,
+    };
+
+    if version > EXPORT_SCHEMA_VERSION as u64 {
+        return Err(OboeteError::Parse(format!(
+            "The file was exported with a newer format (version {}), this version of Oboete reads up to version {}",
+            version, EXPORT_SCHEMA_VERSION
+        )));
+    }
+
+    match version {
+        //Before the version, export_all wrote a list of studysets and export_studyset a single one
+        0 => {
+            let studysets = if value.is_array() {
+                serde_json::from_value(value)?
+            } else {
+                vec![serde_json::from_value(value)?]
+            };
+            Ok(ExportDocument::new(studysets))
+        }
+        _ => Ok(serde_json::from_value(value)?),
+    }
+}
+
+/// Copies the studyset with all its folders and flashcards, the copies start unseen.
+/// Returns the id of the new studyset, named like the original plus "(copy)"
+pub async fn duplicate_studyset(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+) -> Result<i32, OboeteError> {
+    let pool = match db.clone() {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let studyset = get_full_studyset(db.clone(), studyset_id).await?;
+
+    //Studyset names are unique, so copies of copies get numbered
+    let mut name = format!("{} (copy)", studyset.name);
+    let mut copy_number = 1;
+    loop {
+        let taken: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM studysets WHERE name = ?")
+            .bind(&name)
+            .fetch_one(&pool.db_pool)
+            .await?;
+        if taken == 0 {
+            break;
+        }
+        copy_number += 1;
+        name = format!("{} (copy {})", studyset.name, copy_number);
+    }
+
+    let copy = StudySet {
+        id: None,
+        name,
+        folders: studyset
+            .folders
+            .into_iter()
+            .map(|folder| Folder {
+                id: None,
+                flashcards: folder
+                    .flashcards
+                    .into_iter()
+                    .map(|flashcard| Flashcard {
+                        tags: flashcard.tags,
+                        image_path: flashcard.image_path,
+                        audio_path: flashcard.audio_path,
+                        favorite: flashcard.favorite,
+                        card_type: flashcard.card_type,
+                        ..Flashcard::new(flashcard.front, flashcard.back)
+                    })
+                    .collect(),
+                ..folder
+            })
+            .collect(),
+        icon: studyset.icon,
+        color: studyset.color,
+    };
+
+    import_studysets(db, vec![copy])
+        .await?
+        .first()
+        .and_then(|created| created.id)
+        .ok_or_else(|| OboeteError::NotFound(String::from("The studyset couldn't be duplicated")))
+}
+
+/// Returns the studyset with all its folders and their flashcards
+async fn get_full_studyset(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+) -> Result<StudySet, OboeteError> {
+    let pool = match db.clone() {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row = sqlx::query("SELECT * FROM studysets WHERE id = ?")
+        .bind(studyset_id)
+        .fetch_one(&pool.db_pool)
+        .await?;
+
+    let mut studyset = StudySet {
+        id: row.get("id"),
+        name: row.get("name"),
+        folders: Vec::new(),
+        icon: row.get("icon"),
+        color: row.get("color"),
+    };
+
+    for mut folder in get_studyset_folders(db.clone(), studyset_id).await? {
+        if let Some(folder_id) = folder.id {
+            folder.flashcards = get_folder_flashcards(db.clone(), folder_id).await?;
+        }
+        studyset.folders.push(folder);
+    }
+
+    Ok(studyset)
+}
+
+/// Returns the folders that belong to the studyset
 pub async fn get_studyset_folders(
     db: Option<OboeteDb>,
     id: i32,
 ) -> Result<Vec<Folder>, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let mut rows = sqlx::query("SELECT * FROM folders WHERE studyset_id = ? ORDER BY id ASC")
-        .bind(id)
-        .fetch(&pool.db_pool);
+    let mut rows = sqlx::query(
+        "SELECT folders.*, COUNT(flashcards.id) AS flashcard_count
+         FROM folders
+         LEFT JOIN flashcards ON flashcards.folder_id = folders.id
+         WHERE folders.studyset_id = ?
+         GROUP BY folders.id
+         ORDER BY folders.position ASC, folders.id ASC",
+    )
+    .bind(id)
+    .fetch(&pool.db_pool);
 
     let mut result = Vec::<Folder>::new();
 
     while let Some(row) = rows.try_next().await? {
         let id = row.try_get("id").unwrap_or(0);
         let name = row.try_get("name").unwrap_or("Error");
+        let source_path = row.try_get("source_path").unwrap_or(None);
+        let flashcard_count: i64 = row.try_get("flashcard_count").unwrap_or(0);
 
         let folder = Folder {
             id: Some(id),
             name: String::from(name),
             flashcards: Vec::<Flashcard>::new(),
+            source_path,
+            flashcard_count: flashcard_count as usize,
         };
 
         if let Some(_id) = folder.id {
@@ -207,6 +534,55 @@ pub async fn get_studyset_folders(
     Ok(result)
 }
 
+/// Creates a folder at the end of the studyset for each trimmed name, all of them or none.
+/// Blank names and names already used by a folder of the studyset (or repeated) are skipped.
+/// Returns how many folders were created
+pub async fn create_folders(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+    names: Vec<String>,
+) -> Result<usize, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    let mut taken: HashSet<String> =
+        sqlx::query_scalar("SELECT name FROM folders WHERE studyset_id = ?")
+            .bind(studyset_id)
+            .fetch_all(&mut *transaction)
+            .await?
+            .into_iter()
+            .collect();
+
+    let mut created = 0;
+    for name in names {
+        let name = name.trim().to_string();
+        if name.is_empty() || !taken.insert(name.clone()) {
+            continue;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO folders (name, studyset_id, position)
+            VALUES (?, ?, (SELECT COALESCE(MAX(position) + 1, 0) FROM folders WHERE studyset_id = ?))
+            "#,
+        )
+        .bind(name)
+        .bind(studyset_id)
+        .bind(studyset_id)
+        .execute(&mut *transaction)
+        .await?;
+        created += 1;
+    }
+
+    transaction.commit().await?;
+    Ok(created)
+}
+
+/// Creates the folder (at the end of the studyset) when it has no id, otherwise updates it
 pub async fn upsert_folder(
     db: Option<OboeteDb>,
     folder: Folder,
@@ -214,14 +590,10 @@ pub async fn upsert_folder(
 ) -> Result<i64, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = if folder.id.is_some() {
+    let command = if let Some(id) = folder.id {
         sqlx::query(
             "UPDATE folders
                 SET
@@ -231,18 +603,19 @@ pub async fn upsert_folder(
             ",
         )
         .bind(folder.name)
-        .bind(folder.id.unwrap())
+        .bind(id)
         .execute(&pool.db_pool)
         .await
     } else {
         sqlx::query(
             r#"
-            INSERT INTO folders (name, studyset_id)
-            VALUES (?, ?)
+            INSERT INTO folders (name, studyset_id, position)
+            VALUES (?, ?, (SELECT COALESCE(MAX(position) + 1, 0) FROM folders WHERE studyset_id = ?))
             "#,
         )
         .bind(folder.name)
         .bind(studyset_id)
+        .bind(studyset_id)
         .execute(&pool.db_pool)
         .await
     };
@@ -253,19 +626,96 @@ pub async fn upsert_folder(
     }
 }
 
+/// Creates a folder with the trimmed name at the end of the studyset, empty names and names
+/// already used by another folder of the studyset are rejected. Returns the id of the folder
+pub async fn create_folder(
+    db: Option<OboeteDb>,
+    name: String,
+    studyset_id: i32,
+) -> Result<i64, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(OboeteError::EmptyName);
+    }
+
+    let duplicated: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM folders WHERE name = $1 AND studyset_id = $2")
+            .bind(&name)
+            .bind(studyset_id)
+            .fetch_one(&pool.db_pool)
+            .await?;
+    if duplicated > 0 {
+        return Err(OboeteError::NameTaken(name));
+    }
+
+    upsert_folder(Some(pool), Folder::new(name), studyset_id).await
+}
+
+/// Renames the folder, empty names and names already used by another folder
+/// of its studyset are rejected
+pub async fn rename_folder(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    new_name: String,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(OboeteError::EmptyName);
+    }
+
+    let duplicated: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM folders
+         WHERE name = $1
+             AND id != $2
+             AND studyset_id = (SELECT studyset_id FROM folders WHERE id = $2)",
+    )
+    .bind(new_name)
+    .bind(folder_id)
+    .fetch_one(&pool.db_pool)
+    .await?;
+    if duplicated > 0 {
+        return Err(OboeteError::NameTaken(new_name.to_string()));
+    }
+
+    let command = sqlx::query(
+        "UPDATE folders
+             SET
+                 name = $1
+             WHERE
+                 id = $2",
+    )
+    .bind(new_name)
+    .bind(folder_id)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns the flashcards that belong to the folder
 pub async fn get_folder_flashcards(
     db: Option<OboeteDb>,
     id: i32,
 ) -> Result<Vec<Flashcard>, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
+    let mut tags = get_folder_tags(&pool.db_pool, id).await?;
     let mut rows = sqlx::query("SELECT * FROM flashcards WHERE folder_id = ? ORDER BY id ASC")
         .bind(id)
         .fetch(&pool.db_pool);
@@ -273,26 +723,91 @@ pub async fn get_folder_flashcards(
     let mut result = Vec::<Flashcard>::new();
 
     while let Some(row) = rows.try_next().await? {
-        let id = row.try_get("id").unwrap_or(0);
-        let front = row.try_get("front").unwrap_or("Error");
-        let back = row.try_get("back").unwrap_or("Error");
-        let status = row.try_get("status").unwrap_or_default();
+        let flashcard = flashcard_from_row(&row);
+        if let Some(id) = flashcard.id {
+            result.push(Flashcard {
+                tags: tags.remove(&id).unwrap_or_default(),
+                ..flashcard
+            });
+        }
+    }
 
-        let flashcard: Flashcard = Flashcard {
-            id: Some(id),
-            front: String::from(front),
-            back: String::from(back),
-            status: status,
-        };
+    Ok(result)
+}
 
-        if let Some(_id) = flashcard.id {
-            result.push(flashcard);
-        }
+/// Returns every flashcard of every studyset, with the folder and studyset they belong to
+pub async fn get_all_flashcards(
+    db: Option<OboeteDb>,
+) -> Result<Vec<FlashcardLocation>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut rows = sqlx::query(
+        "SELECT
+            flashcards.id,
+            flashcards.front,
+            flashcards.back,
+            flashcards.status,
+            flashcards.ease_factor,
+            flashcards.interval,
+            flashcards.repetitions,
+            flashcards.due_date,
+            flashcards.leitner_box,
+            flashcards.image_path,
+            flashcards.audio_path,
+            flashcards.created_at,
+            flashcards.average_response_ms,
+            flashcards.response_count,
+            flashcards.favorite,
+            flashcards.card_type,
+            flashcards.good_streak,
+            folders.id AS folder_id,
+            folders.name AS folder_name,
+            studysets.id AS studyset_id,
+            studysets.name AS studyset_name
+        FROM flashcards
+        JOIN folders ON flashcards.folder_id = folders.id
+        JOIN studysets ON folders.studyset_id = studysets.id
+        ORDER BY studysets.id ASC, folders.position ASC, folders.id ASC, flashcards.id ASC",
+    )
+    .fetch(&pool.db_pool);
+
+    let mut result = Vec::<FlashcardLocation>::new();
+
+    while let Some(row) = rows.try_next().await? {
+        let folder_id = row.try_get("folder_id").unwrap_or(0);
+        let folder_name = row.try_get("folder_name").unwrap_or("Error");
+        let studyset_id = row.try_get("studyset_id").unwrap_or(0);
+        let studyset_name = row.try_get("studyset_name").unwrap_or("Error");
+
+        result.push(FlashcardLocation {
+            flashcard: flashcard_from_row(&row),
+            folder_id,
+            folder_name: String::from(folder_name),
+            studyset_id,
+            studyset_name: String::from(studyset_name),
+        });
     }
 
     Ok(result)
 }
 
+/// The flashcards of every folder that have to be studied now
+pub async fn get_all_due_flashcards(
+    db: Option<OboeteDb>,
+) -> Result<Vec<FlashcardLocation>, OboeteError> {
+    let now = scheduler::now();
+
+    Ok(get_all_flashcards(db)
+        .await?
+        .into_iter()
+        .filter(|location| scheduler::is_due(&location.flashcard, now))
+        .collect())
+}
+
+/// Creates the flashcard inside of the folder when it has no id, otherwise updates it
 pub async fn upsert_flashcard(
     db: Option<OboeteDb>,
     flashcard: Flashcard,
@@ -300,140 +815,506 @@ pub async fn upsert_flashcard(
 ) -> Result<i64, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = if flashcard.id.is_some() {
+    let command = if let Some(id) = flashcard.id {
         sqlx::query(
             "UPDATE flashcards
              SET
                  front = $1,
                  back = $2,
-                 status = $3
+                 status = $3,
+                 image_path = $4,
+                 audio_path = $5,
+                 card_type = $6
              WHERE
-                 id = $4",
+                 id = $7",
         )
         .bind(flashcard.front)
         .bind(flashcard.back)
         .bind(flashcard.status)
-        .bind(flashcard.id.unwrap())
+        .bind(flashcard.image_path)
+        .bind(flashcard.audio_path)
+        .bind(flashcard.card_type.to_db())
+        .bind(id)
         .execute(&pool.db_pool)
         .await
     } else {
-        sqlx::query(
-            r#"
-            INSERT INTO flashcards (front, back, status, folder_id)
-            VALUES (?, ?, ?, ?)
-            "#,
-        )
-        .bind(flashcard.front)
-        .bind(flashcard.back)
-        .bind(flashcard.status)
-        .bind(folder_id)
-        .execute(&pool.db_pool)
-        .await
+        let mut connection = pool.db_pool.acquire().await?;
+        insert_flashcard(&mut connection, &flashcard, folder_id).await
     };
 
     match command {
-        Ok(result) => Ok(result.last_insert_rowid()),
+        Ok(result) => {
+            let flashcard_id = flashcard.id.unwrap_or(result.last_insert_rowid() as i32);
+            set_flashcard_tags(Some(pool), flashcard_id, flashcard.tags).await?;
+            Ok(result.last_insert_rowid())
+        }
         Err(err) => Err(err.into()),
     }
 }
 
-pub async fn get_single_flashcard(db: Option<OboeteDb>, id: i32) -> Result<Flashcard, OboeteError> {
+/// Inserts the flashcard in the folder with everything it has (its schedule, stats, media...),
+/// so the imported flashcards keep their progress. Flashcards that haven't been saved get the
+/// current time as their creation time
+async fn insert_flashcard(
+    connection: &mut SqliteConnection,
+    flashcard: &Flashcard,
+    folder_id: i32,
+) -> Result<SqliteQueryResult, sqlx::Error> {
+    let created_at = match flashcard.created_at {
+        0 => scheduler::now(),
+        created_at => created_at,
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO flashcards (
+            front,
+            back,
+            status,
+            ease_factor,
+            interval,
+            repetitions,
+            due_date,
+            leitner_box,
+            image_path,
+            audio_path,
+            folder_id,
+            created_at,
+            average_response_ms,
+            response_count,
+            favorite,
+            card_type,
+            good_streak
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&flashcard.front)
+    .bind(&flashcard.back)
+    .bind(flashcard.status)
+    .bind(flashcard.ease_factor)
+    .bind(flashcard.interval)
+    .bind(flashcard.repetitions)
+    .bind(flashcard.due_date)
+    .bind(flashcard.leitner_box)
+    .bind(&flashcard.image_path)
+    .bind(&flashcard.audio_path)
+    .bind(folder_id)
+    .bind(created_at)
+    .bind(flashcard.average_response_ms)
+    .bind(flashcard.response_count)
+    .bind(flashcard.favorite)
+    .bind(flashcard.card_type.to_db())
+    .bind(flashcard.good_streak)
+    .execute(connection)
+    .await
+}
+
+/// Replaces the tags of the flashcard, they're trimmed and saved without duplicates
+pub async fn set_flashcard_tags(
+    db: Option<OboeteDb>,
+    flashcard_id: i32,
+    tags: Vec<String>,
+) -> Result<(), OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let row_result = sqlx::query("SELECT * FROM flashcards WHERE id = ?")
+    let mut transaction = pool.db_pool.begin().await?;
+    save_flashcard_tags(&mut transaction, flashcard_id, &tags).await?;
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Replaces the tags of the flashcard with the trimmed tags, without duplicates
+async fn save_flashcard_tags(
+    connection: &mut SqliteConnection,
+    flashcard_id: i32,
+    tags: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM flashcard_tags WHERE flashcard_id = ?")
+        .bind(flashcard_id)
+        .execute(&mut *connection)
+        .await?;
+
+    let tags: HashSet<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    for tag in tags {
+        sqlx::query("INSERT INTO flashcard_tags (flashcard_id, tag) VALUES (?, ?)")
+            .bind(flashcard_id)
+            .bind(tag)
+            .execute(&mut *connection)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the flashcards of the folder that have the given tag
+pub async fn get_flashcards_by_tag(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    tag: String,
+) -> Result<Vec<Flashcard>, OboeteError> {
+    let flashcards = get_folder_flashcards(db, folder_id).await?;
+
+    Ok(flashcards
+        .into_iter()
+        .filter(|flashcard| flashcard.tags.contains(&tag))
+        .collect())
+}
+
+/// Returns the flashcards of the folder whose front or back contain the query,
+/// ignoring case and diacritics
+pub async fn search_flashcards(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    query: String,
+) -> Result<Vec<Flashcard>, OboeteError> {
+    //SQLite can't ignore the diacritics, so the flashcards are compared here
+    let query = normalize_for_search(query.trim());
+    let flashcards = get_folder_flashcards(db, folder_id).await?;
+    Ok(flashcards
+        .into_iter()
+        .filter(|flashcard| {
+            normalize_for_search(&flashcard.front).contains(&query)
+                || normalize_for_search(&flashcard.back).contains(&query)
+        })
+        .collect())
+}
+
+/// Tags of every flashcard of the folder (by flashcard id)
+async fn get_folder_tags(
+    db_pool: &Pool<Sqlite>,
+    folder_id: i32,
+) -> Result<HashMap<i32, Vec<String>>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT flashcard_tags.flashcard_id, flashcard_tags.tag
+        FROM flashcard_tags
+        JOIN flashcards ON flashcard_tags.flashcard_id = flashcards.id
+        WHERE flashcards.folder_id = ?
+        ORDER BY flashcard_tags.tag ASC",
+    )
+    .bind(folder_id)
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+    for row in rows {
+        tags.entry(row.get("flashcard_id"))
+            .or_default()
+            .push(row.get("tag"));
+    }
+
+    Ok(tags)
+}
+
+/// The flashcard of a row of the flashcards table, without its tags (they're in their own table).
+/// Missing or invalid columns get the default values
+fn flashcard_from_row(row: &SqliteRow) -> Flashcard {
+    Flashcard {
+        id: Some(row.try_get("id").unwrap_or(0)),
+        front: row.try_get("front").unwrap_or("Error").to_string(),
+        back: row.try_get("back").unwrap_or("Error").to_string(),
+        status: row.try_get("status").unwrap_or_default(),
+        ease_factor: row.try_get("ease_factor").unwrap_or(DEFAULT_EASE_FACTOR),
+        interval: row.try_get("interval").unwrap_or_default(),
+        repetitions: row.try_get("repetitions").unwrap_or_default(),
+        due_date: row.try_get("due_date").unwrap_or_default(),
+        leitner_box: row.try_get("leitner_box").unwrap_or(LEITNER_FIRST_BOX),
+        tags: Vec::new(),
+        image_path: row.try_get("image_path").unwrap_or_default(),
+        audio_path: row.try_get("audio_path").unwrap_or_default(),
+        created_at: row.try_get("created_at").unwrap_or_default(),
+        average_response_ms: row.try_get("average_response_ms").unwrap_or_default(),
+        response_count: row.try_get("response_count").unwrap_or_default(),
+        favorite: row.try_get("favorite").unwrap_or_default(),
+        card_type: CardType::from_db(row.try_get("card_type").unwrap_or_default()),
+        good_streak: row.try_get("good_streak").unwrap_or_default(),
+    }
+}
+
+/// Whether the folder already has a flashcard with the front, ignoring the case and the surrounding
+/// whitespace. It's compared here instead of in SQL as SQLite only lowercases ASCII characters
+pub async fn flashcard_exists(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    front: String,
+) -> Result<bool, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let fronts: Vec<String> =
+        sqlx::query_scalar("SELECT front FROM flashcards WHERE folder_id = ?")
+            .bind(folder_id)
+            .fetch_all(&pool.db_pool)
+            .await?;
+
+    Ok(fronts.iter().any(|existing| same_front(existing, &front)))
+}
+
+/// Returns the flashcard with the given id
+pub async fn get_single_flashcard(db: Option<OboeteDb>, id: i32) -> Result<Flashcard, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row_result = sqlx::query("SELECT * FROM flashcards WHERE id = ?")
         .bind(id)
         .fetch_one(&pool.db_pool)
         .await;
+    let tags: Vec<String> =
+        sqlx::query_scalar("SELECT tag FROM flashcard_tags WHERE flashcard_id = ? ORDER BY tag")
+            .bind(id)
+            .fetch_all(&pool.db_pool)
+            .await?;
 
     match row_result {
-        Ok(row) => {
-            let flashcard: Flashcard = Flashcard {
-                id: row.get("id"),
-                front: row.get("front"),
-                back: row.get("back"),
-                status: row.get("status"),
-            };
-            Ok(flashcard)
-        }
+        Ok(row) => Ok(Flashcard {
+            tags,
+            ..flashcard_from_row(&row)
+        }),
         Err(err) => Err(err.into()),
     }
 }
 
+/// Stars or unstars the flashcard
+pub async fn set_flashcard_favorite(
+    db: Option<OboeteDb>,
+    flashcard_id: i32,
+    favorite: bool,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    sqlx::query("UPDATE flashcards SET favorite = ? WHERE id = ?")
+        .bind(favorite)
+        .bind(flashcard_id)
+        .execute(&pool.db_pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Moves the flashcard to another folder, moving it to the folder it's already in does nothing
+pub async fn move_flashcard(
+    db: Option<OboeteDb>,
+    flashcard_id: i32,
+    target_folder_id: i32,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let current_folder_id: Option<i32> =
+        sqlx::query_scalar("SELECT folder_id FROM flashcards WHERE id = ?")
+            .bind(flashcard_id)
+            .fetch_optional(&pool.db_pool)
+            .await?;
+    let Some(current_folder_id) = current_folder_id else {
+        return Err(OboeteError::FlashcardNotFound);
+    };
+
+    if current_folder_id == target_folder_id {
+        return Ok(());
+    }
+
+    let target_folder: Option<i32> = sqlx::query_scalar("SELECT id FROM folders WHERE id = ?")
+        .bind(target_folder_id)
+        .fetch_optional(&pool.db_pool)
+        .await?;
+    if target_folder.is_none() {
+        return Err(OboeteError::FolderNotFound);
+    }
+
+    sqlx::query(
+        "UPDATE flashcards
+             SET
+                 folder_id = $1
+             WHERE
+                 id = $2",
+    )
+    .bind(target_folder_id)
+    .bind(flashcard_id)
+    .execute(&pool.db_pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Saves the status of the flashcard after being studied, returns the flashcards of the scope
 pub async fn update_flashcard_status(
     db: Option<OboeteDb>,
     flashcard: Flashcard,
-    folder_id: i32,
+    scope: StudyScope,
 ) -> Result<Vec<Flashcard>, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let _command = sqlx::query(
+    save_flashcard_status(&mut *pool.db_pool.acquire().await?, &flashcard).await?;
+
+    get_scope_flashcards(Some(pool), scope).await
+}
+
+/// Saves the status, the schedule and the stats of the flashcard
+async fn save_flashcard_status(
+    connection: &mut SqliteConnection,
+    flashcard: &Flashcard,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
         "UPDATE flashcards
              SET
-                 status = $1
+                 status = $1,
+                 ease_factor = $2,
+                 interval = $3,
+                 repetitions = $4,
+                 due_date = $5,
+                 leitner_box = $6,
+                 average_response_ms = $7,
+                 response_count = $8,
+                 good_streak = $9
              WHERE
-                 id = $2",
+                 id = $10",
     )
     .bind(flashcard.status)
-    .bind(flashcard.id.unwrap())
-    .execute(&pool.db_pool)
-    .await;
+    .bind(flashcard.ease_factor)
+    .bind(flashcard.interval)
+    .bind(flashcard.repetitions)
+    .bind(flashcard.due_date)
+    .bind(flashcard.leitner_box)
+    .bind(flashcard.average_response_ms)
+    .bind(flashcard.response_count)
+    .bind(flashcard.good_streak)
+    .bind(flashcard.id.unwrap_or_default())
+    .execute(connection)
+    .await?;
 
-    let mut rows = sqlx::query("SELECT * FROM flashcards WHERE folder_id = ? ORDER BY id ASC")
-        .bind(folder_id)
-        .fetch(&pool.db_pool);
+    Ok(())
+}
 
-    let mut result = Vec::<Flashcard>::new();
+/// The flashcards of the folder, or the due ones of every folder
+async fn get_scope_flashcards(
+    db: Option<OboeteDb>,
+    scope: StudyScope,
+) -> Result<Vec<Flashcard>, OboeteError> {
+    match scope {
+        StudyScope::Folder(folder_id) => get_folder_flashcards(db, folder_id).await,
+        StudyScope::AllDue => Ok(get_all_due_flashcards(db)
+            .await?
+            .into_iter()
+            .map(|location| location.flashcard)
+            .collect()),
+    }
+}
 
-    while let Some(row) = rows.try_next().await? {
-        let id = row.try_get("id").unwrap_or(0);
-        let front = row.try_get("front").unwrap_or("Error");
-        let back = row.try_get("back").unwrap_or("Error");
-        let status = row.try_get("status").unwrap_or_default();
+/// Records the grade in the review history of the flashcard and saves its new status,
+/// both in a single transaction so the history always matches the status
+pub async fn grade_flashcard(
+    db: Option<OboeteDb>,
+    flashcard: Flashcard,
+    grade: u8,
+    scope: StudyScope,
+) -> Result<Vec<Flashcard>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
 
-        let flashcard: Flashcard = Flashcard {
-            id: Some(id),
-            front: String::from(front),
-            back: String::from(back),
-            status: status,
-        };
+    let mut transaction = pool.db_pool.begin().await?;
 
-        if let Some(_id) = flashcard.id {
-            result.push(flashcard);
-        }
-    }
+    sqlx::query("INSERT INTO review_history (flashcard_id, grade, reviewed_at) VALUES (?, ?, ?)")
+        .bind(flashcard.id.unwrap_or_default())
+        .bind(grade as i32)
+        .bind(scheduler::now())
+        .execute(&mut *transaction)
+        .await?;
+    save_flashcard_status(&mut transaction, &flashcard).await?;
 
-    Ok(result)
+    transaction.commit().await?;
+
+    get_scope_flashcards(Some(pool), scope).await
+}
+
+/// Removes the last grade from the review history of the flashcard and saves the status
+/// it had before it
+pub async fn undo_flashcard_grade(
+    db: Option<OboeteDb>,
+    flashcard: Flashcard,
+    scope: StudyScope,
+) -> Result<Vec<Flashcard>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    sqlx::query(
+        "DELETE FROM review_history WHERE id = (
+             SELECT id FROM review_history WHERE flashcard_id = ? ORDER BY id DESC LIMIT 1
+         )",
+    )
+    .bind(flashcard.id.unwrap_or_default())
+    .execute(&mut *transaction)
+    .await?;
+    save_flashcard_status(&mut transaction, &flashcard).await?;
+
+    transaction.commit().await?;
+
+    get_scope_flashcards(Some(pool), scope).await
+}
+
+/// Every grade the flashcard has been given, the most recent first
+pub async fn get_flashcard_history(
+    db: Option<OboeteDb>,
+    flashcard_id: i32,
+) -> Result<Vec<Review>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let rows = sqlx::query(
+        "SELECT grade, reviewed_at, datetime(reviewed_at, 'unixepoch', 'localtime') AS date
+         FROM review_history
+         WHERE flashcard_id = ?
+         ORDER BY id DESC",
+    )
+    .bind(flashcard_id)
+    .fetch_all(&pool.db_pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| Review {
+            grade: row.try_get("grade").unwrap_or_default(),
+            reviewed_at: row.try_get("reviewed_at").unwrap_or_default(),
+            date: row.try_get("date").unwrap_or_default(),
+        })
+        .collect())
 }
 
+/// Deletes the studyset with all its folders and flashcards
 pub async fn delete_studyset(db: Option<OboeteDb>, id: i32) -> Result<bool, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
     let command = sqlx::query("DELETE FROM studysets WHERE id = ?")
@@ -447,14 +1328,11 @@ pub async fn delete_studyset(db: Option<OboeteDb>, id: i32) -> Result<bool, Oboe
     }
 }
 
+/// Returns the folder with the given id
 pub async fn get_single_folder(db: Option<OboeteDb>, id: i32) -> Result<Folder, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
     let row_result = sqlx::query("SELECT * FROM folders WHERE id = ?")
@@ -468,6 +1346,8 @@ pub async fn get_single_folder(db: Option<OboeteDb>, id: i32) -> Result<Folder,
                 id: row.get("id"),
                 name: row.get("name"),
                 flashcards: Vec::new(),
+                source_path: row.get("source_path"),
+                flashcard_count: 0,
             };
             Ok(folder)
         }
@@ -475,85 +1355,211 @@ pub async fn get_single_folder(db: Option<OboeteDb>, id: i32) -> Result<Folder,
     }
 }
 
+/// Deletes the folder with all its flashcards, the positions of the remaining folders are kept contiguous
 pub async fn delete_folder(db: Option<OboeteDb>, id: i32) -> Result<bool, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = sqlx::query("DELETE FROM folders WHERE id = ?")
+    let mut transaction = pool.db_pool.begin().await?;
+
+    let studyset_id: Option<i32> =
+        sqlx::query_scalar("SELECT studyset_id FROM folders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+    sqlx::query("DELETE FROM folders WHERE id = ?")
         .bind(id)
-        .execute(&pool.db_pool)
-        .await;
+        .execute(&mut *transaction)
+        .await?;
 
-    match command {
-        Ok(_) => Ok(true),
-        Err(err) => Err(err.into()),
+    if let Some(studyset_id) = studyset_id {
+        let folder_ids = get_folder_order(&mut transaction, studyset_id).await?;
+        save_folder_order(&mut transaction, &folder_ids).await?;
     }
+
+    transaction.commit().await?;
+    Ok(true)
 }
 
-pub async fn delete_flashcard(db: Option<OboeteDb>, id: i32) -> Result<(), OboeteError> {
+/// Replaces the text in the given sides of every flashcard of the folder, all in a single transaction.
+/// Returns how many flashcards changed
+pub async fn find_replace_flashcards(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    find: String,
+    replace: String,
+    sides: FlashcardSides,
+    case_sensitive: bool,
+) -> Result<usize, OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = sqlx::query("DELETE FROM flashcards WHERE id = ?")
-        .bind(id)
-        .execute(&pool.db_pool)
-        .await;
+    let mut transaction = pool.db_pool.begin().await?;
 
-    match command {
-        Ok(_) => Ok(()),
-        Err(err) => Err(err.into()),
+    let rows = sqlx::query("SELECT id, front, back FROM flashcards WHERE folder_id = ?")
+        .bind(folder_id)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+    let mut changed = 0;
+    for row in rows {
+        let front: String = row.get("front");
+        let back: String = row.get("back");
+        let Some((front, back)) =
+            replace_flashcard_text(&front, &back, &find, &replace, sides, case_sensitive)
+        else {
+            continue;
+        };
+
+        sqlx::query("UPDATE flashcards SET front = ?, back = ? WHERE id = ?")
+            .bind(front)
+            .bind(back)
+            .bind(row.get::<i32, _>("id"))
+            .execute(&mut *transaction)
+            .await?;
+        changed += 1;
     }
+
+    transaction.commit().await?;
+    Ok(changed)
 }
 
-pub async fn import_flashcards(
+/// Moves every flashcard of the source folder into the target folder and deletes the (now empty)
+/// source, all in a single transaction. Merging a folder with itself does nothing, and both
+/// folders have to belong to the same studyset
+pub async fn merge_folders(
     db: Option<OboeteDb>,
-    flashcards: Vec<Flashcard>,
-    folder_id: i32,
+    source_folder_id: i32,
+    target_folder_id: i32,
 ) -> Result<(), OboeteError> {
-    for flashcard in flashcards {
-        if let Err(err) = upsert_flashcard(db.clone(), flashcard, folder_id).await {
-            return Err(err);
-        }
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    if source_folder_id == target_folder_id {
+        return Ok(());
+    }
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    let studyset_of = |folder_id: i32| {
+        sqlx::query_scalar::<_, i32>("SELECT studyset_id FROM folders WHERE id = ?").bind(folder_id)
+    };
+    let target_studyset_id = studyset_of(target_folder_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+    let studyset_id = studyset_of(source_folder_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+    let (Some(studyset_id), Some(target_studyset_id)) = (studyset_id, target_studyset_id) else {
+        return Err(OboeteError::FolderNotFound);
+    };
+    //A stale or crafted id could move the flashcards to another studyset
+    if studyset_id != target_studyset_id {
+        return Err(OboeteError::Validation(String::from(
+            "Only folders of the same studyset can be merged",
+        )));
     }
+
+    sqlx::query("UPDATE flashcards SET folder_id = $1 WHERE folder_id = $2")
+        .bind(target_folder_id)
+        .bind(source_folder_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM folders WHERE id = ?")
+        .bind(source_folder_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    let folder_ids = get_folder_order(&mut transaction, studyset_id).await?;
+    save_folder_order(&mut transaction, &folder_ids).await?;
+
+    transaction.commit().await?;
     Ok(())
 }
 
-pub async fn reset_single_flashcard_status(
+/// Swaps the position of the folder with the one above or below it, the first folder can't go up
+/// and the last one can't go down
+pub async fn move_folder(
     db: Option<OboeteDb>,
-    flashcard_id: Option<i32>,
+    folder_id: i32,
+    direction: MoveDirection,
 ) -> Result<(), OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    let studyset_id: i32 = sqlx::query_scalar("SELECT studyset_id FROM folders WHERE id = ?")
+        .bind(folder_id)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+    let mut folder_ids = get_folder_order(&mut transaction, studyset_id).await?;
+    if let Some(index) = folder_ids.iter().position(|id| *id == folder_id) {
+        let neighbor = match direction {
+            MoveDirection::Up => index.checked_sub(1),
+            MoveDirection::Down => Some(index + 1).filter(|neighbor| *neighbor < folder_ids.len()),
+        };
+        if let Some(neighbor) = neighbor {
+            folder_ids.swap(index, neighbor);
         }
+    }
+
+    //Every position is saved again, so older folders that share the default position get fixed too
+    save_folder_order(&mut transaction, &folder_ids).await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Ids of the folders of the studyset in the order they're shown
+async fn get_folder_order(
+    connection: &mut SqliteConnection,
+    studyset_id: i32,
+) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM folders WHERE studyset_id = ? ORDER BY position ASC, id ASC")
+        .bind(studyset_id)
+        .fetch_all(connection)
+        .await
+}
+
+/// Numbers the folders from 0 in the given order
+async fn save_folder_order(
+    connection: &mut SqliteConnection,
+    folder_ids: &[i32],
+) -> Result<(), sqlx::Error> {
+    for (position, folder_id) in folder_ids.iter().enumerate() {
+        sqlx::query("UPDATE folders SET position = $1 WHERE id = $2")
+            .bind(position as i32)
+            .bind(folder_id)
+            .execute(&mut *connection)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the flashcard with the given id
+pub async fn delete_flashcard(db: Option<OboeteDb>, id: i32) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = sqlx::query(
-        "UPDATE flashcards
-             SET
-                 status = $1
-             WHERE
-                 id = $2",
-    )
-    .bind(0)
-    .bind(flashcard_id.unwrap())
-    .execute(&pool.db_pool)
-    .await;
+    let command = sqlx::query("DELETE FROM flashcards WHERE id = ?")
+        .bind(id)
+        .execute(&pool.db_pool)
+        .await;
 
     match command {
         Ok(_) => Ok(()),
@@ -561,28 +1567,575 @@ pub async fn reset_single_flashcard_status(
     }
 }
 
-pub async fn reset_folder_flashcard_status(
-    db: Option<OboeteDb>,
-    folder_id: Option<i32>,
-) -> Result<(), OboeteError> {
+/// Deletes every given flashcard in a single transaction, so none is deleted if one fails
+pub async fn delete_flashcards(db: Option<OboeteDb>, ids: Vec<i32>) -> Result<(), OboeteError> {
     let pool = match db {
         Some(db) => db,
-        None => {
-            return Err(OboeteError {
-                message: String::from("Cannot access DB pool"),
-            })
-        }
+        None => return Err(OboeteError::NoDatabase),
     };
 
-    let command = sqlx::query(
-        "UPDATE flashcards
-             SET
-                 status = $1
-             WHERE
+    let mut transaction = pool.db_pool.begin().await?;
+    for id in ids {
+        sqlx::query("DELETE FROM flashcards WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+    }
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Adds the given flashcards to the folder as new flashcards, keeping their progress.
+/// They're added in a single transaction, so none is added if one fails
+pub async fn import_flashcards(
+    db: Option<OboeteDb>,
+    flashcards: Vec<Flashcard>,
+    folder_id: i32,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+    insert_flashcards(&mut transaction, &flashcards, folder_id).await?;
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Inserts the flashcards with their tags in the folder. The ids of imported flashcards
+/// belong to another database, so they're always new rows here
+async fn insert_flashcards(
+    connection: &mut SqliteConnection,
+    flashcards: &[Flashcard],
+    folder_id: i32,
+) -> Result<(), sqlx::Error> {
+    for flashcard in flashcards {
+        let flashcard_id = insert_flashcard(&mut *connection, flashcard, folder_id)
+            .await?
+            .last_insert_rowid() as i32;
+        save_flashcard_tags(&mut *connection, flashcard_id, &flashcard.tags).await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the given studysets with all their folders and flashcards, returns the created studysets.
+/// They're created in a single transaction, so nothing is created if one of them fails
+pub async fn import_studysets(
+    db: Option<OboeteDb>,
+    studysets: Vec<StudySet>,
+) -> Result<Vec<StudySet>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+    let created = insert_studysets(&mut transaction, studysets).await?;
+    transaction.commit().await?;
+
+    Ok(created)
+}
+
+/// Inserts the studysets with their folders and flashcards as new rows,
+/// returns the created studysets without their folders
+async fn insert_studysets(
+    connection: &mut SqliteConnection,
+    studysets: Vec<StudySet>,
+) -> Result<Vec<StudySet>, sqlx::Error> {
+    let mut result = Vec::new();
+
+    for studyset in studysets {
+        let studyset_id = sqlx::query("INSERT INTO studysets (name, icon, color) VALUES (?, ?, ?)")
+            .bind(&studyset.name)
+            .bind(&studyset.icon)
+            .bind(&studyset.color)
+            .execute(&mut *connection)
+            .await?
+            .last_insert_rowid() as i32;
+
+        for (position, folder) in studyset.folders.iter().enumerate() {
+            let folder_id =
+                sqlx::query("INSERT INTO folders (name, studyset_id, position) VALUES (?, ?, ?)")
+                    .bind(&folder.name)
+                    .bind(studyset_id)
+                    .bind(position as i32)
+                    .execute(&mut *connection)
+                    .await?
+                    .last_insert_rowid() as i32;
+            insert_flashcards(&mut *connection, &folder.flashcards, folder_id).await?;
+        }
+
+        result.push(StudySet {
+            id: Some(studyset_id),
+            folders: Vec::new(),
+            ..studyset
+        });
+    }
+
+    Ok(result)
+}
+
+/// Adds the flashcards of the CSV content to the folder, the rows that can't be parsed
+/// don't stop the import, they're reported in the returned error once the rest are added
+pub async fn import_flashcards_csv(
+    db: Option<OboeteDb>,
+    csv: String,
+    folder_id: i32,
+) -> Result<usize, OboeteError> {
+    let (flashcards, failed_rows) = parse_csv_flashcards(&csv);
+    let imported = flashcards.len();
+
+    import_flashcards(db, flashcards, folder_id).await?;
+
+    if failed_rows.is_empty() {
+        Ok(imported)
+    } else {
+        let rows: Vec<String> = failed_rows.iter().map(|row| row.to_string()).collect();
+        Err(OboeteError::Parse(format!(
+            "Imported {} flashcards, could not parse rows: {}",
+            imported,
+            rows.join(", ")
+        )))
+    }
+}
+
+/// Imports the terms and definitions of a Quizlet export, returns how many flashcards were imported
+pub async fn import_quizlet(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    text: String,
+    term_sep: String,
+    card_sep: String,
+) -> Result<usize, OboeteError> {
+    let flashcards = parse_import_content(&card_sep, &term_sep, &text);
+    let imported = flashcards.len();
+
+    import_flashcards(db, flashcards, folder_id).await?;
+
+    Ok(imported)
+}
+
+/// Imports the rows pasted from a spreadsheet (`front<TAB>back`)
+pub async fn import_tsv(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    text: String,
+) -> Result<ImportSummary, OboeteError> {
+    let (flashcards, skipped) = parse_pasted_tsv(&text);
+    let added = flashcards.len();
+
+    import_flashcards(db, flashcards, folder_id).await?;
+
+    Ok(ImportSummary { added, skipped })
+}
+
+/// Sets the status of the flashcard back to new
+pub async fn reset_single_flashcard_status(
+    db: Option<OboeteDb>,
+    flashcard_id: Option<i32>,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let command = sqlx::query(
+        "UPDATE flashcards
+             SET
+                 status = $1,
+                 ease_factor = $3,
+                 interval = 0,
+                 repetitions = 0,
+                 due_date = 0,
+                 leitner_box = $4,
+                 good_streak = 0
+             WHERE
+                 id = $2",
+    )
+    .bind(0)
+    .bind(flashcard_id.unwrap())
+    .bind(DEFAULT_EASE_FACTOR)
+    .bind(LEITNER_FIRST_BOX)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Sets the status of every flashcard of the folder back to new
+pub async fn reset_folder_flashcard_status(
+    db: Option<OboeteDb>,
+    folder_id: Option<i32>,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let command = sqlx::query(
+        "UPDATE flashcards
+             SET
+                 status = $1,
+                 ease_factor = $3,
+                 interval = 0,
+                 repetitions = 0,
+                 due_date = 0,
+                 leitner_box = $4,
+                 good_streak = 0
+             WHERE
                  folder_id = $2",
     )
     .bind(0)
     .bind(folder_id.unwrap())
+    .bind(DEFAULT_EASE_FACTOR)
+    .bind(LEITNER_FIRST_BOX)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Sets the status of every flashcard of every folder of the studyset back to new
+pub async fn reset_studyset_flashcard_status(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let command = sqlx::query(
+        "UPDATE flashcards
+             SET
+                 status = 0,
+                 ease_factor = $1,
+                 interval = 0,
+                 repetitions = 0,
+                 due_date = 0,
+                 leitner_box = $3,
+                 good_streak = 0
+             WHERE
+                 folder_id IN (SELECT id FROM folders WHERE studyset_id = $2)",
+    )
+    .bind(DEFAULT_EASE_FACTOR)
+    .bind(studyset_id)
+    .bind(LEITNER_FIRST_BOX)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns the amount of flashcards inside of every folder of the studyset
+pub async fn count_studyset_flashcards(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+) -> Result<usize, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM flashcards
+         JOIN folders ON flashcards.folder_id = folders.id
+         WHERE folders.studyset_id = ?",
+    )
+    .bind(studyset_id)
+    .fetch_one(&pool.db_pool)
+    .await?;
+
+    Ok(count as usize)
+}
+
+/// Returns the amount of flashcards of every studyset (by id) in a single query,
+/// studysets without flashcards are left out
+pub async fn count_flashcards_by_studyset(
+    db: Option<OboeteDb>,
+) -> Result<HashMap<i32, usize>, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let counts: Vec<(i32, i64)> = sqlx::query_as(
+        "SELECT folders.studyset_id, COUNT(*) FROM flashcards
+         JOIN folders ON flashcards.folder_id = folders.id
+         GROUP BY folders.studyset_id",
+    )
+    .fetch_all(&pool.db_pool)
+    .await?;
+
+    Ok(counts
+        .into_iter()
+        .map(|(studyset_id, count)| (studyset_id, count as usize))
+        .collect())
+}
+
+/// Returns the amount of flashcards inside of the folder
+pub async fn count_folder_flashcards(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+) -> Result<usize, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row_result = sqlx::query("SELECT COUNT(*) AS count FROM flashcards WHERE folder_id = ?")
+        .bind(folder_id)
+        .fetch_one(&pool.db_pool)
+        .await;
+
+    match row_result {
+        Ok(row) => {
+            let count: i64 = row.get("count");
+            Ok(count as usize)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Counts the flashcards of the folder by status
+pub async fn get_folder_stats(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+) -> Result<FolderStats, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let rows = sqlx::query(
+        "SELECT status, COUNT(*) AS count FROM flashcards WHERE folder_id = ? GROUP BY status",
+    )
+    .bind(folder_id)
+    .fetch_all(&pool.db_pool)
+    .await?;
+
+    let mut stats = FolderStats::default();
+    for row in rows {
+        let status: i32 = row.get("status");
+        let count = row.get::<i64, _>("count") as usize;
+
+        stats.total += count;
+        match status {
+            0 => stats.unseen += count,
+            1 => stats.bad += count,
+            2 => stats.ok += count,
+            3 => stats.good += count,
+            _ => {}
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Counts the flashcards of every studyset that have to be studied now (following their due date),
+/// leaving out the ones mastered with the given streak threshold (see `scheduler::is_mastered`),
+/// a threshold of None counts them too
+pub async fn get_due_count(
+    db: Option<OboeteDb>,
+    mastered_streak: Option<usize>,
+) -> Result<i32, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row = sqlx::query(
+        "SELECT COUNT(*) AS count FROM flashcards
+         WHERE due_date <= $1
+             AND ($2 IS NULL OR good_streak < $2 OR due_date + $3 <= $1)",
+    )
+    .bind(scheduler::now())
+    .bind(mastered_streak.map(|streak| streak as i64))
+    .bind(scheduler::MASTERED_REST)
+    .fetch_one(&pool.db_pool)
+    .await?;
+
+    Ok(row.get::<i64, _>("count") as i32)
+}
+
+/// Records a completed study session on the current local date
+pub async fn log_study_session(db: Option<OboeteDb>) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    sqlx::query("INSERT INTO study_log (date) VALUES (date('now', 'localtime'))")
+        .execute(&pool.db_pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Returns true if a study session was completed on the current local date
+pub async fn has_studied_today(db: Option<OboeteDb>) -> Result<bool, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row = sqlx::query(
+        "SELECT EXISTS(SELECT 1 FROM study_log WHERE date = date('now', 'localtime')) AS studied",
+    )
+    .fetch_one(&pool.db_pool)
+    .await?;
+
+    Ok(row.get("studied"))
+}
+
+/// Current local day, weekday and hour, taken from SQLite so they match the study log dates
+pub async fn get_local_time(db: Option<OboeteDb>) -> Result<LocalTime, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row = sqlx::query(
+        "SELECT CAST(julianday(date('now', 'localtime')) AS INTEGER) AS day,
+             CAST(strftime('%w', 'now', 'localtime') AS INTEGER) AS weekday,
+             CAST(strftime('%H', 'now', 'localtime') AS INTEGER) AS hour",
+    )
+    .fetch_one(&pool.db_pool)
+    .await?;
+
+    Ok(LocalTime {
+        day: row.get("day"),
+        weekday: row.get::<i64, _>("weekday") as u32,
+        hour: row.get::<i64, _>("hour") as u32,
+    })
+}
+
+/// Counts the consecutive local days with at least one completed study session.
+/// A streak whose last session was yesterday is kept until today is over
+pub async fn get_current_streak(db: Option<OboeteDb>) -> Result<i32, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    //Days are compared as julian day numbers, so the month and year boundaries don't matter
+    let today: i64 =
+        sqlx::query("SELECT CAST(julianday(date('now', 'localtime')) AS INTEGER) AS today")
+            .fetch_one(&pool.db_pool)
+            .await?
+            .get("today");
+    let days: Vec<i64> = sqlx::query(
+        "SELECT DISTINCT CAST(julianday(date) AS INTEGER) AS day FROM study_log ORDER BY day DESC",
+    )
+    .fetch_all(&pool.db_pool)
+    .await?
+    .iter()
+    .map(|row| row.get("day"))
+    .collect();
+
+    let mut expected = if days.contains(&today) {
+        today
+    } else {
+        today - 1
+    };
+    let mut streak = 0;
+    for day in days {
+        //Sessions logged with the clock set ahead don't break the streak
+        if day > expected {
+            continue;
+        }
+        if day < expected {
+            break;
+        }
+        streak += 1;
+        expected -= 1;
+    }
+
+    Ok(streak)
+}
+
+/// Saves the icon of the studyset
+pub async fn set_studyset_icon(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+    icon: String,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let command = sqlx::query(
+        "UPDATE studysets
+             SET
+                 icon = $1
+             WHERE
+                 id = $2",
+    )
+    .bind(icon)
+    .bind(studyset_id)
+    .execute(&pool.db_pool)
+    .await;
+
+    match command {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Saves (or clears) the accent color of the studyset
+pub async fn set_studyset_color(
+    db: Option<OboeteDb>,
+    studyset_id: i32,
+    color: Option<String>,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    sqlx::query("UPDATE studysets SET color = ? WHERE id = ?")
+        .bind(color)
+        .bind(studyset_id)
+        .execute(&pool.db_pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Saves (or clears) the source file the folder gets synced from
+pub async fn set_folder_source_path(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    source_path: Option<String>,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let command = sqlx::query(
+        "UPDATE folders
+             SET
+                 source_path = $1
+             WHERE
+                 id = $2",
+    )
+    .bind(source_path)
+    .bind(folder_id)
     .execute(&pool.db_pool)
     .await;
 
@@ -591,3 +2144,365 @@ pub async fn reset_folder_flashcard_status(
         Err(err) => Err(err.into()),
     }
 }
+
+/// How the folder is studied, the folders that have never changed it get the defaults
+pub async fn get_folder_study_preferences(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+) -> Result<FolderStudyPreferences, OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let row = sqlx::query("SELECT * FROM folder_study_preferences WHERE folder_id = ?")
+        .bind(folder_id)
+        .fetch_optional(&pool.db_pool)
+        .await?;
+
+    Ok(match row {
+        Some(row) => FolderStudyPreferences {
+            mode: StudyMode::from_db(row.get("study_mode")),
+            direction: StudyDirection::from_db(row.get("study_direction")),
+            order: StudyOrder::from_db(row.get("study_order")),
+            filter: StudyFilter::from_db(row.get("study_filter")),
+        },
+        None => FolderStudyPreferences::default(),
+    })
+}
+
+/// Saves how the folder is studied, nothing is saved if the folder doesn't exist (anymore)
+pub async fn set_folder_study_preferences(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    preferences: FolderStudyPreferences,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    sqlx::query(
+        "INSERT INTO folder_study_preferences
+             (folder_id, study_mode, study_direction, study_order, study_filter)
+         SELECT $1, $2, $3, $4, $5 WHERE EXISTS (SELECT 1 FROM folders WHERE id = $1)
+         ON CONFLICT (folder_id) DO UPDATE
+             SET
+                 study_mode = excluded.study_mode,
+                 study_direction = excluded.study_direction,
+                 study_order = excluded.study_order,
+                 study_filter = excluded.study_filter",
+    )
+    .bind(folder_id)
+    .bind(preferences.mode.to_db())
+    .bind(preferences.direction.to_db())
+    .bind(preferences.order.to_db())
+    .bind(preferences.filter.to_db())
+    .execute(&pool.db_pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Updates the flashcards of the folder that have the same front (compared with [`front_key`]),
+/// inserts the new ones and (optionally) deletes the ones that are missing from the given
+/// flashcards. Everything is done in a single transaction, so the folder is never left half synced
+pub async fn merge_import_flashcards(
+    db: Option<OboeteDb>,
+    flashcards: Vec<Flashcard>,
+    folder_id: i32,
+    delete_missing: bool,
+) -> Result<(), OboeteError> {
+    let pool = match db {
+        Some(db) => db,
+        None => return Err(OboeteError::NoDatabase),
+    };
+
+    let mut transaction = pool.db_pool.begin().await?;
+
+    let existing: Vec<(i32, String, String)> = sqlx::query_as(
+        "SELECT id, front, back FROM flashcards WHERE folder_id = ? ORDER BY id ASC",
+    )
+    .bind(folder_id)
+    .fetch_all(&mut *transaction)
+    .await?;
+    let existing_by_front: HashMap<String, (i32, String)> = existing
+        .into_iter()
+        .map(|(id, front, back)| (front_key(&front), (id, back)))
+        .collect();
+    let mut imported_fronts = HashSet::new();
+
+    for flashcard in flashcards {
+        let front = front_key(&flashcard.front);
+        if !imported_fronts.insert(front.clone()) {
+            continue;
+        }
+
+        match existing_by_front.get(&front) {
+            Some((id, back)) => {
+                if *back != flashcard.back {
+                    sqlx::query("UPDATE flashcards SET back = ? WHERE id = ?")
+                        .bind(&flashcard.back)
+                        .bind(id)
+                        .execute(&mut *transaction)
+                        .await?;
+                }
+            }
+            None => {
+                insert_flashcards(&mut transaction, &[flashcard], folder_id).await?;
+            }
+        }
+    }
+
+    if delete_missing {
+        for (front, (id, _)) in existing_by_front {
+            if !imported_fronts.contains(&front) {
+                sqlx::query("DELETE FROM flashcards WHERE id = ?")
+                    .bind(id)
+                    .execute(&mut *transaction)
+                    .await?;
+            }
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Re-imports the flashcards of a folder from its source file
+pub async fn sync_folder_from_source(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    delete_missing: bool,
+) -> Result<(), OboeteError> {
+    let folder = get_single_folder(db.clone(), folder_id).await?;
+    let source_path = match folder.source_path {
+        Some(source_path) => source_path,
+        None => {
+            return Err(OboeteError::NotFound(String::from(
+                "The folder has no source file",
+            )))
+        }
+    };
+
+    let flashcards = parse_tsv_file(&source_path)?;
+    merge_import_flashcards(db, flashcards, folder_id, delete_missing).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn folder_with(fronts_and_backs: &[(&str, &str)]) -> (Option<OboeteDb>, i32) {
+        let db = Some(OboeteDb::in_memory().await.unwrap());
+        let studyset = create_studyset(db.clone(), String::from("Tests"))
+            .await
+            .unwrap();
+        let folder_id = create_folder(db.clone(), String::from("Folder"), studyset.id.unwrap())
+            .await
+            .unwrap() as i32;
+
+        for (front, back) in fronts_and_backs {
+            let flashcard = Flashcard::new(front.to_string(), back.to_string());
+            upsert_flashcard(db.clone(), flashcard, folder_id)
+                .await
+                .unwrap();
+        }
+
+        (db, folder_id)
+    }
+
+    async fn sides(db: &Option<OboeteDb>, folder_id: i32) -> Vec<(String, String)> {
+        get_folder_flashcards(db.clone(), folder_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|flashcard| (flashcard.front, flashcard.back))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn find_replace_with_case_sensitivity() {
+        let (db, folder_id) = folder_with(&[("Verb: ir", "to go"), ("verb: ser", "to be")]).await;
+
+        let changed = find_replace_flashcards(
+            db.clone(),
+            folder_id,
+            String::from("Verb"),
+            String::from("V."),
+            FlashcardSides::Both,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(
+            sides(&db, folder_id).await,
+            [
+                (String::from("V.: ir"), String::from("to go")),
+                (String::from("verb: ser"), String::from("to be")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_replace_without_case_sensitivity_in_one_side() {
+        let (db, folder_id) = folder_with(&[("TO go", "to go"), ("ir", "To Be")]).await;
+
+        let changed = find_replace_flashcards(
+            db.clone(),
+            folder_id,
+            String::from("to "),
+            String::from(""),
+            FlashcardSides::Back,
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            sides(&db, folder_id).await,
+            [
+                (String::from("TO go"), String::from("go")),
+                (String::from("ir"), String::from("Be")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicated_fronts_ignore_case_and_whitespace() {
+        let (db, folder_id) = folder_with(&[("Ñandú", "rhea")]).await;
+
+        assert!(
+            flashcard_exists(db.clone(), folder_id, String::from("  ñandú "))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !flashcard_exists(db.clone(), folder_id, String::from("nandu"))
+                .await
+                .unwrap()
+        );
+
+        //Other folders don't count
+        let other_id = create_folder(db.clone(), String::from("Other"), 1)
+            .await
+            .unwrap() as i32;
+        assert!(!flashcard_exists(db, other_id, String::from("Ñandú"))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn search_ignores_case_and_diacritics() {
+        let (db, folder_id) = folder_with(&[
+            ("café", "coffee"),
+            ("Thé", "tea"),
+            ("Brötchen", "bread roll"),
+        ])
+        .await;
+
+        let found = |query: &str| {
+            let db = db.clone();
+            let query = query.to_string();
+            async move {
+                search_flashcards(db, folder_id, query)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|flashcard| flashcard.front)
+                    .collect::<Vec<String>>()
+            }
+        };
+
+        assert_eq!(found("CAFE").await, ["café"]);
+        assert_eq!(found(" the ").await, ["Thé"]);
+        assert_eq!(found("brotchen").await, ["Brötchen"]);
+        assert_eq!(found("BREAD").await, ["Brötchen"]);
+        assert!(found("milk").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn folder_study_preferences_round_trip() {
+        let (db, folder_id) = folder_with(&[]).await;
+        assert_eq!(
+            get_folder_study_preferences(db.clone(), folder_id)
+                .await
+                .unwrap(),
+            FolderStudyPreferences::default()
+        );
+
+        let preferences = FolderStudyPreferences {
+            mode: StudyMode::Typed,
+            direction: StudyDirection::BackFirst,
+            order: StudyOrder::OldestFirst,
+            filter: StudyFilter::OnlyBad,
+        };
+        set_folder_study_preferences(db.clone(), folder_id, preferences)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_folder_study_preferences(db.clone(), folder_id)
+                .await
+                .unwrap(),
+            preferences
+        );
+
+        //Missing folders aren't saved
+        set_folder_study_preferences(db.clone(), folder_id + 100, preferences)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_folder_study_preferences(db, folder_id + 100)
+                .await
+                .unwrap(),
+            FolderStudyPreferences::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_import_matches_fronts_ignoring_case_and_whitespace() {
+        let (db, folder_id) =
+            folder_with(&[("Perro", "dog"), ("gato", "cat"), ("pez", "fish")]).await;
+
+        let flashcards = vec![
+            Flashcard::new(String::from("perro "), String::from("hound")),
+            Flashcard::new(String::from("GATO"), String::from("cat")),
+            Flashcard::new(String::from("pájaro"), String::from("bird")),
+        ];
+        merge_import_flashcards(db.clone(), flashcards, folder_id, true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sides(&db, folder_id).await,
+            [
+                (String::from("Perro"), String::from("hound")),
+                (String::from("gato"), String::from("cat")),
+                (String::from("pájaro"), String::from("bird")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cloned_handles_share_the_pool() {
+        let (db, folder_id) = folder_with(&[]).await;
+        let clone = db.clone();
+
+        //The in-memory database only lives in the connection of its pool,
+        //a handle with a pool of its own wouldn't see the flashcard
+        upsert_flashcard(
+            clone.clone(),
+            Flashcard::new(String::from("front"), String::from("back")),
+            folder_id,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sides(&db, folder_id).await.len(), 1);
+
+        let (db, clone) = (db.unwrap(), clone.unwrap());
+        clone.db_pool.close().await;
+        assert!(db.db_pool.is_closed());
+    }
+}
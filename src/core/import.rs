@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+/// Turns imported HTML into plain text: line breaks become newlines, entities are decoded
+/// and every other tag is removed
+pub fn strip_html(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut chars = html.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            //A '<' is only a tag when a '>' closes it before another '<' opens,
+            //so "1 < 2" and unterminated ones ("a <b") are text
+            '<' if chars
+                .peek()
+                .is_some_and(|(_, c)| c.is_alphabetic() || *c == '/' || *c == '!')
+                && closes_tag(&html[index + 1..]) =>
+            {
+                let mut tag = String::new();
+                for (_, c) in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                    tag.push(c);
+                }
+
+                let tag = tag.trim().to_lowercase();
+                let name: String = tag
+                    .trim_start_matches('/')
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric())
+                    .collect();
+                let closing = tag.starts_with('/');
+
+                if name == "br" || (closing && matches!(name.as_str(), "p" | "div" | "li")) {
+                    result.push('\n');
+                }
+            }
+            '&' => {
+                let mut entity = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c == ';' || entity.len() > 8 {
+                        break;
+                    }
+                    entity.push(c);
+                    chars.next();
+                }
+
+                let decoded = if chars.peek().is_some_and(|(_, c)| *c == ';') {
+                    decode_html_entity(&entity)
+                } else {
+                    None
+                };
+
+                match decoded {
+                    Some(decoded) => {
+                        chars.next();
+                        result.push(decoded);
+                    }
+                    //Not an entity, keep it as it was
+                    None => {
+                        result.push('&');
+                        result.push_str(&entity);
+                    }
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// Whether the text after a '<' has a '>' before the next '<'
+fn closes_tag(rest: &str) -> bool {
+    rest.find(['<', '>'])
+        .is_some_and(|end| rest[end..].starts_with('>'))
+}
+
+fn decode_html_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok()
+            } else if let Some(decimal) = entity.strip_prefix('#') {
+                decimal.parse::<u32>().ok()
+            } else {
+                None
+            };
+            code.and_then(char::from_u32)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_html_keeps_lone_less_than() {
+        assert_eq!(strip_html("1 < 2"), "1 < 2");
+        assert_eq!(strip_html("<b>1 <2</b>"), "1 <2");
+    }
+
+    #[test]
+    fn strip_html_keeps_unterminated_tags() {
+        assert_eq!(strip_html("a <b and the rest"), "a <b and the rest");
+        assert_eq!(strip_html("<i>a <b c</i> d"), "a <b c d");
+    }
+
+    #[test]
+    fn strip_html_removes_nested_tags() {
+        assert_eq!(
+            strip_html("<div><b>bold <i>and italic</i></b></div><div>next</div>"),
+            "bold and italic\nnext"
+        );
+        assert_eq!(strip_html("one<br>two<br/>three"), "one\ntwo\nthree");
+        assert_eq!(strip_html("<!-- note -->text"), "text");
+    }
+
+    #[test]
+    fn strip_html_decodes_entities() {
+        assert_eq!(strip_html("&lt;b&gt; &amp; &quot;q&quot;"), "<b> & \"q\"");
+        assert_eq!(strip_html("&#26085;&#x672C;"), "日本");
+        assert_eq!(strip_html("a&nbsp;b"), "a b");
+        assert_eq!(strip_html("AT&T &unknown; &"), "AT&T &unknown; &");
+    }
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use cosmic::iced::keyboard::{key::Named, Key};
+use cosmic::widget::menu::key_bind::{KeyBind, Modifier};
+
+use crate::app::MenuAction;
+
+pub fn key_binds() -> HashMap<KeyBind, MenuAction> {
+    let mut key_binds = HashMap::new();
+
+    macro_rules! bind {
+        ([$($modifier:ident),* $(,)?], $key:expr, $action:ident) => {{
+            key_binds.insert(
+                KeyBind {
+                    modifiers: vec![$(Modifier::$modifier),*],
+                    key: $key,
+                },
+                MenuAction::$action,
+            );
+        }};
+    }
+
+    bind!([Ctrl], Key::Character("n".into()), NewStudySet);
+    bind!([], Key::Named(Named::F2), RenameStudySet);
+    bind!([Ctrl], Key::Character(",".into()), Settings);
+    bind!([Shift], Key::Character("?".into()), KeyboardShortcuts);
+
+    //Only used while studying
+    bind!([], Key::Character("0".into()), GradeAgain);
+    bind!([], Key::Character("1".into()), GradeBad);
+    bind!([], Key::Character("2".into()), GradeOk);
+    bind!([], Key::Character("3".into()), GradeGood);
+    bind!([], Key::Character("4".into()), GradeEasy);
+    bind!([], Key::Character("j".into()), GradeBad);
+    bind!([], Key::Character("k".into()), GradeOk);
+    bind!([], Key::Character("l".into()), GradeGood);
+    bind!([], Key::Named(Named::Space), FlipFlashcard);
+    bind!([Ctrl], Key::Character("z".into()), UndoGrade);
+    bind!([], Key::Named(Named::ArrowLeft), PreviousFlashcard);
+    bind!([], Key::Named(Named::ArrowRight), NextFlashcard);
+
+    key_binds
+}
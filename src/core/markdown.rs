@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        alignment::Horizontal,
+        font::{Font, Weight},
+        Length,
+    },
+    theme, widget, Element,
+};
+
+use crate::utils::break_long_words;
+
+/// Max length of a word before it's forced to wrap
+const MAX_WORD_LENGTH: usize = 20;
+
+/// The subset of Markdown that gets rendered, everything else is shown as plain text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownBlock {
+    //The u8 is the heading level (1 to 3)
+    Heading(u8, String),
+    Bullet(String),
+    Code(String),
+    Paragraph { text: String, bold: bool },
+}
+
+/// Splits the text into blocks, inline markers (bold, italic and code) are removed
+/// because a line can only be rendered with a single style
+pub fn parse_markdown(text: &str) -> Vec<MarkdownBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with("```"))
+                .collect();
+            blocks.push(MarkdownBlock::Code(code.join("\n")));
+        } else if let Some((level, heading)) = parse_heading(trimmed) {
+            blocks.push(MarkdownBlock::Heading(level, strip_inline(heading)));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            blocks.push(MarkdownBlock::Bullet(strip_inline(item)));
+        } else if !trimmed.is_empty() {
+            let bold = trimmed.len() > 4
+                && ((trimmed.starts_with("**") && trimmed.ends_with("**"))
+                    || (trimmed.starts_with("__") && trimmed.ends_with("__")));
+            blocks.push(MarkdownBlock::Paragraph {
+                text: strip_inline(trimmed),
+                bold,
+            });
+        }
+    }
+
+    blocks
+}
+
+/// The text without any Markdown markers, used where only a single line of text fits
+pub fn markdown_to_plain_text(text: &str) -> String {
+    parse_markdown(text)
+        .into_iter()
+        .map(|block| match block {
+            MarkdownBlock::Heading(_, text)
+            | MarkdownBlock::Bullet(text)
+            | MarkdownBlock::Code(text)
+            | MarkdownBlock::Paragraph { text, .. } => text,
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Renders the blocks as a column of text widgets, long words are broken so they can't overflow
+pub fn view_markdown<'a, Message: 'static>(text: &str, size: u16) -> Element<'a, Message> {
+    let spacing = theme::active().cosmic().spacing;
+    let blocks = parse_markdown(text);
+    let mut column = widget::column::with_capacity(blocks.len())
+        .spacing(spacing.space_xxs)
+        .width(Length::Fill);
+
+    for block in blocks {
+        let element: Element<'a, Message> = match block {
+            MarkdownBlock::Heading(level, text) => {
+                widget::text(break_long_words(&text, MAX_WORD_LENGTH))
+                    .size(size + (4 - level as u16) * 4)
+                    .font(Font {
+                        weight: Weight::Bold,
+                        ..Font::DEFAULT
+                    })
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center)
+                    .into()
+            }
+            MarkdownBlock::Bullet(text) => {
+                widget::text(format!("•  {}", break_long_words(&text, MAX_WORD_LENGTH)))
+                    .size(size)
+                    .width(Length::Fill)
+                    .into()
+            }
+            MarkdownBlock::Code(code) => widget::container(
+                widget::text(break_long_words(&code, MAX_WORD_LENGTH))
+                    .size(size.saturating_sub(4).max(12))
+                    .font(Font::MONOSPACE)
+                    .width(Length::Fill),
+            )
+            .style(theme::Container::Card)
+            .padding(spacing.space_xxs)
+            .width(Length::Fill)
+            .into(),
+            MarkdownBlock::Paragraph { text, bold } => {
+                let paragraph = widget::text(break_long_words(&text, MAX_WORD_LENGTH))
+                    .size(size)
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center);
+                if bold {
+                    paragraph
+                        .font(Font {
+                            weight: Weight::Bold,
+                            ..Font::DEFAULT
+                        })
+                        .into()
+                } else {
+                    paragraph.into()
+                }
+            }
+        };
+        column = column.push(element);
+    }
+
+    column.into()
+}
+
+/// Headings go from # to ###, deeper ones are shown as the smallest heading
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 {
+        return None;
+    }
+
+    line[level..]
+        .strip_prefix(' ')
+        .map(|heading| (level.min(3) as u8, heading.trim()))
+}
+
+/// Removes the bold, italic and inline code markers, snake_case words are left untouched
+fn strip_inline(text: &str) -> String {
+    let text = text.replace("**", "").replace("__", "").replace('`', "");
+
+    text.split(' ')
+        .map(|word| {
+            let inner = word.trim_matches(|c| c == '*' || c == '_');
+            if inner.is_empty() {
+                word
+            } else {
+                inner
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+/// Replaces the inline math (`$...$`) of the text with its Unicode rendering,
+/// formulas that use unsupported commands are left as they were written
+pub fn render_inline_math(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        match find_closing_dollar(after) {
+            Some(end) => {
+                let source = &after[..end];
+                match render_formula(source) {
+                    Some(rendered) => result.push_str(&rendered),
+                    None => {
+                        result.push('$');
+                        result.push_str(source);
+                        result.push('$');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('$');
+                rest = after;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Like in most Markdown flavours, the formula can't start or end with a space
+/// and the closing $ can't be followed by a digit, so prices ($5 and $10) aren't math
+fn find_closing_dollar(text: &str) -> Option<usize> {
+    if text.starts_with(char::is_whitespace) || text.starts_with('$') {
+        return None;
+    }
+
+    text.char_indices().find_map(|(index, c)| {
+        let closes = c == '$'
+            && index > 0
+            && !text[..index].ends_with(char::is_whitespace)
+            && !text[..index].ends_with('\\')
+            && !text[index + 1..].starts_with(|c: char| c.is_ascii_digit());
+        closes.then_some(index)
+    })
+}
+
+/// Renders a formula, returns None if it has something that can't be shown with Unicode
+fn render_formula(source: &str) -> Option<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut position = 0;
+    let rendered = render_group(&chars, &mut position, false)?;
+    (position == chars.len()).then_some(rendered)
+}
+
+/// Renders until the end of the formula or (inside of braces) until the closing brace
+fn render_group(chars: &[char], position: &mut usize, in_braces: bool) -> Option<String> {
+    let mut result = String::new();
+
+    while *position < chars.len() {
+        let c = chars[*position];
+        *position += 1;
+
+        match c {
+            '}' if in_braces => return Some(result),
+            '}' => return None,
+            '{' => result.push_str(&render_group(chars, position, true)?),
+            '^' => {
+                let argument = render_argument(chars, position)?;
+                result.push_str(&map_chars(&argument, superscript)?);
+            }
+            '_' => {
+                let argument = render_argument(chars, position)?;
+                result.push_str(&map_chars(&argument, subscript)?);
+            }
+            '\\' => result.push_str(&render_command(chars, position)?),
+            c => result.push(c),
+        }
+    }
+
+    //A brace was never closed
+    (!in_braces).then_some(result)
+}
+
+/// The argument of ^, _ or a command: a group in braces, a command or a single character
+fn render_argument(chars: &[char], position: &mut usize) -> Option<String> {
+    let c = *chars.get(*position)?;
+    *position += 1;
+
+    match c {
+        '{' => render_group(chars, position, true),
+        '\\' => render_command(chars, position),
+        c => Some(c.to_string()),
+    }
+}
+
+fn render_command(chars: &[char], position: &mut usize) -> Option<String> {
+    let name: String = chars[*position..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+
+    //Escaped symbols (\{, \$, \%...)
+    if name.is_empty() {
+        let c = *chars.get(*position)?;
+        *position += 1;
+        return Some(c.to_string());
+    }
+    *position += name.chars().count();
+
+    match name.as_str() {
+        "frac" => {
+            let numerator = render_argument(chars, position)?;
+            let denominator = render_argument(chars, position)?;
+            Some(format!(
+                "{}/{}",
+                parenthesize(&numerator),
+                parenthesize(&denominator)
+            ))
+        }
+        "sqrt" => {
+            let radicand = render_argument(chars, position)?;
+            Some(format!("√{}", parenthesize(&radicand)))
+        }
+        name => symbol(name).map(String::from),
+    }
+}
+
+/// Multi character expressions get parentheses so the result can't be misread
+fn parenthesize(text: &str) -> String {
+    if text.chars().count() > 1 {
+        format!("({})", text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn map_chars(text: &str, map: fn(char) -> Option<char>) -> Option<String> {
+    text.chars().map(map).collect()
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" | "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "propto" => "∝",
+        "infty" => "∞",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "oint" => "∮",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "to" | "rightarrow" => "→",
+        "leftarrow" => "←",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftrightarrow" | "iff" => "⇔",
+        "in" => "∈",
+        "notin" => "∉",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "cup" => "∪",
+        "cap" => "∩",
+        "emptyset" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "angle" => "∠",
+        "degree" | "circ" => "°",
+        "cdots" | "ldots" | "dots" => "…",
+        "quad" | "qquad" => "  ",
+        _ => return None,
+    })
+}
+
+fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        ' ' => ' ',
+        '°' => '°',
+        _ => return None,
+    })
+}
+
+fn subscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        ' ' => ' ',
+        _ => return None,
+    })
+}
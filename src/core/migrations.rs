@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use sqlx::{Pool, Row, Sqlite, SqliteConnection};
+
+use crate::utils::OboeteError;
+
+/// A change to the schema of the database, the version of the schema is the
+/// number of migrations that have been applied
+enum Migration {
+    Sql(&'static str),
+    /// Databases created before the schema was versioned may already have the column,
+    /// so it's only added if it doesn't exist (ALTER TABLE would fail otherwise)
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+    },
+}
+
+/// Every migration in the order it has to be applied, new ones go at the end
+/// and the ones that have been released must never be changed
+const MIGRATIONS: &[Migration] = &[
+    // 1
+    Migration::Sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS studysets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS folders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            studyset_id INTEGER NOT NULL,
+            FOREIGN KEY (studyset_id) REFERENCES studysets(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS flashcards (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            front TEXT NOT NULL,
+            back TEXT NOT NULL,
+            status INTEGER NOT NULL,
+            folder_id INTEGER NOT NULL,
+            FOREIGN KEY (folder_id) REFERENCES folders(id) ON DELETE CASCADE
+        );
+        "#,
+    ),
+    // 2
+    Migration::AddColumn {
+        table: "folders",
+        column: "source_path",
+        definition: "TEXT",
+    },
+    // 3
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "ease_factor",
+        definition: "REAL NOT NULL DEFAULT 2.5",
+    },
+    // 4
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "interval",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 5
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "repetitions",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 6
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "due_date",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 7
+    Migration::AddColumn {
+        table: "studysets",
+        column: "icon",
+        definition: "TEXT NOT NULL DEFAULT 'folder-symbolic'",
+    },
+    // 8
+    Migration::Sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS flashcard_tags (
+            flashcard_id INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (flashcard_id, tag),
+            FOREIGN KEY (flashcard_id) REFERENCES flashcards(id) ON DELETE CASCADE
+        );
+        "#,
+    ),
+    // 9
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "image_path",
+        definition: "TEXT",
+    },
+    // 10
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "audio_path",
+        definition: "TEXT",
+    },
+    // 11
+    Migration::AddColumn {
+        table: "folders",
+        column: "position",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 12
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "created_at",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 13
+    Migration::AddColumn {
+        table: "studysets",
+        column: "color",
+        definition: "TEXT",
+    },
+    // 14
+    Migration::Sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS study_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL
+        );
+        "#,
+    ),
+    // 15
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "leitner_box",
+        definition: "INTEGER NOT NULL DEFAULT 1",
+    },
+    // 16
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "average_response_ms",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 17
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "response_count",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 18
+    Migration::Sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS review_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            flashcard_id INTEGER NOT NULL,
+            grade INTEGER NOT NULL,
+            reviewed_at INTEGER NOT NULL,
+            FOREIGN KEY (flashcard_id) REFERENCES flashcards(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS review_history_flashcard ON review_history (flashcard_id);
+        "#,
+    ),
+    // 19
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "favorite",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 20
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "card_type",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 21
+    Migration::AddColumn {
+        table: "flashcards",
+        column: "good_streak",
+        definition: "INTEGER NOT NULL DEFAULT 0",
+    },
+    // 22
+    Migration::Sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS folder_study_preferences (
+            folder_id INTEGER PRIMARY KEY,
+            study_mode INTEGER NOT NULL DEFAULT 0,
+            study_direction INTEGER NOT NULL DEFAULT 0,
+            study_order INTEGER NOT NULL DEFAULT 0,
+            study_filter INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (folder_id) REFERENCES folders(id) ON DELETE CASCADE
+        );
+        "#,
+    ),
+];
+
+/// The schema version a database has once every migration is applied
+pub const LATEST_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Applies the migrations the database doesn't have yet, each one in its own transaction
+/// together with the new user_version, so a failed migration leaves the previous version.
+/// Returns the version the database ends at, databases of a newer version of the app are refused
+pub async fn run(db_pool: &Pool<Sqlite>) -> Result<i64, OboeteError> {
+    let mut version = schema_version(db_pool).await?;
+
+    if version > LATEST_VERSION {
+        return Err(OboeteError::NewerSchema(version));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        let mut transaction = db_pool.begin().await?;
+
+        apply(&mut transaction, migration).await?;
+        version += 1;
+        //PRAGMA doesn't accept bound parameters
+        sqlx::query(&format!("PRAGMA user_version = {}", version))
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+    }
+
+    Ok(version)
+}
+
+/// The schema version stored in the database, 0 for new databases and for
+/// the ones created before the schema was versioned
+pub async fn schema_version(db_pool: &Pool<Sqlite>) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query("PRAGMA user_version")
+        .fetch_one(db_pool)
+        .await?;
+
+    Ok(row.get(0))
+}
+
+async fn apply(
+    connection: &mut SqliteConnection,
+    migration: &Migration,
+) -> Result<(), sqlx::Error> {
+    match migration {
+        Migration::Sql(sql) => {
+            sqlx::query(sql).execute(&mut *connection).await?;
+        }
+        Migration::AddColumn {
+            table,
+            column,
+            definition,
+        } => {
+            let row =
+                sqlx::query("SELECT COUNT(*) AS count FROM pragma_table_info(?) WHERE name = ?")
+                    .bind(table)
+                    .bind(column)
+                    .fetch_one(&mut *connection)
+                    .await?;
+            let count: i64 = row.get("count");
+
+            if count == 0 {
+                sqlx::query(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    table, column, definition
+                ))
+                .execute(&mut *connection)
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,13 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-pub mod database;
+//The modules that don't need the interface live in the library, the database is reached through the api
+pub use oboete::core::{api, math, scheduler};
+
+pub mod audio;
+pub mod config;
 pub mod icon_cache;
+pub mod key_binds;
 pub mod localization;
+pub mod markdown;
+pub mod notifications;
 pub mod settings;
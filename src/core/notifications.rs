@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use ashpd::desktop::notification::{Notification, NotificationProxy, Priority};
+
+use crate::utils::OboeteError;
+
+/// Id of the study reminder, a new reminder replaces the previous one if it's still shown
+const STUDY_REMINDER_ID: &str = "study-reminder";
+
+/// Sends the study reminder through the notification portal
+pub async fn send_study_reminder(title: String, body: String) -> Result<(), OboeteError> {
+    let proxy = NotificationProxy::new()
+        .await
+        .map_err(|err| OboeteError::Notification(err.to_string()))?;
+    proxy
+        .add_notification(
+            STUDY_REMINDER_ID,
+            Notification::new(&title)
+                .body(body.as_str())
+                .priority(Priority::Normal),
+        )
+        .await
+        .map_err(|err| OboeteError::Notification(err.to_string()))
+}
@@ -0,0 +1,393 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io::Write;
+
+use crate::{
+    core::database::{get_folder_flashcards, get_single_folder, OboeteDb},
+    models::Flashcard,
+    utils::OboeteError,
+};
+
+/// A4 page, in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const TITLE_SIZE: f32 = 18.0;
+const TEXT_SIZE: f32 = 11.0;
+const CARD_TEXT_SIZE: f32 = 14.0;
+/// Distance between the baselines of two lines, relative to the font size
+const LINE_SPACING: f32 = 1.3;
+const COLUMN_GAP: f32 = 20.0;
+const CARD_COLUMNS: usize = 2;
+const CARD_ROWS: usize = 4;
+const CARD_PADDING: f32 = 12.0;
+
+/// How the flashcards are laid out on the pages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PdfLayout {
+    /// A table with the front of every flashcard next to its back
+    #[default]
+    TwoColumns,
+    /// Cards with dashed borders to cut out, every page of fronts is followed by their backs
+    /// mirrored, so they line up when printed on both sides
+    CutOutCards,
+}
+
+/// Writes the flashcards of the folder to a printable PDF, titled with the name of the folder
+pub async fn export_folder_pdf(
+    db: Option<OboeteDb>,
+    folder_id: i32,
+    path: String,
+    layout: PdfLayout,
+) -> Result<(), OboeteError> {
+    let folder = get_single_folder(db.clone(), folder_id).await?;
+    let flashcards = get_folder_flashcards(db, folder_id).await?;
+
+    let document = match layout {
+        PdfLayout::TwoColumns => two_columns_document(&folder.name, &flashcards),
+        PdfLayout::CutOutCards => cut_out_cards_document(&flashcards),
+    };
+    tokio::fs::write(path, document.finish()?).await?;
+
+    Ok(())
+}
+
+/// Title followed by a row per flashcard, a row that doesn't fit continues on the next page
+fn two_columns_document(title: &str, flashcards: &[Flashcard]) -> PdfDocument {
+    let mut document = PdfDocument::new();
+    let column_width = (PAGE_WIDTH - 2.0 * MARGIN - COLUMN_GAP) / 2.0;
+    let line_height = TEXT_SIZE * LINE_SPACING;
+
+    let mut y = PAGE_HEIGHT - MARGIN - TITLE_SIZE;
+    document.text(MARGIN, y, TITLE_SIZE, true, title);
+    y -= TITLE_SIZE;
+
+    for flashcard in flashcards {
+        let front = wrap_text(&flashcard.front, TEXT_SIZE, true, column_width);
+        let back = wrap_text(&flashcard.back, TEXT_SIZE, false, column_width);
+
+        for line in 0..front.len().max(back.len()) {
+            if y - line_height < MARGIN {
+                document.new_page();
+                y = PAGE_HEIGHT - MARGIN;
+            }
+            y -= line_height;
+            if let Some(text) = front.get(line) {
+                document.text(MARGIN, y, TEXT_SIZE, true, text);
+            }
+            if let Some(text) = back.get(line) {
+                document.text(
+                    MARGIN + column_width + COLUMN_GAP,
+                    y,
+                    TEXT_SIZE,
+                    false,
+                    text,
+                );
+            }
+        }
+
+        y -= line_height / 2.0;
+        document.line(MARGIN, y, PAGE_WIDTH - MARGIN, y, false);
+    }
+
+    document
+}
+
+/// Pages of 8 cards, each one followed by the page with their backs
+fn cut_out_cards_document(flashcards: &[Flashcard]) -> PdfDocument {
+    let mut document = PdfDocument::new();
+    let card_width = (PAGE_WIDTH - 2.0 * MARGIN) / CARD_COLUMNS as f32;
+    let card_height = (PAGE_HEIGHT - 2.0 * MARGIN) / CARD_ROWS as f32;
+
+    for (page, cards) in flashcards.chunks(CARD_COLUMNS * CARD_ROWS).enumerate() {
+        if page > 0 {
+            document.new_page();
+        }
+        for back in [false, true] {
+            if back {
+                document.new_page();
+            }
+
+            for (index, flashcard) in cards.iter().enumerate() {
+                let row = index / CARD_COLUMNS;
+                //The backs are mirrored, as the sheet is turned over along its long side
+                let column = match back {
+                    false => index % CARD_COLUMNS,
+                    true => CARD_COLUMNS - 1 - index % CARD_COLUMNS,
+                };
+                let x = MARGIN + column as f32 * card_width;
+                let top = PAGE_HEIGHT - MARGIN - row as f32 * card_height;
+                let text = match back {
+                    false => &flashcard.front,
+                    true => &flashcard.back,
+                };
+
+                document.rectangle(x, top - card_height, card_width, card_height, true);
+                document.card_text(x, top, card_width, card_height, text, !back);
+            }
+        }
+    }
+
+    document
+}
+
+/// Minimal PDF writer using the standard Helvetica fonts, every reader has them so nothing is
+/// embedded, but only the characters of the Windows-1252 encoding can be shown
+struct PdfDocument {
+    /// Content stream of every page
+    pages: Vec<Vec<u8>>,
+}
+
+impl PdfDocument {
+    fn new() -> PdfDocument {
+        PdfDocument {
+            pages: vec![Vec::new()],
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Vec::new());
+    }
+
+    fn content(&mut self) -> &mut Vec<u8> {
+        self.pages
+            .last_mut()
+            .expect("the document always has a page")
+    }
+
+    /// Writes a line of text with its baseline at y
+    fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, text: &str) {
+        let font = if bold { "F2" } else { "F1" };
+        let content = self.content();
+        let _ = write!(
+            content,
+            "BT /{} {:.1} Tf {:.2} {:.2} Td (",
+            font, size, x, y
+        );
+        content.extend(encode_text(text));
+        content.extend_from_slice(b") Tj ET\n");
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, dashed: bool) {
+        let dash = if dashed { "[4 3] 0 d" } else { "[] 0 d" };
+        let _ = writeln!(
+            self.content(),
+            "0.6 G 0.5 w {} {:.2} {:.2} m {:.2} {:.2} l S",
+            dash,
+            x1,
+            y1,
+            x2,
+            y2
+        );
+    }
+
+    fn rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, dashed: bool) {
+        let dash = if dashed { "[4 3] 0 d" } else { "[] 0 d" };
+        let _ = writeln!(
+            self.content(),
+            "0.6 G 0.5 w {} {:.2} {:.2} {:.2} {:.2} re S",
+            dash,
+            x,
+            y,
+            width,
+            height
+        );
+    }
+
+    /// Text centered in a card, the lines that don't fit are cut and the last one ends in an ellipsis
+    fn card_text(&mut self, x: f32, top: f32, width: f32, height: f32, text: &str, bold: bool) {
+        let line_height = CARD_TEXT_SIZE * LINE_SPACING;
+        let max_width = width - 2.0 * CARD_PADDING;
+        let max_lines = ((height - 2.0 * CARD_PADDING) / line_height).floor() as usize;
+
+        let mut lines = wrap_text(text, CARD_TEXT_SIZE, bold, max_width);
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                while !last.is_empty()
+                    && text_width(&format!("{}…", last), CARD_TEXT_SIZE, bold) > max_width
+                {
+                    last.pop();
+                }
+                last.push('…');
+            }
+        }
+
+        let block_height = lines.len() as f32 * line_height;
+        let mut y = top - (height - block_height) / 2.0 - CARD_TEXT_SIZE;
+        for line in lines {
+            let line_x = x + (width - text_width(&line, CARD_TEXT_SIZE, bold)) / 2.0;
+            self.text(line_x, y, CARD_TEXT_SIZE, bold, &line);
+            y -= line_height;
+        }
+    }
+
+    /// Numbers the pages and builds the file
+    fn finish(mut self) -> Result<Vec<u8>, OboeteError> {
+        let page_count = self.pages.len();
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            let number = format!("{} / {}", index + 1, page_count);
+            let x = (PAGE_WIDTH - text_width(&number, 9.0, false)) / 2.0;
+            writeln!(
+                page,
+                "0 g BT /F1 9.0 Tf {:.2} {:.2} Td ({}) Tj ET",
+                x,
+                MARGIN / 2.0,
+                number
+            )?;
+        }
+
+        //1 is the catalog, 2 the page tree, 3 and 4 the fonts and then every page and its content
+        let mut objects: Vec<Vec<u8>> = Vec::with_capacity(4 + page_count * 2);
+        let kids: Vec<String> = (0..page_count)
+            .map(|index| format!("{} 0 R", 5 + index * 2))
+            .collect();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_count
+            )
+            .into_bytes(),
+        );
+        for font in ["Helvetica", "Helvetica-Bold"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font
+                )
+                .into_bytes(),
+            );
+        }
+        for (index, content) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    6 + index * 2
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut file = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(file.len());
+            writeln!(file, "{} 0 obj", index + 1)?;
+            file.extend_from_slice(object);
+            file.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = file.len();
+        write!(file, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1)?;
+        for offset in offsets {
+            writeln!(file, "{:010} 00000 n ", offset)?;
+        }
+        write!(
+            file,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )?;
+
+        Ok(file)
+    }
+}
+
+/// Splits the text in lines no wider than max_width, words that are too long are split too
+fn wrap_text(text: &str, size: f32, bold: bool, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.trim().lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if text_width(&candidate, size, bold) <= max_width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if text_width(&line, size, bold) > max_width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Width of the text in points, from the Helvetica metrics (the bold one is a bit wider)
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units: u32 = text.chars().map(char_width).sum();
+    let factor = if bold { 1.08 } else { 1.0 };
+    units as f32 * size / 1000.0 * factor
+}
+
+/// Width of a Helvetica character in thousandths of the font size
+fn char_width(c: char) -> u32 {
+    const ASCII_WIDTHS: [u32; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556,
+        556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722,
+        722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722,
+        667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556,
+        556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500,
+        500, 334, 260, 334, 584,
+    ];
+
+    match c as u32 {
+        code @ 32..=126 => ASCII_WIDTHS[(code - 32) as usize],
+        _ => 556,
+    }
+}
+
+/// Encodes the text for a PDF string in Windows-1252, the characters it doesn't have become "?"
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            '\t' | '\n' | '\r' => b' ',
+            ' '..='~' | '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+
+    bytes
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::{
+    core::database::{get_due_count, get_local_time, has_studied_today, OboeteDb},
+    utils::OboeteError,
+};
+
+/// When the study reminders can be sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderSchedule {
+    /// Days of the week the reminder is sent on, starting on Sunday
+    pub weekdays: [bool; 7],
+    /// Hour of the day (0-23) from which the reminder is sent
+    pub hour: u32,
+    /// Hours (from, until) without reminders, they can go past midnight like (22, 8)
+    pub quiet_hours: Option<(u32, u32)>,
+}
+
+impl Default for ReminderSchedule {
+    fn default() -> Self {
+        Self {
+            weekdays: [true; 7],
+            hour: 18,
+            quiet_hours: None,
+        }
+    }
+}
+
+/// Current local day and time, as SQLite sees it like the dates of the study log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    /// Julian day number, it changes at local midnight
+    pub day: i64,
+    /// Day of the week, 0 is Sunday
+    pub weekday: u32,
+    pub hour: u32,
+}
+
+/// A reminder that has to be sent now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StudyReminder {
+    /// Day it's sent on, no other reminder is sent until the next day
+    pub day: i64,
+    /// Flashcards due now
+    pub due_count: i32,
+}
+
+impl ReminderSchedule {
+    pub fn is_quiet(&self, hour: u32) -> bool {
+        match self.quiet_hours {
+            Some((from, until)) if from <= until => (from..until).contains(&hour),
+            Some((from, until)) => hour >= from || hour < until,
+            None => false,
+        }
+    }
+
+    /// Returns true if the reminder of the day can be sent now: one of the days of the schedule,
+    /// once its hour is reached, outside of the quiet hours and if it wasn't sent already today
+    pub fn is_time(&self, now: LocalTime, last_reminder_day: i64) -> bool {
+        self.weekdays[now.weekday as usize % 7]
+            && now.hour >= self.hour
+            && !self.is_quiet(now.hour)
+            && now.day != last_reminder_day
+    }
+}
+
+/// Returns the reminder to send now, if it's time and there are flashcards due that haven't been
+/// studied today. The mastered flashcards are left out like in the due count
+pub async fn check_study_reminder(
+    db: Option<OboeteDb>,
+    schedule: ReminderSchedule,
+    last_reminder_day: i64,
+    mastered_streak: Option<usize>,
+) -> Result<Option<StudyReminder>, OboeteError> {
+    let now = get_local_time(db.clone()).await?;
+    if !schedule.is_time(now, last_reminder_day) || has_studied_today(db.clone()).await? {
+        return Ok(None);
+    }
+
+    let due_count = get_due_count(db, mastered_streak).await?;
+    Ok((due_count > 0).then_some(StudyReminder {
+        day: now.day,
+        due_count,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(weekday: u32, hour: u32) -> LocalTime {
+        LocalTime {
+            day: 2_460_000,
+            weekday,
+            hour,
+        }
+    }
+
+    #[test]
+    fn quiet_hours_can_go_past_midnight() {
+        let schedule = ReminderSchedule {
+            quiet_hours: Some((22, 8)),
+            ..Default::default()
+        };
+        assert!(schedule.is_quiet(23));
+        assert!(schedule.is_quiet(0));
+        assert!(schedule.is_quiet(7));
+        assert!(!schedule.is_quiet(8));
+        assert!(!schedule.is_quiet(21));
+
+        let schedule = ReminderSchedule {
+            quiet_hours: Some((12, 14)),
+            ..Default::default()
+        };
+        assert!(schedule.is_quiet(13));
+        assert!(!schedule.is_quiet(14));
+        assert!(!ReminderSchedule::default().is_quiet(3));
+    }
+
+    #[test]
+    fn reminder_is_sent_once_on_the_scheduled_days() {
+        let mut schedule = ReminderSchedule {
+            hour: 18,
+            quiet_hours: Some((20, 8)),
+            ..Default::default()
+        };
+        schedule.weekdays[0] = false;
+
+        assert!(schedule.is_time(at(1, 18), 0));
+        assert!(!schedule.is_time(at(1, 17), 0));
+        //Quiet hours win over the hour of the reminder
+        assert!(!schedule.is_time(at(1, 21), 0));
+        //Sunday isn't selected
+        assert!(!schedule.is_time(at(0, 18), 0));
+        //Already sent today
+        assert!(!schedule.is_time(at(1, 19), 2_460_000));
+    }
+}
@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Flashcard;
+
+/// Ease factor given to flashcards that have never been studied
+pub const DEFAULT_EASE_FACTOR: f64 = 2.5;
+
+/// The ease factor never goes below this, so hard flashcards don't get stuck
+const MIN_EASE_FACTOR: f64 = 1.3;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Default bounds of the interval (in days) of the flashcards, so they're neither reviewed every
+/// day forever nor left unseen for decades once the ease factor grows
+pub const DEFAULT_MIN_INTERVAL_DAYS: i32 = 1;
+pub const DEFAULT_MAX_INTERVAL_DAYS: i32 = 5 * 365;
+
+/// Flashcards graded with a complete blackout (grade 0) are due again after this many seconds
+const AGAIN_DELAY: i64 = 10 * 60;
+
+/// Mastered flashcards are left out of the due ones until they have been due for this long
+pub const MASTERED_REST: i64 = 60 * SECONDS_PER_DAY;
+
+/// Box new flashcards start in, and the one wrong answers send them back to
+pub const LEITNER_FIRST_BOX: i32 = 1;
+
+/// Days until a flashcard of each Leitner box has to be studied again,
+/// the higher the box the less often it's reviewed
+const LEITNER_BOX_INTERVALS: [i32; 5] = [1, 2, 4, 8, 16];
+
+/// Selectable schedulers of a studyset
+pub const SCHEDULER_KINDS: [SchedulerKind; 2] = [SchedulerKind::Sm2, SchedulerKind::Leitner];
+
+/// How the due date of the flashcards of a studyset is calculated after grading them
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SchedulerKind {
+    #[default]
+    Sm2,
+    Leitner,
+}
+
+impl SchedulerKind {
+    /// Updates the schedule of the flashcard with the given grade (from 0 to 5), keeping the
+    /// interval within the limits. A grade of 0 makes it due again in a few minutes instead
+    /// of the next day
+    pub fn update(&self, flashcard: &mut Flashcard, grade: u8, now: i64, limits: IntervalLimits) {
+        match self {
+            SchedulerKind::Sm2 => sm2_update(flashcard, grade, now, limits),
+            SchedulerKind::Leitner => LeitnerScheduler::update(flashcard, grade, now, limits),
+        }
+
+        if grade == 0 {
+            flashcard.due_date = now + AGAIN_DELAY;
+        }
+
+        //Good (4) and Easy (5) grades count towards mastering the flashcard
+        flashcard.good_streak = match grade {
+            4.. => flashcard.good_streak + 1,
+            _ => 0,
+        };
+    }
+}
+
+/// Shortest and longest interval (in days) the flashcards of a studyset are scheduled with
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct IntervalLimits {
+    pub min_interval_days: i32,
+    pub max_interval_days: i32,
+}
+
+impl Default for IntervalLimits {
+    fn default() -> Self {
+        Self {
+            min_interval_days: DEFAULT_MIN_INTERVAL_DAYS,
+            max_interval_days: DEFAULT_MAX_INTERVAL_DAYS,
+        }
+    }
+}
+
+impl IntervalLimits {
+    /// Brings the interval between the limits, a minimum below a day counts as a day and
+    /// a maximum below the minimum as the minimum
+    pub fn clamp(&self, interval: i32) -> i32 {
+        let min = self.min_interval_days.max(1);
+        interval.clamp(min, self.max_interval_days.max(min))
+    }
+}
+
+/// Current time as a unix timestamp (in seconds)
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Returns true if the flashcard has to be studied at the given time
+pub fn is_due(flashcard: &Flashcard, now: i64) -> bool {
+    flashcard.due_date <= now
+}
+
+/// Returns true if the flashcard has been graded Good (or Easy) at least `threshold` times in a
+/// row and it's resting, it isn't studied with the due flashcards until the rest is over.
+/// A threshold of None means flashcards are never mastered
+pub fn is_mastered(flashcard: &Flashcard, threshold: Option<usize>, now: i64) -> bool {
+    threshold.is_some_and(|threshold| flashcard.good_streak as usize >= threshold)
+        && now < flashcard.due_date + MASTERED_REST
+}
+
+/// Updates the schedule of the flashcard following the SM-2 algorithm,
+/// the grade goes from 0 (complete blackout) to 5 (perfect response)
+pub fn sm2_update(flashcard: &mut Flashcard, grade: u8, now: i64, limits: IntervalLimits) {
+    let grade = grade.min(5);
+
+    if grade >= 3 {
+        flashcard.interval = match flashcard.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (flashcard.interval as f64 * flashcard.ease_factor).round() as i32,
+        };
+        flashcard.repetitions += 1;
+    } else {
+        flashcard.repetitions = 0;
+        flashcard.interval = 1;
+    }
+    flashcard.interval = limits.clamp(flashcard.interval);
+
+    let difficulty = (5 - grade) as f64;
+    flashcard.ease_factor = (flashcard.ease_factor + 0.1 - difficulty * (0.08 + difficulty * 0.02))
+        .max(MIN_EASE_FACTOR);
+    flashcard.due_date = now + flashcard.interval as i64 * SECONDS_PER_DAY;
+}
+
+/// Scheduling with the boxes of the Leitner system, a right answer (grade 3 or more) moves
+/// the flashcard up a box, an easy one (grade 5) two boxes and a wrong one sends it back
+/// to the first box
+pub struct LeitnerScheduler;
+
+impl LeitnerScheduler {
+    pub fn update(flashcard: &mut Flashcard, grade: u8, now: i64, limits: IntervalLimits) {
+        let last_box = LEITNER_BOX_INTERVALS.len() as i32;
+        flashcard.leitner_box = match grade {
+            5.. => (flashcard.leitner_box + 2).clamp(LEITNER_FIRST_BOX, last_box),
+            3..=4 => (flashcard.leitner_box + 1).clamp(LEITNER_FIRST_BOX, last_box),
+            _ => LEITNER_FIRST_BOX,
+        };
+
+        flashcard.interval =
+            limits.clamp(LEITNER_BOX_INTERVALS[(flashcard.leitner_box - 1) as usize]);
+        flashcard.due_date = now + flashcard.interval as i64 * SECONDS_PER_DAY;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flashcard(interval: i32, repetitions: i32) -> Flashcard {
+        let mut flashcard = Flashcard::new(String::from("front"), String::from("back"));
+        flashcard.interval = interval;
+        flashcard.repetitions = repetitions;
+        flashcard
+    }
+
+    #[test]
+    fn sm2_interval_stops_at_the_maximum() {
+        let limits = IntervalLimits::default();
+        let mut long = flashcard(DEFAULT_MAX_INTERVAL_DAYS - 10, 8);
+        sm2_update(&mut long, 5, 0, limits);
+        assert_eq!(long.interval, DEFAULT_MAX_INTERVAL_DAYS);
+        assert_eq!(
+            long.due_date,
+            DEFAULT_MAX_INTERVAL_DAYS as i64 * SECONDS_PER_DAY
+        );
+
+        //Even intervals that don't fit an i32 once multiplied
+        let mut huge = flashcard(i32::MAX, 20);
+        sm2_update(&mut huge, 4, 0, limits);
+        assert_eq!(huge.interval, DEFAULT_MAX_INTERVAL_DAYS);
+    }
+
+    #[test]
+    fn sm2_interval_starts_at_the_minimum() {
+        let limits = IntervalLimits::default();
+        let mut forgotten = flashcard(300, 6);
+        sm2_update(&mut forgotten, 1, 0, limits);
+        assert_eq!(forgotten.interval, DEFAULT_MIN_INTERVAL_DAYS);
+        assert_eq!(forgotten.repetitions, 0);
+
+        //A corrupted interval doesn't keep the flashcard due forever
+        let mut corrupted = flashcard(0, 4);
+        corrupted.ease_factor = MIN_EASE_FACTOR;
+        sm2_update(&mut corrupted, 3, 0, limits);
+        assert_eq!(corrupted.interval, DEFAULT_MIN_INTERVAL_DAYS);
+    }
+
+    #[test]
+    fn studyset_limits_clamp_both_ends() {
+        let limits = IntervalLimits {
+            min_interval_days: 3,
+            max_interval_days: 30,
+        };
+
+        let mut new = flashcard(0, 0);
+        SchedulerKind::Sm2.update(&mut new, 4, 0, limits);
+        assert_eq!(new.interval, 3);
+        assert_eq!(new.due_date, 3 * SECONDS_PER_DAY);
+
+        let mut known = flashcard(25, 5);
+        SchedulerKind::Sm2.update(&mut known, 5, 0, limits);
+        assert_eq!(known.interval, 30);
+
+        let mut boxed = flashcard(0, 0);
+        boxed.leitner_box = LEITNER_BOX_INTERVALS.len() as i32;
+        SchedulerKind::Leitner.update(&mut boxed, 5, 0, limits);
+        assert_eq!(boxed.interval, 16);
+        SchedulerKind::Leitner.update(&mut boxed, 1, 0, limits);
+        assert_eq!(boxed.leitner_box, LEITNER_FIRST_BOX);
+        assert_eq!(boxed.interval, 3);
+    }
+
+    #[test]
+    fn limits_out_of_order_still_clamp() {
+        let limits = IntervalLimits {
+            min_interval_days: 0,
+            max_interval_days: -5,
+        };
+        assert_eq!(limits.clamp(40), 1);
+
+        let limits = IntervalLimits {
+            min_interval_days: 10,
+            max_interval_days: 5,
+        };
+        assert_eq!(limits.clamp(1), 10);
+        assert_eq!(limits.clamp(40), 10);
+    }
+
+    #[test]
+    fn sm2_follows_the_first_intervals_and_the_ease_factor() {
+        let limits = IntervalLimits::default();
+        let mut card = flashcard(0, 0);
+
+        sm2_update(&mut card, 4, 0, limits);
+        assert_eq!((card.interval, card.repetitions), (1, 1));
+        assert_eq!(card.ease_factor, DEFAULT_EASE_FACTOR);
+
+        sm2_update(&mut card, 5, 0, limits);
+        assert_eq!((card.interval, card.repetitions), (6, 2));
+        assert!((card.ease_factor - 2.6).abs() < 1e-9);
+
+        sm2_update(&mut card, 3, 100, limits);
+        assert_eq!((card.interval, card.repetitions), (16, 3));
+        assert!((card.ease_factor - 2.46).abs() < 1e-9);
+        assert_eq!(card.due_date, 100 + 16 * SECONDS_PER_DAY);
+    }
+
+    #[test]
+    fn sm2_failure_restarts_and_lowers_the_ease_factor() {
+        let limits = IntervalLimits::default();
+        let mut card = flashcard(30, 4);
+
+        sm2_update(&mut card, 2, 0, limits);
+        assert_eq!((card.interval, card.repetitions), (1, 0));
+        assert!((card.ease_factor - 2.18).abs() < 1e-9);
+
+        //The ease factor never goes below the minimum
+        for _ in 0..10 {
+            sm2_update(&mut card, 0, 0, limits);
+        }
+        assert_eq!(card.ease_factor, MIN_EASE_FACTOR);
+    }
+
+    #[test]
+    fn blackout_is_due_again_in_minutes_and_resets_the_streak() {
+        let mut card = flashcard(10, 3);
+        card.good_streak = 4;
+
+        SchedulerKind::Sm2.update(&mut card, 4, 0, IntervalLimits::default());
+        assert_eq!(card.good_streak, 5);
+        SchedulerKind::Sm2.update(&mut card, 3, 0, IntervalLimits::default());
+        assert_eq!(card.good_streak, 0);
+
+        card.good_streak = 2;
+        SchedulerKind::Leitner.update(&mut card, 0, 1_000, IntervalLimits::default());
+        assert_eq!(card.due_date, 1_000 + AGAIN_DELAY);
+        assert_eq!(card.good_streak, 0);
+    }
+
+    #[test]
+    fn leitner_moves_between_the_boxes() {
+        let limits = IntervalLimits::default();
+        let mut card = flashcard(0, 0);
+        assert_eq!(card.leitner_box, LEITNER_FIRST_BOX);
+
+        //Right answers go up a box, easy ones two
+        LeitnerScheduler::update(&mut card, 3, 0, limits);
+        assert_eq!((card.leitner_box, card.interval), (2, 2));
+        LeitnerScheduler::update(&mut card, 5, 0, limits);
+        assert_eq!((card.leitner_box, card.interval), (4, 8));
+        assert_eq!(card.due_date, 8 * SECONDS_PER_DAY);
+
+        //The last box is never left with right answers
+        LeitnerScheduler::update(&mut card, 5, 0, limits);
+        LeitnerScheduler::update(&mut card, 4, 0, limits);
+        assert_eq!((card.leitner_box, card.interval), (5, 16));
+
+        //Wrong answers go back to the first box
+        LeitnerScheduler::update(&mut card, 2, 0, limits);
+        assert_eq!((card.leitner_box, card.interval), (LEITNER_FIRST_BOX, 1));
+    }
+}
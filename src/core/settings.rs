@@ -5,20 +5,26 @@ use cosmic::{
     iced::{Limits, Size},
 };
 
-use super::icon_cache::{IconCache, ICON_CACHE};
+use super::{
+    config::OboeteConfig,
+    icon_cache::{IconCache, ICON_CACHE},
+};
 
-pub fn init() -> Settings {
+pub fn init(config: &OboeteConfig) -> Settings {
     set_icon_cache();
 
-    let settings = get_app_settings();
+    let settings = get_app_settings(config);
     settings
 }
 
-pub fn get_app_settings() -> Settings {
+pub fn get_app_settings(config: &OboeteConfig) -> Settings {
     let mut settings = Settings::default();
 
     settings = settings.size_limits(Limits::NONE.min_width(500.0).min_height(180.0));
-    settings = settings.size(Size::new(1200.0, 800.0));
+    settings = settings.size(Size::new(
+        config.window_width as f32,
+        config.window_height as f32,
+    ));
     settings = settings.debug(false);
     settings
 }
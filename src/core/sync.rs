@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        database::{get_all_full_studysets, replace_all_studysets, OboeteDb},
+        scheduler,
+    },
+    models::StudySet,
+    utils::OboeteError,
+};
+
+/// Name of the backup inside of the remote folder
+const BACKUP_FILE_NAME: &str = "oboete-sync.json";
+
+/// Backup uploaded to the remote folder, every studyset plus when it was uploaded
+#[derive(Debug, Deserialize, Serialize)]
+struct SyncBackup {
+    /// Unix timestamp of the upload
+    modified_at: i64,
+    studysets: Vec<StudySet>,
+}
+
+/// Where the backup is synced to, and the last backup this device pushed or pulled
+#[derive(Debug, Clone, Default)]
+pub struct SyncConfig {
+    /// WebDAV folder the backup is stored in
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// modified_at of the last backup pushed or pulled, 0 if it never synced
+    pub last_sync: i64,
+}
+
+impl SyncConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.url.trim().is_empty()
+    }
+
+    fn backup_url(&self) -> String {
+        format!(
+            "{}/{}",
+            self.url.trim().trim_end_matches('/'),
+            BACKUP_FILE_NAME
+        )
+    }
+
+    fn authenticate(&self, request: RequestBuilder) -> RequestBuilder {
+        if self.username.is_empty() {
+            request
+        } else {
+            request.basic_auth(&self.username, Some(&self.password))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    Push,
+    Pull,
+}
+
+#[derive(Debug, Clone)]
+pub enum SyncOutcome {
+    /// Both sides have the backup with this modified_at
+    Synced(i64),
+    /// Syncing would overwrite changes the other side doesn't have, it has to be confirmed
+    Conflict,
+}
+
+/// Uploads every studyset to the remote folder. Unless it's forced, it's a conflict if the remote
+/// backup has been modified (by another device) since the last sync
+pub async fn push_backup(
+    db: Option<OboeteDb>,
+    config: SyncConfig,
+    force: bool,
+) -> Result<SyncOutcome, OboeteError> {
+    let client = Client::new();
+
+    if !force {
+        if let Some(remote) = fetch_backup(&client, &config).await? {
+            if remote.modified_at > config.last_sync {
+                return Ok(SyncOutcome::Conflict);
+            }
+        }
+    }
+
+    let backup = SyncBackup {
+        modified_at: scheduler::now(),
+        studysets: get_all_full_studysets(db).await?,
+    };
+    let json = serde_json::to_string(&backup)?;
+
+    config
+        .authenticate(client.put(config.backup_url()))
+        .header("Content-Type", "application/json")
+        .body(json)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(network_error)?;
+
+    Ok(SyncOutcome::Synced(backup.modified_at))
+}
+
+/// Replaces every studyset with the ones of the remote backup. Unless it's forced, it's a conflict
+/// if the remote backup isn't newer than the last sync, as the local changes would be lost
+pub async fn pull_backup(
+    db: Option<OboeteDb>,
+    config: SyncConfig,
+    force: bool,
+) -> Result<SyncOutcome, OboeteError> {
+    let client = Client::new();
+
+    let Some(remote) = fetch_backup(&client, &config).await? else {
+        return Err(OboeteError::NotFound(String::from(
+            "There's no backup in the remote folder",
+        )));
+    };
+
+    if !force && remote.modified_at <= config.last_sync {
+        return Ok(SyncOutcome::Conflict);
+    }
+
+    replace_all_studysets(db, remote.studysets).await?;
+
+    Ok(SyncOutcome::Synced(remote.modified_at))
+}
+
+/// Downloads the remote backup, None if there isn't one yet
+async fn fetch_backup(
+    client: &Client,
+    config: &SyncConfig,
+) -> Result<Option<SyncBackup>, OboeteError> {
+    let response = config
+        .authenticate(client.get(config.backup_url()))
+        .send()
+        .await
+        .map_err(network_error)?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let json = response
+        .error_for_status()
+        .map_err(network_error)?
+        .text()
+        .await
+        .map_err(network_error)?;
+
+    Ok(Some(serde_json::from_str(&json)?))
+}
+
+fn network_error(err: reqwest::Error) -> OboeteError {
+    OboeteError::Network(err.to_string())
+}
@@ -1,26 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
 use cosmic::{
     iced::{
         alignment::{Horizontal, Vertical},
+        widget::text_editor,
         Alignment, Color, Length,
     },
     theme,
     widget::{self},
     Apply, Element,
 };
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::icon_cache::IconCache,
+    core::{
+        api::transfer::PdfLayout,
+        audio::AudioPlayback,
+        config::StudyLabels,
+        icon_cache::IconCache,
+        markdown::{markdown_to_plain_text, view_markdown},
+        math::render_inline_math,
+        scheduler::{self, IntervalLimits, SchedulerKind},
+    },
     fl,
-    models::Flashcard,
-    utils::{export_flashcards, parse_ankifile, parse_import_content, select_random_flashcard},
+    models::{
+        CardType, Flashcard, FlashcardLocation, FlashcardSides, FolderStats,
+        FolderStudyPreferences, ImportSummary, Review, StudyDirection, StudyFilter, StudyMode,
+        StudyOrder, StudyScope,
+    },
+    utils::{
+        answer_matches, break_long_words, cloze_answers, cloze_text, decode_file_path,
+        export_flashcards, export_test_sheet, fitting_text_size, front_key, matches_tags,
+        parse_ankifile, parse_tsv_file, replace_flashcard_text, select_weighted_flashcard,
+        study_rng, OboeteError,
+    },
 };
+
+/// Longer response times are counted as this, so a flashcard left on screen doesn't skew its average
+const MAX_RESPONSE_TIME: Duration = Duration::from_secs(300);
+
+/// Max length of a word before it's forced to wrap
+const MAX_WORD_LENGTH: usize = 20;
+
+/// Grades of a study session that can be undone
+const UNDO_LIMIT: usize = 10;
+
+/// Flashcards shown at once in the list, big folders are split in pages
+const FLASHCARDS_PAGE_SIZE: usize = 100;
+
 pub struct Flashcards {
     pub current_folder_id: i32,
     pub flashcards: Vec<Flashcard>,
     pub new_edit_flashcard: CreateEditFlashcardState,
-    pub currently_studying_flashcard: Flashcard,
+    /// Flashcard being studied, None when there's nothing left to study
+    pub currently_studying_flashcard: Option<Flashcard>,
     pub currently_studying_flashcard_side: CurrentFlashcardSide,
     pub options_page_input: OptionsContextPageInputState,
+    pub study_labels: StudyLabels,
+    pub current_folder_source: Option<String>,
+    pub sync_delete_missing: bool,
+    /// Flashcard studied right before the current one, so it isn't repeated immediately
+    pub last_studied_id: Option<i32>,
+    pub study_direction: StudyDirection,
+    pub study_filter: StudyFilter,
+    pub study_order: StudyOrder,
+    pub study_mode: StudyMode,
+    /// Random choices of the study session, reseeded when a session starts
+    rng: StdRng,
+    /// Answers of the multiple choice mode (backs of the folder flashcards)
+    pub choices: Vec<String>,
+    /// Answer picked in the multiple choice mode, shown as right or wrong until the next flashcard
+    pub selected_choice: Option<usize>,
+    /// Answer written in the typed mode
+    pub typed_answer: String,
+    /// Whether the typed answer was right, None until it's submitted
+    pub typed_answer_result: Option<bool>,
+    /// Typos accepted in the typed mode
+    pub typo_tolerance: usize,
+    /// Maximum flashcards graded in a study session, None is unlimited
+    pub session_length: Option<usize>,
+    /// Scheduler of the studyset, decides when the graded flashcards are due again
+    pub scheduler: SchedulerKind,
+    /// Interval limits of the studyset the scheduler keeps the graded flashcards within
+    pub interval_limits: IntervalLimits,
+    /// Whether the study session goes through the due flashcards of every folder
+    /// instead of the ones of the current folder
+    pub studying_all_due: bool,
+    /// Scheduler and interval limits of the studyset of each flashcard (by id) in the study
+    /// all due session
+    card_schedulers: HashMap<i32, (SchedulerKind, IntervalLimits)>,
+    /// Studyset and folder of each flashcard (by id) in the study all due session
+    folder_labels: HashMap<i32, String>,
+    /// Flashcards graded in each folder during the study all due session
+    session_folders: BTreeMap<String, usize>,
+    /// Flashcard waiting for the delete confirmation
+    pub pending_delete: Option<i32>,
+    /// New flashcard waiting for the duplicate check (and its confirmation) before being saved
+    pub pending_duplicate: Option<Flashcard>,
+    /// Flashcards graded in the current study session
+    pub studied_count: usize,
+    /// Last flashcards graded in the session (as they were before), their grade and the response
+    /// time recorded with it, to undo them
+    pub grade_history: Vec<(Flashcard, StudyActions, Option<Duration>)>,
+    /// Flashcards that were due when the study session started
+    pub session_size: usize,
+    /// Whether the finished session has been recorded in the study log, so undoing and
+    /// grading the last flashcard again doesn't record it twice
+    session_logged: bool,
+    /// Whether the study page goes through the flashcards without grading them
+    pub browsing: bool,
+    /// Position of the flashcard shown in the browse mode
+    browse_index: usize,
+    /// Whether the create context page was opened from the study page
+    creating_from_study: bool,
+    /// Whether the edit context page was opened from the study page, the edited flashcard is
+    /// refreshed in the session instead of reloading the folder
+    editing_from_study: bool,
+    /// Whether the next loaded flashcards include one created from the study page,
+    /// so the ones the session doesn't know yet are added to it
+    joining_session: bool,
+    pub session_results: SessionResults,
+    /// Rows pasted in the paste import context page
+    paste_import_content: text_editor::Content,
+    /// Flashcards added and rows skipped by the last paste import
+    paste_import_result: Option<ImportSummary>,
+    /// Front and back typed in the quick add inputs of the flashcard list
+    quick_add_front: String,
+    quick_add_back: String,
+    /// Why the last quick add couldn't be saved
+    quick_add_error: Option<String>,
+    /// Flashcard shown in the history context page and its past grades
+    history_flashcard: Option<Flashcard>,
+    flashcard_history: Vec<Review>,
+    pub quick_add_front_id: widget::Id,
+    pub quick_add_back_id: widget::Id,
+    /// When the studied flashcard was shown
+    shown_at: Option<Instant>,
+    /// Time it took to pick or type the answer of the studied flashcard, in the modes where it's
+    /// answered before being graded
+    response_time: Option<Duration>,
+    /// Response time of every flashcard graded in the session
+    session_response_times: Vec<Duration>,
+    /// Good grades in a row that make a flashcard mastered, None never masters them
+    pub mastered_streak: Option<usize>,
+    /// Whether the mastered flashcards are studied with the due ones anyway
+    pub include_mastered: bool,
+    /// Session resumed once the flashcards of its folder are loaded
+    pub resuming_session: Option<PausedSession>,
+    pub folder_stats: FolderStats,
+    /// Tags the flashcard list is filtered by, without any every flashcard is listed
+    pub tag_filter: BTreeSet<String>,
+    /// Whether the listed flashcards need every selected tag instead of any of them
+    pub match_all_tags: bool,
+    pub search_query: String,
+    /// Flashcards matching the search query, None while not searching
+    pub search_results: Option<Vec<Flashcard>>,
+    /// Increased on every search input, so only the last one (once the typing stops) runs
+    pub search_generation: u64,
+    /// Whether the flashcards are rendered as Markdown
+    pub render_markdown: bool,
+    /// Maximum characters of each side of a flashcard, None is unlimited
+    pub length_limit: Option<usize>,
+    /// Audio of the current flashcard being played, it's stopped when dropped
+    pub audio_playback: Option<AudioPlayback>,
+    /// Page of the flashcard list being shown, starting at 0
+    pub list_page: usize,
+    /// Whether the flashcard list shows the selection buttons instead of the row actions
+    pub selection_mode: bool,
+    /// Ids of the flashcards selected in the selection mode
+    pub selected_flashcards: HashSet<i32>,
+    /// Order of the flashcard list, kept while the app is open
+    pub list_sort: FlashcardSort,
+    sort_labels: Vec<String>,
+    replace_side_labels: Vec<String>,
+    pdf_layout_labels: Vec<String>,
+    study_mode_labels: Vec<String>,
+    study_direction_labels: Vec<String>,
+    study_filter_labels: Vec<String>,
+    study_order_labels: Vec<String>,
+}
+
+/// How many flashcards got each grade during a study session
+#[derive(Debug, Default, Clone, Copy, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SessionResults {
+    pub bad: usize,
+    pub ok: usize,
+    pub good: usize,
+}
+
+impl SessionResults {
+    /// Counter of the status the action gives to the flashcard
+    fn count_mut(&mut self, action: &StudyActions) -> &mut usize {
+        match action.status() {
+            1 => &mut self.bad,
+            2 => &mut self.ok,
+            _ => &mut self.good,
+        }
+    }
+}
+
+/// Progress of a folder study session the app was closed in the middle of, so it can go on
+/// from there on the next launch
+#[derive(Debug, Default, Clone, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PausedSession {
+    pub folder_id: i32,
+    /// Flashcard that was being studied, it's shown first when the session is resumed
+    pub flashcard_id: Option<i32>,
+    pub last_studied_id: Option<i32>,
+    pub studied_count: usize,
+    pub session_size: usize,
+    pub results: SessionResults,
+    /// Response time of every flashcard graded in the session, in milliseconds
+    pub response_times: Vec<u64>,
 }
 
 pub struct CreateEditFlashcardState {
@@ -28,6 +223,16 @@ pub struct CreateEditFlashcardState {
     front: String,
     back: String,
     status: i32,
+    /// Comma separated tags
+    tags: String,
+    image_path: Option<String>,
+    audio_path: Option<String>,
+    card_type: CardType,
+    /// Front and back the flashcard had when it was loaded (empty for new flashcards)
+    loaded_front: String,
+    loaded_back: String,
+    /// Why the flashcard couldn't be saved, cleared once it's changed
+    error: Option<String>,
 }
 
 impl CreateEditFlashcardState {
@@ -37,7 +242,47 @@ impl CreateEditFlashcardState {
             front: String::new(),
             back: String::new(),
             status: 0,
+            tags: String::new(),
+            image_path: None,
+            audio_path: None,
+            card_type: CardType::Basic,
+            loaded_front: String::new(),
+            loaded_back: String::new(),
+            error: None,
+        }
+    }
+
+    /// Whether the front or back have been changed since the flashcard was loaded
+    pub fn is_dirty(&self) -> bool {
+        self.front != self.loaded_front || self.back != self.loaded_back
+    }
+
+    /// Returns the trimmed front and back, fails if the front is empty (or only whitespace),
+    /// a cloze flashcard has no answers or a side is longer than the limit
+    pub fn validate(&self, length_limit: Option<usize>) -> Result<(String, String), OboeteError> {
+        let front = self.front.trim();
+        let back = self.back.trim();
+
+        if front.is_empty() {
+            return Err(OboeteError::Validation(fl!("flashcard-front-empty")));
+        }
+
+        if self.card_type == CardType::Cloze
+            && cloze_answers(front).iter().all(|answer| answer.is_empty())
+        {
+            return Err(OboeteError::Validation(fl!("flashcard-cloze-empty")));
+        }
+
+        if let Some(limit) = length_limit {
+            if front.chars().count() > limit || back.chars().count() > limit {
+                return Err(OboeteError::Validation(fl!(
+                    "flashcard-too-long",
+                    limit = limit
+                )));
+            }
         }
+
+        Ok((front.to_string(), back.to_string()))
     }
 }
 
@@ -45,14 +290,28 @@ pub struct OptionsContextPageInputState {
     pub between_terms: String,
     pub between_cards: String,
     pub import_content: String,
+    pub test_sheet_shuffled: bool,
+    pub test_sheet_answer_key: bool,
+    pub find_text: String,
+    pub replace_text: String,
+    pub replace_sides: FlashcardSides,
+    pub replace_case_sensitive: bool,
+    pub pdf_layout: PdfLayout,
 }
 
 impl OptionsContextPageInputState {
     pub fn new() -> OptionsContextPageInputState {
         OptionsContextPageInputState {
-            between_terms: String::new(),
-            between_cards: String::new(),
+            between_terms: String::from("\\t"),
+            between_cards: String::from("\\n"),
             import_content: String::new(),
+            test_sheet_shuffled: false,
+            test_sheet_answer_key: true,
+            find_text: String::new(),
+            replace_text: String::new(),
+            replace_sides: FlashcardSides::default(),
+            replace_case_sensitive: false,
+            pdf_layout: PdfLayout::default(),
         }
     }
 }
@@ -61,53 +320,239 @@ impl OptionsContextPageInputState {
 pub enum Message {
     LaunchUrl(String),
     ToggleCreatePage(Option<Flashcard>),
+    //Creates a flashcard from the study page, it joins the current session once saved
+    CreateWhileStudying,
+    //Edits the flashcard being studied, the session goes on once saved
+    EditWhileStudying,
+    //The studied flashcard as it was saved from the study page
+    StudiedFlashcardEdited(Flashcard),
     ToggleOptionsPage,
+    TogglePasteImportPage,
+    PasteImportAction(text_editor::Action),
+    PasteImport,
+    //The usizes are the flashcards added and the rows skipped
+    PasteImported(ImportSummary),
 
     Upsert,
+    QuickAddFrontInput(String),
+    QuickAddBackInput(String),
+    //Enter in the front input moves to the back one
+    QuickAddNext,
+    QuickAdd,
+    //The bool is whether the folder already has a flashcard with the same front
+    DuplicateChecked(bool),
+    //Saves the new flashcard waiting for the duplicate check
+    AddDuplicate,
     Upserted,
     LoadFlashcards,
     Import,
+    FindReplace,
+    Replaced,
+    RequestDelete(Option<i32>),
+    CancelDelete,
+    RequestMove(Option<i32>),
     Delete(Option<i32>),
+    ShowHistory(Flashcard),
+    LeaveStudyPage,
+    HistoryLoaded(Vec<Review>),
 
     SetFlashcards(Vec<Flashcard>),
+    SetFolderStats(FolderStats),
     SwapFlashcardSide,
     StudyFlashcards,
+    StudyAgain,
+    ToggleStudyDirection,
+    ToggleStudyFilter,
+    ToggleStudyOrder,
+    ToggleStudyMode,
+    //How the folder is studied, loaded when it's opened
+    SetStudyPreferences(FolderStudyPreferences),
+    ToggleBrowse,
+    BrowsePrevious,
+    BrowseNext,
+    SelectChoice(usize),
+    GradeChoice,
+    TypedAnswerInput(String),
+    SubmitTypedAnswer,
+    GradeTypedAnswer,
     UpdateFlashcardStatus(Flashcard, StudyActions),
     RestartSingleFlashcardStatus(Option<i32>),
     RestartFolderFlashcardStatus,
     OpenAnkiFileSelection,
+    OpenCsvFileSelection,
     OpenFolderExportDestination,
+    OpenTestSheetExportDestination,
+    OpenPdfExportDestination,
+    OpenSyncSourceSelection,
+    SyncFromSource,
+    OpenImageFileSelection,
+    OpenAudioFileSelection,
+    PlayAudio,
 
     UpdatedStatus(Vec<Flashcard>),
+    UndoLastGrade,
+    //The i32 is the id of the flashcard whose grade was undone
+    RestoredStatus(i32, Vec<Flashcard>),
     LoadedSingle(Flashcard),
     ContextPageBackInput(String),
+    ContextPageTagsInput(String),
+    ContextPageCloze(bool),
+    ToggleTagFilter(String),
+    SearchInput(String),
+    //The u64 is the search generation it was scheduled for
+    RunSearch(u64),
+    SetSearchResults(Vec<Flashcard>),
     ContextPageFrontInput(String),
     OpenAnkiFileResult(Vec<String>),
+    OpenCsvFileResult(Vec<String>),
+    ImportFailed(OboeteError),
     OptionsPageInput(OptionsContextPageInputActions),
     OpenFolderExportDestinationResult(Vec<String>),
+    OpenTestSheetExportDestinationResult(Vec<String>),
+    OpenPdfExportDestinationResult(Vec<String>),
+    OpenSyncSourceResult(Vec<String>),
+    SetFolderSource(Option<String>),
+    SyncDeleteMissingToggled(bool),
+    OpenImageFileResult(Vec<String>),
+    //None removes the image
+    SetImage(Option<String>),
+    OpenAudioFileResult(Vec<String>),
+    //None removes the audio
+    SetAudio(Option<String>),
+    SetListPage(usize),
+    //The usize is the index of the sort in the dropdown
+    SetListSort(usize),
+    ToggleSelectionMode,
+    //The i32 is the Flashcard Id
+    ToggleSelected(i32),
+    //The i32 is the Flashcard Id
+    ToggleFavorite(i32),
+    SelectAll,
+    ClearSelection,
+    DeleteSelected,
+    DeletedSelected,
 }
 
 pub enum Command {
     //The i32 is the Folder Id
     LoadFlashcards(i32),
+    //The i32 is the Folder Id
+    LoadFolderStats(i32),
+    LoadDueCount,
+    LogStudySession,
+    //Waits until the user stops typing, the u64 is the search generation
+    DebounceSearch(u64),
+    //The i32 is the Folder Id
+    SearchFlashcards(i32, String),
     ToggleCreateFlashcardPage(Option<Flashcard>),
     UpsertFlashcard(Flashcard),
+    //The i32 is the Flashcard Id
+    LoadStudiedFlashcard(i32),
+    FocusQuickAddFront,
+    FocusQuickAddBack,
     OpenStudyFolderFlashcardsPage,
-    UpdateFlashcardStatus(Flashcard),
+    ResumeStudySession(PausedSession),
+    //The i32 is the Folder Id
+    OpenFolder(i32),
+    OpenFolders,
+    //The i32 is the Folder Id
+    SaveStudyPreferences(i32, FolderStudyPreferences),
+    //Waits a moment so the picked answer can be seen before grading it
+    ShowChoiceFeedback,
+    //The u8 is the grade, it's recorded in the review history
+    UpdateFlashcardStatus(Flashcard, u8),
+    //Saves the flashcard as it was before its last grade
+    RestoreFlashcardStatus(Flashcard),
+    //The usize is the amount of selected flashcards
+    ConfirmDeleteSelected(usize),
+    //The String is the front of the flashcard
+    ConfirmDelete(String),
+    //The i32 is the Folder Id, the String is the front of the new flashcard
+    CheckDuplicate(i32, String),
+    //The String is the front of the new flashcard
+    ConfirmDuplicate(String),
+    //The i32 is the Flashcard Id, the destination folder is picked in a dialog
+    PickMoveDestination(i32),
     DeleteFlashcard(Option<i32>),
+    //The i32 is the Flashcard Id
+    LoadFlashcardHistory(i32),
+    //The i32 is the Flashcard Id, the bool is whether it's starred
+    SetFavorite(i32, bool),
+    OpenHistoryPage,
     ToggleOptionsPage,
+    TogglePasteImportPage,
     ImportFlashcards(Vec<Flashcard>),
+    //The i32 is the Folder Id, the String are the pasted rows
+    ImportTsv(i32, String),
+    //The Strings are the content, the separator between terms and the one between cards
+    ImportQuizlet(String, String, String),
+    //The i32 is the Folder Id, then the text to find, its replacement, the sides and the case sensitivity
+    FindReplace(i32, String, String, FlashcardSides, bool),
     RestartSingleFlashcardStatus(Option<i32>),
     RestartFolderFlashcardStatus(i32),
     OpenAnkiFileSelection,
+    OpenCsvFileSelection,
+    ImportCsvFlashcards(String),
+    ShowError(OboeteError),
     OpenFolderExportDestination,
+    OpenTestSheetExportDestination,
+    OpenPdfExportDestination,
+    //The i32 is the Folder Id, the String is the path of the PDF
+    ExportFolderPdf(i32, String, PdfLayout),
+    OpenSyncSourceSelection,
+    //The i32 is the Folder Id
+    SetFolderSource(i32, String),
+    OpenImageFileSelection,
+    //The String is the path of the picked image, it's copied into the app data directory
+    StoreFlashcardImage(String),
+    OpenAudioFileSelection,
+    //The String is the path of the picked audio, it's copied into the app data directory
+    StoreFlashcardAudio(String),
+    //Missing is the amount of flashcards that the sync will delete
+    SyncFromSource {
+        folder_id: i32,
+        delete_missing: bool,
+        missing: usize,
+    },
 }
 
+/// Grades of a studied flashcard, from forgotten (Again) to effortless (Easy).
+///
+/// Again keeps the Bad status (1) instead of going back to 0: status 0 means "never studied" to
+/// the New badge, the Unseen study filter, the selection weights and the folder statistics, so a
+/// forgotten flashcard would be counted as a new one. Its scheduling is what sets it apart from
+/// Bad, it's due again in a few minutes
 #[derive(Debug, Clone)]
 pub enum StudyActions {
+    /// Not remembered at all, it has to be studied again in a few minutes
+    Again,
     Bad,
     Ok,
     Good,
+    /// Remembered without any effort, it's scheduled further away than Good
+    Easy,
+}
+
+impl StudyActions {
+    /// SM-2 grade (0-5) of each study action
+    fn grade(&self) -> u8 {
+        match self {
+            StudyActions::Again => 0,
+            StudyActions::Bad => 1,
+            StudyActions::Ok => 3,
+            StudyActions::Good => 4,
+            StudyActions::Easy => 5,
+        }
+    }
+
+    /// Status the flashcard gets, Again counts as Bad (see [`StudyActions`]) and Easy as Good
+    fn status(&self) -> i32 {
+        match self {
+            StudyActions::Again | StudyActions::Bad => 1,
+            StudyActions::Ok => 2,
+            StudyActions::Good | StudyActions::Easy => 3,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -116,27 +561,261 @@ pub enum CurrentFlashcardSide {
     Back,
 }
 
+/// Study modes in the order they're listed in the study preferences dropdown
+const STUDY_MODES: [StudyMode; 3] = [
+    StudyMode::Flashcards,
+    StudyMode::MultipleChoice,
+    StudyMode::Typed,
+];
+
+/// Maximum amount of answers shown in the multiple choice mode
+const MAX_CHOICES: usize = 4;
+
+/// Directions in the order they're listed in the study preferences dropdown
+const STUDY_DIRECTIONS: [StudyDirection; 3] = [
+    StudyDirection::FrontFirst,
+    StudyDirection::BackFirst,
+    StudyDirection::Random,
+];
+
+/// Filters in the order they're listed in the study preferences dropdown
+const STUDY_FILTERS: [StudyFilter; 5] = [
+    StudyFilter::AllDue,
+    StudyFilter::OnlyBad,
+    StudyFilter::Unseen,
+    StudyFilter::Mixed,
+    StudyFilter::Favorites,
+];
+
+/// Orders in the order they're listed in the study preferences dropdown
+const STUDY_ORDERS: [StudyOrder; 3] = [
+    StudyOrder::Random,
+    StudyOrder::Sequential,
+    StudyOrder::OldestFirst,
+];
+
+/// Localized name of a study option, shown on its button and in its dropdown
+trait StudyOptionTitle {
+    fn title(&self) -> String;
+}
+
+impl StudyOptionTitle for StudyMode {
+    fn title(&self) -> String {
+        match self {
+            StudyMode::Flashcards => fl!("study-mode-flashcards"),
+            StudyMode::MultipleChoice => fl!("study-mode-multiple-choice"),
+            StudyMode::Typed => fl!("study-mode-typed"),
+        }
+    }
+}
+
+impl StudyOptionTitle for StudyDirection {
+    fn title(&self) -> String {
+        match self {
+            StudyDirection::FrontFirst => fl!("study-front-first"),
+            StudyDirection::BackFirst => fl!("study-back-first"),
+            StudyDirection::Random => fl!("study-random-side"),
+        }
+    }
+}
+
+impl StudyOptionTitle for StudyFilter {
+    fn title(&self) -> String {
+        match self {
+            StudyFilter::AllDue => fl!("study-filter-all-due"),
+            StudyFilter::OnlyBad => fl!("study-filter-only-bad"),
+            StudyFilter::Unseen => fl!("study-filter-unseen"),
+            StudyFilter::Mixed => fl!("study-filter-mixed"),
+            StudyFilter::Favorites => fl!("study-filter-favorites"),
+        }
+    }
+}
+
+impl StudyOptionTitle for StudyOrder {
+    fn title(&self) -> String {
+        match self {
+            StudyOrder::Random => fl!("study-order-random"),
+            StudyOrder::Sequential => fl!("study-order-sequential"),
+            StudyOrder::OldestFirst => fl!("study-order-oldest-first"),
+        }
+    }
+}
+
+/// Order of the flashcard list
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FlashcardSort {
+    /// Creation order, the oldest first
+    #[default]
+    Created,
+    Front,
+    Back,
+    /// New, bad, ok and good
+    Status,
+    RecentlyAdded,
+}
+
+/// Sorts in the order they're listed in the dropdown
+const FLASHCARD_SORTS: [FlashcardSort; 5] = [
+    FlashcardSort::Created,
+    FlashcardSort::Front,
+    FlashcardSort::Back,
+    FlashcardSort::Status,
+    FlashcardSort::RecentlyAdded,
+];
+
+impl FlashcardSort {
+    fn title(&self) -> String {
+        match self {
+            FlashcardSort::Created => fl!("sort-created"),
+            FlashcardSort::Front => fl!("sort-front"),
+            FlashcardSort::Back => fl!("sort-back"),
+            FlashcardSort::Status => fl!("sort-status"),
+            FlashcardSort::RecentlyAdded => fl!("sort-recently-added"),
+        }
+    }
+
+    /// Sorts the flashcards, the sort is stable so equal ones keep the creation order.
+    /// Flashcards created before the creation date was saved fall back to their id
+    fn sort(&self, flashcards: &mut [&Flashcard]) {
+        match self {
+            FlashcardSort::Created => flashcards.sort_by_key(|flashcard| flashcard.id),
+            FlashcardSort::Front => {
+                flashcards.sort_by_cached_key(|flashcard| flashcard.front.to_lowercase())
+            }
+            FlashcardSort::Back => {
+                flashcards.sort_by_cached_key(|flashcard| flashcard.back.to_lowercase())
+            }
+            FlashcardSort::Status => flashcards.sort_by_key(|flashcard| flashcard.status),
+            FlashcardSort::RecentlyAdded => flashcards
+                .sort_by_key(|flashcard| std::cmp::Reverse((flashcard.created_at, flashcard.id))),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum OptionsContextPageInputActions {
     BetweenTerms(String),
     BetweenCards(String),
     ImportContent(String),
+    TestSheetShuffled(bool),
+    TestSheetAnswerKey(bool),
+    FindText(String),
+    ReplaceText(String),
+    //The usize is the index of the sides in the dropdown
+    ReplaceSides(usize),
+    ReplaceCaseSensitive(bool),
+    //The usize is the index of the layout in the dropdown
+    PdfLayout(usize),
+    //The usizes are the index of the study preference in its dropdown
+    StudyMode(usize),
+    StudyDirection(usize),
+    StudyFilter(usize),
+    StudyOrder(usize),
 }
 
+/// Sides in the order they're listed in the find and replace dropdown
+const REPLACE_SIDES: [FlashcardSides; 3] = [
+    FlashcardSides::Both,
+    FlashcardSides::Front,
+    FlashcardSides::Back,
+];
+
+/// Layouts in the order they're listed in the PDF export dropdown
+const PDF_LAYOUTS: [PdfLayout; 2] = [PdfLayout::TwoColumns, PdfLayout::CutOutCards];
+
 impl Flashcards {
     pub fn new() -> Self {
         Self {
             current_folder_id: 0,
             flashcards: Vec::new(),
-            currently_studying_flashcard: Flashcard {
-                id: None,
-                front: String::from("Error"),
-                back: String::from("Error"),
-                status: 0,
-            },
+            currently_studying_flashcard: None,
             new_edit_flashcard: CreateEditFlashcardState::new(),
             currently_studying_flashcard_side: CurrentFlashcardSide::Front,
             options_page_input: OptionsContextPageInputState::new(),
+            study_labels: StudyLabels::default(),
+            current_folder_source: None,
+            sync_delete_missing: false,
+            last_studied_id: None,
+            study_direction: StudyDirection::default(),
+            study_filter: StudyFilter::default(),
+            study_order: StudyOrder::default(),
+            rng: study_rng(),
+            study_mode: StudyMode::default(),
+            choices: Vec::new(),
+            selected_choice: None,
+            typed_answer: String::new(),
+            typed_answer_result: None,
+            typo_tolerance: 1,
+            session_length: None,
+            scheduler: SchedulerKind::default(),
+            interval_limits: IntervalLimits::default(),
+            studying_all_due: false,
+            card_schedulers: HashMap::new(),
+            folder_labels: HashMap::new(),
+            session_folders: BTreeMap::new(),
+            pending_delete: None,
+            pending_duplicate: None,
+            studied_count: 0,
+            grade_history: Vec::new(),
+            session_size: 0,
+            session_logged: false,
+            browsing: false,
+            browse_index: 0,
+            creating_from_study: false,
+            editing_from_study: false,
+            joining_session: false,
+            session_results: SessionResults::default(),
+            paste_import_content: text_editor::Content::new(),
+            paste_import_result: None,
+            quick_add_front: String::new(),
+            quick_add_back: String::new(),
+            quick_add_error: None,
+            history_flashcard: None,
+            flashcard_history: Vec::new(),
+            quick_add_front_id: widget::Id::unique(),
+            quick_add_back_id: widget::Id::unique(),
+            shown_at: None,
+            response_time: None,
+            session_response_times: Vec::new(),
+            mastered_streak: Some(5),
+            include_mastered: false,
+            resuming_session: None,
+            folder_stats: FolderStats::default(),
+            tag_filter: BTreeSet::new(),
+            match_all_tags: false,
+            search_query: String::new(),
+            search_results: None,
+            search_generation: 0,
+            render_markdown: false,
+            length_limit: None,
+            audio_playback: None,
+            list_page: 0,
+            selection_mode: false,
+            selected_flashcards: HashSet::new(),
+            list_sort: FlashcardSort::default(),
+            sort_labels: FLASHCARD_SORTS.iter().map(|sort| sort.title()).collect(),
+            replace_side_labels: REPLACE_SIDES
+                .iter()
+                .map(|sides| match sides {
+                    FlashcardSides::Both => fl!("replace-both-sides"),
+                    FlashcardSides::Front => fl!("replace-front-side"),
+                    FlashcardSides::Back => fl!("replace-back-side"),
+                })
+                .collect(),
+            pdf_layout_labels: PDF_LAYOUTS
+                .iter()
+                .map(|layout| match layout {
+                    PdfLayout::TwoColumns => fl!("pdf-layout-two-columns"),
+                    PdfLayout::CutOutCards => fl!("pdf-layout-cut-out-cards"),
+                })
+                .collect(),
+            study_mode_labels: STUDY_MODES.iter().map(|mode| mode.title()).collect(),
+            study_direction_labels: STUDY_DIRECTIONS
+                .iter()
+                .map(|direction| direction.title())
+                .collect(),
+            study_filter_labels: STUDY_FILTERS.iter().map(|filter| filter.title()).collect(),
+            study_order_labels: STUDY_ORDERS.iter().map(|order| order.title()).collect(),
         }
     }
 
@@ -144,51 +823,495 @@ impl Flashcards {
         let mut commands = Vec::new();
 
         match message {
-            Message::Upsert => commands.push(Command::UpsertFlashcard(Flashcard {
-                id: self.new_edit_flashcard.id,
-                front: self.new_edit_flashcard.front.to_string(),
-                back: self.new_edit_flashcard.back.to_string(),
-                status: self.new_edit_flashcard.status,
-            })),
+            Message::Upsert => match self.new_edit_flashcard.validate(self.length_limit) {
+                Ok((front, back)) => {
+                    let flashcard = Flashcard {
+                        id: self.new_edit_flashcard.id,
+                        status: self.new_edit_flashcard.status,
+                        tags: self
+                            .new_edit_flashcard
+                            .tags
+                            .split(',')
+                            .map(|tag| tag.trim().to_string())
+                            .filter(|tag| !tag.is_empty())
+                            .collect(),
+                        image_path: self.new_edit_flashcard.image_path.clone(),
+                        audio_path: self.new_edit_flashcard.audio_path.clone(),
+                        card_type: self.new_edit_flashcard.card_type,
+                        ..Flashcard::new(front, back)
+                    };
+
+                    //Only new flashcards are checked, so an edit is never a duplicate of itself
+                    if flashcard.id.is_none() {
+                        commands.push(Command::CheckDuplicate(
+                            self.current_folder_id,
+                            flashcard.front.clone(),
+                        ));
+                        self.pending_duplicate = Some(flashcard);
+                    } else {
+                        commands.push(Command::UpsertFlashcard(flashcard));
+                    }
+                }
+                Err(err) => self.new_edit_flashcard.error = Some(err.to_string()),
+            },
+            Message::QuickAddFrontInput(value) => {
+                self.quick_add_front = value;
+                self.quick_add_error = None;
+            }
+            Message::QuickAddBackInput(value) => {
+                self.quick_add_back = value;
+                self.quick_add_error = None;
+            }
+            Message::QuickAddNext => commands.push(Command::FocusQuickAddBack),
+            Message::QuickAdd => {
+                //The quick add is saved like the create page (validation and duplicate check
+                //included), the draft of the create page is kept aside meanwhile
+                let draft = std::mem::replace(
+                    &mut self.new_edit_flashcard,
+                    CreateEditFlashcardState {
+                        front: self.quick_add_front.clone(),
+                        back: self.quick_add_back.clone(),
+                        ..CreateEditFlashcardState::new()
+                    },
+                );
+                commands = self.update(Message::Upsert);
+                self.quick_add_error = self.new_edit_flashcard.error.take();
+                self.new_edit_flashcard = draft;
+
+                if self.quick_add_error.is_none() {
+                    self.quick_add_front.clear();
+                    self.quick_add_back.clear();
+                }
+                commands.push(Command::FocusQuickAddFront);
+            }
+            Message::DuplicateChecked(exists) => {
+                if let Some(flashcard) = &self.pending_duplicate {
+                    if exists {
+                        commands.push(Command::ConfirmDuplicate(flashcard.front.clone()));
+                    } else {
+                        return self.update(Message::AddDuplicate);
+                    }
+                }
+            }
+            Message::AddDuplicate => {
+                if let Some(flashcard) = self.pending_duplicate.take() {
+                    commands.push(Command::UpsertFlashcard(flashcard));
+                }
+            }
             Message::Upserted => {
+                //Only the edited flashcard changes, reloading the folder would replace the
+                //flashcards of a study all due session
+                if self.editing_from_study {
+                    if let Some(flashcard_id) = self.new_edit_flashcard.id {
+                        commands.push(Command::LoadStudiedFlashcard(flashcard_id));
+                    }
+                }
+
                 self.new_edit_flashcard = CreateEditFlashcardState::new();
                 self.options_page_input = OptionsContextPageInputState::new();
+                self.joining_session = self.creating_from_study;
+                self.creating_from_study = false;
 
-                commands.push(Command::LoadFlashcards(self.current_folder_id))
+                if self.editing_from_study {
+                    self.editing_from_study = false;
+                } else {
+                    commands.push(Command::LoadFlashcards(self.current_folder_id))
+                }
+            }
+            Message::StudiedFlashcardEdited(edited) => {
+                let copies = self
+                    .flashcards
+                    .iter_mut()
+                    .chain(self.currently_studying_flashcard.iter_mut());
+                for flashcard in copies {
+                    if flashcard.id == edited.id {
+                        *flashcard = edited.clone();
+                    }
+                }
+
+                //The choices are picked again in case the answer changed, unless one was picked
+                let studying_edited = self
+                    .currently_studying_flashcard
+                    .as_ref()
+                    .is_some_and(|flashcard| flashcard.id == edited.id);
+                if studying_edited && self.selected_choice.is_none() {
+                    self.choices = self.select_choices();
+                }
             }
             Message::LoadedSingle(flashcard) => {
                 self.new_edit_flashcard = CreateEditFlashcardState {
                     id: flashcard.id,
+                    loaded_front: flashcard.front.clone(),
+                    loaded_back: flashcard.back.clone(),
+                    error: None,
                     front: flashcard.front,
                     back: flashcard.back,
                     status: flashcard.status,
+                    tags: flashcard.tags.join(", "),
+                    image_path: flashcard.image_path,
+                    audio_path: flashcard.audio_path,
+                    card_type: flashcard.card_type,
                 };
             }
-            Message::SetFlashcards(flashcards) => self.flashcards = flashcards,
+            Message::SetFlashcards(flashcards) => {
+                let previous_ids: Vec<Option<i32>> = self
+                    .flashcards
+                    .iter()
+                    .map(|flashcard| flashcard.id)
+                    .collect();
+                self.flashcards = flashcards;
+                if self.joining_session {
+                    self.joining_session = false;
+                    self.join_session(&previous_ids);
+                }
+                //Deleted (or moved) flashcards can't stay selected
+                self.selected_flashcards.retain(|id| {
+                    self.flashcards
+                        .iter()
+                        .any(|flashcard| flashcard.id == Some(*id))
+                });
+                commands.push(Command::LoadFolderStats(self.current_folder_id));
+
+                if !self.search_query.trim().is_empty() {
+                    commands.push(Command::SearchFlashcards(
+                        self.current_folder_id,
+                        self.search_query.clone(),
+                    ));
+                }
+
+                if let Some(paused) = self.resuming_session.take() {
+                    if paused.folder_id == self.current_folder_id {
+                        commands.push(Command::ResumeStudySession(paused));
+                    }
+                }
+
+                //The tags no flashcard has anymore are dropped from the filter
+                let tags = self.folder_tags();
+                self.tag_filter.retain(|tag| tags.contains(tag));
+            }
+            Message::SetFolderStats(stats) => self.folder_stats = stats,
+            Message::ShowHistory(flashcard) => {
+                if let Some(flashcard_id) = flashcard.id {
+                    self.history_flashcard = Some(flashcard);
+                    self.flashcard_history = Vec::new();
+                    commands.push(Command::LoadFlashcardHistory(flashcard_id));
+                    commands.push(Command::OpenHistoryPage);
+                }
+            }
+            Message::HistoryLoaded(history) => self.flashcard_history = history,
+            Message::LeaveStudyPage => {
+                if self.studying_all_due {
+                    commands.push(Command::OpenFolders);
+                } else {
+                    commands.push(Command::OpenFolder(self.current_folder_id));
+                }
+            }
             Message::ToggleCreatePage(flashcard) => {
                 if flashcard.is_none() {
                     self.new_edit_flashcard = CreateEditFlashcardState::new();
                 }
+                self.editing_from_study = false;
 
                 commands.push(Command::ToggleCreateFlashcardPage(flashcard))
             }
+            Message::CreateWhileStudying => {
+                self.new_edit_flashcard = CreateEditFlashcardState::new();
+                self.creating_from_study = true;
+                self.editing_from_study = false;
+
+                commands.push(Command::ToggleCreateFlashcardPage(None))
+            }
+            Message::EditWhileStudying => {
+                if let Some(flashcard) = &self.currently_studying_flashcard {
+                    self.creating_from_study = false;
+                    self.editing_from_study = true;
+
+                    commands.push(Command::ToggleCreateFlashcardPage(Some(flashcard.clone())))
+                }
+            }
             Message::StudyFlashcards => commands.push(Command::OpenStudyFolderFlashcardsPage),
-            Message::ContextPageFrontInput(value) => self.new_edit_flashcard.front = value,
-            Message::ContextPageBackInput(value) => self.new_edit_flashcard.back = value,
+            Message::StudyAgain => self.start_session(),
+            Message::ToggleStudyMode => {
+                self.study_mode = self.study_mode.next();
+                commands.push(self.save_study_preferences())
+            }
+            Message::ToggleBrowse => {
+                self.browsing = !self.browsing;
+                if self.browsing {
+                    self.browse_to(0);
+                } else {
+                    self.next_flashcard();
+                }
+            }
+            Message::BrowsePrevious => {
+                if self.browsing {
+                    let count = self.browse_pool().len().max(1);
+                    self.browse_to(self.browse_index + count - 1);
+                }
+            }
+            Message::BrowseNext => {
+                if self.browsing {
+                    self.browse_to(self.browse_index + 1);
+                }
+            }
+            Message::SelectChoice(index) => {
+                if self.selected_choice.is_none() && index < self.choices.len() {
+                    self.selected_choice = Some(index);
+                    self.response_time = self.shown_at.map(|shown_at| shown_at.elapsed());
+                    commands.push(Command::ShowChoiceFeedback);
+                }
+            }
+            Message::GradeChoice => {
+                if let (Some(index), Some(flashcard)) = (
+                    self.selected_choice,
+                    self.currently_studying_flashcard.clone(),
+                ) {
+                    let action = if self.choices[index] == study_answer(&flashcard) {
+                        StudyActions::Good
+                    } else {
+                        StudyActions::Bad
+                    };
+                    commands.extend(self.update(Message::UpdateFlashcardStatus(flashcard, action)));
+                }
+            }
+            Message::TypedAnswerInput(value) => {
+                if self.typed_answer_result.is_none() {
+                    self.typed_answer = value;
+                }
+            }
+            Message::SubmitTypedAnswer => {
+                if let (None, Some(flashcard)) =
+                    (self.typed_answer_result, &self.currently_studying_flashcard)
+                {
+                    let correct = answer_matches(
+                        &self.typed_answer,
+                        &study_answer(flashcard),
+                        self.typo_tolerance,
+                    );
+                    self.response_time = self.shown_at.map(|shown_at| shown_at.elapsed());
+                    self.typed_answer_result = Some(correct);
+                }
+            }
+            Message::GradeTypedAnswer => {
+                if let (Some(correct), Some(flashcard)) = (
+                    self.typed_answer_result,
+                    self.currently_studying_flashcard.clone(),
+                ) {
+                    let action = if correct {
+                        StudyActions::Good
+                    } else {
+                        StudyActions::Bad
+                    };
+                    commands.extend(self.update(Message::UpdateFlashcardStatus(flashcard, action)));
+                }
+            }
+            Message::ToggleStudyDirection => {
+                self.study_direction = self.study_direction.next();
+                commands.push(self.save_study_preferences())
+            }
+            Message::ToggleStudyFilter => {
+                self.study_filter = self.study_filter.next();
+                commands.push(self.save_study_preferences())
+            }
+            Message::ToggleStudyOrder => {
+                self.study_order = self.study_order.next();
+                commands.push(self.save_study_preferences())
+            }
+            Message::SetStudyPreferences(preferences) => self.set_study_preferences(preferences),
+            Message::ContextPageFrontInput(value) => {
+                self.new_edit_flashcard.front = value;
+                self.new_edit_flashcard.error = None;
+            }
+            Message::ContextPageBackInput(value) => {
+                self.new_edit_flashcard.back = value;
+                self.new_edit_flashcard.error = None;
+            }
+            Message::ContextPageTagsInput(value) => self.new_edit_flashcard.tags = value,
+            Message::ContextPageCloze(cloze) => {
+                self.new_edit_flashcard.card_type = if cloze {
+                    CardType::Cloze
+                } else {
+                    CardType::Basic
+                };
+                self.new_edit_flashcard.error = None;
+            }
+            Message::ToggleTagFilter(tag) => {
+                if self.tag_filter.remove(&tag) == false {
+                    self.tag_filter.insert(tag);
+                }
+                self.list_page = 0;
+            }
+            Message::SearchInput(value) => {
+                self.search_query = value;
+                self.search_generation += 1;
+                self.list_page = 0;
+                if self.search_query.trim().is_empty() {
+                    self.search_results = None;
+                } else {
+                    commands.push(Command::DebounceSearch(self.search_generation));
+                }
+            }
+            Message::RunSearch(generation) => {
+                if generation == self.search_generation && !self.search_query.trim().is_empty() {
+                    commands.push(Command::SearchFlashcards(
+                        self.current_folder_id,
+                        self.search_query.clone(),
+                    ));
+                }
+            }
+            Message::SetSearchResults(flashcards) => {
+                if !self.search_query.trim().is_empty() {
+                    self.search_results = Some(flashcards);
+                }
+            }
+            Message::SetListPage(page) => self.list_page = page,
+            Message::SetListSort(index) => {
+                if let Some(sort) = FLASHCARD_SORTS.get(index) {
+                    self.list_sort = *sort;
+                    self.list_page = 0;
+                }
+            }
+            Message::ToggleSelectionMode => {
+                self.selection_mode = !self.selection_mode;
+                self.selected_flashcards.clear();
+            }
+            //The star is changed on every copy of the flashcard, so the list isn't reloaded
+            Message::ToggleFavorite(flashcard_id) => {
+                let Some(favorite) = self
+                    .flashcards
+                    .iter()
+                    .find(|flashcard| flashcard.id == Some(flashcard_id))
+                    .map(|flashcard| flashcard.favorite == false)
+                else {
+                    return commands;
+                };
+
+                let copies = self
+                    .flashcards
+                    .iter_mut()
+                    .chain(self.search_results.iter_mut().flatten())
+                    .chain(self.currently_studying_flashcard.iter_mut());
+                for flashcard in copies {
+                    if flashcard.id == Some(flashcard_id) {
+                        flashcard.favorite = favorite;
+                    }
+                }
+
+                commands.push(Command::SetFavorite(flashcard_id, favorite));
+            }
+            Message::ToggleSelected(flashcard_id) => {
+                if !self.selected_flashcards.remove(&flashcard_id) {
+                    self.selected_flashcards.insert(flashcard_id);
+                }
+            }
+            //Every flashcard the search and tag filter let through, not only the ones of this page
+            Message::SelectAll => {
+                let visible_ids: Vec<i32> = self
+                    .visible_flashcards()
+                    .iter()
+                    .filter_map(|flashcard| flashcard.id)
+                    .collect();
+                self.selected_flashcards.extend(visible_ids);
+            }
+            Message::ClearSelection => self.selected_flashcards.clear(),
+            //A single flashcard is deleted like from its own delete button
+            Message::DeleteSelected => match self.selected_flashcards.len() {
+                0 => {}
+                1 => {
+                    let flashcard_id = self.selected_flashcards.iter().next().copied();
+                    commands.extend(self.update(Message::RequestDelete(flashcard_id)));
+                }
+                selected => commands.push(Command::ConfirmDeleteSelected(selected)),
+            },
+            Message::DeletedSelected => {
+                self.selected_flashcards.clear();
+                self.selection_mode = false;
+                commands.push(Command::LoadFlashcards(self.current_folder_id));
+            }
             Message::UpdateFlashcardStatus(mut flashcard, action) => {
-                match action {
-                    StudyActions::Bad => flashcard.status = 1,
-                    StudyActions::Ok => flashcard.status = 2,
-                    StudyActions::Good => flashcard.status = 3,
+                //Only saved flashcards can be graded
+                if flashcard.id.is_none() {
+                    return commands;
+                }
+
+                let previous = flashcard.clone();
+                flashcard.status = action.status();
+                let (scheduler, limits) = flashcard
+                    .id
+                    .and_then(|id| self.card_schedulers.get(&id))
+                    .copied()
+                    .unwrap_or((self.scheduler, self.interval_limits));
+                scheduler.update(&mut flashcard, action.grade(), scheduler::now(), limits);
+                if let Some(label) = flashcard.id.and_then(|id| self.folder_labels.get(&id)) {
+                    *self.session_folders.entry(label.clone()).or_default() += 1;
                 }
 
-                commands.push(Command::UpdateFlashcardStatus(flashcard))
+                let response_time = self
+                    .response_time
+                    .take()
+                    .or_else(|| self.shown_at.map(|shown_at| shown_at.elapsed()));
+                let response_time = response_time.map(|response_time| {
+                    let response_time = response_time.min(MAX_RESPONSE_TIME);
+                    flashcard.record_response(response_time.as_millis() as i64);
+                    self.session_response_times.push(response_time);
+                    response_time
+                });
+
+                self.grade_history
+                    .push((previous, action.clone(), response_time));
+                if self.grade_history.len() > UNDO_LIMIT {
+                    self.grade_history.remove(0);
+                }
+
+                self.studied_count += 1;
+                *self.session_results.count_mut(&action) += 1;
+
+                commands.push(Command::UpdateFlashcardStatus(flashcard, action.grade()))
             }
             Message::UpdatedStatus(flashcards) => {
                 self.flashcards = flashcards;
-                self.currently_studying_flashcard_side = CurrentFlashcardSide::Front;
-                self.currently_studying_flashcard = select_random_flashcard(&self.flashcards)
-                    .unwrap_or(Flashcard::new_error_variant());
+                commands.push(Command::LoadFolderStats(self.current_folder_id));
+                commands.push(Command::LoadDueCount);
+                self.last_studied_id = self
+                    .currently_studying_flashcard
+                    .as_ref()
+                    .and_then(|flashcard| flashcard.id);
+                self.next_flashcard();
+                if self.session_finished() && self.session_logged == false {
+                    self.session_logged = true;
+                    commands.push(Command::LogStudySession);
+                }
+            }
+            //The flashcard gets back the status and scheduling it had before being graded
+            Message::UndoLastGrade => {
+                if let Some((flashcard, action, response_time)) = self.grade_history.pop() {
+                    self.studied_count = self.studied_count.saturating_sub(1);
+                    *self.session_results.count_mut(&action) -= 1;
+                    //Grades without a response time didn't add one to the session
+                    if response_time.is_some() {
+                        self.session_response_times.pop();
+                    }
+                    if let Some(label) = flashcard.id.and_then(|id| self.folder_labels.get(&id)) {
+                        if let Some(count) = self.session_folders.get_mut(label) {
+                            *count = count.saturating_sub(1);
+                        }
+                    }
+
+                    commands.push(Command::RestoreFlashcardStatus(flashcard))
+                }
+            }
+            Message::RestoredStatus(flashcard_id, flashcards) => {
+                self.flashcards = flashcards;
+                commands.push(Command::LoadFolderStats(self.current_folder_id));
+                commands.push(Command::LoadDueCount);
+                if let Some(flashcard) = self
+                    .flashcards
+                    .iter()
+                    .find(|flashcard| flashcard.id == Some(flashcard_id))
+                    .cloned()
+                {
+                    self.show_flashcard(Some(flashcard));
+                }
             }
             Message::SwapFlashcardSide => match self.currently_studying_flashcard_side {
                 CurrentFlashcardSide::Front => {
@@ -198,11 +1321,50 @@ impl Flashcards {
                     self.currently_studying_flashcard_side = CurrentFlashcardSide::Front
                 }
             },
-            Message::Delete(flashcard_id) => commands.push(Command::DeleteFlashcard(flashcard_id)),
+            Message::RequestDelete(flashcard_id) => {
+                if let Some(flashcard) = self
+                    .flashcards
+                    .iter()
+                    .find(|flashcard| flashcard_id.is_some() && flashcard.id == flashcard_id)
+                {
+                    self.pending_delete = flashcard_id;
+                    commands.push(Command::ConfirmDelete(flashcard.front.clone()));
+                }
+            }
+            Message::CancelDelete => self.pending_delete = None,
+            Message::RequestMove(flashcard_id) => {
+                if let Some(flashcard_id) = flashcard_id {
+                    commands.push(Command::PickMoveDestination(flashcard_id))
+                }
+            }
+            Message::Delete(flashcard_id) => {
+                self.pending_delete = None;
+                commands.push(Command::DeleteFlashcard(flashcard_id))
+            }
             Message::LoadFlashcards => {
                 commands.push(Command::LoadFlashcards(self.current_folder_id))
             }
             Message::ToggleOptionsPage => commands.push(Command::ToggleOptionsPage),
+            Message::TogglePasteImportPage => {
+                self.paste_import_result = None;
+                commands.push(Command::TogglePasteImportPage)
+            }
+            Message::PasteImportAction(action) => {
+                self.paste_import_result = None;
+                self.paste_import_content.perform(action);
+            }
+            Message::PasteImport => {
+                let text = self.paste_import_content.text();
+                if text.trim().is_empty() == false {
+                    commands.push(Command::ImportTsv(self.current_folder_id, text));
+                }
+            }
+            Message::PasteImported(summary) => {
+                self.paste_import_result = Some(summary);
+                self.paste_import_content = text_editor::Content::new();
+
+                commands.push(Command::LoadFlashcards(self.current_folder_id))
+            }
             Message::OptionsPageInput(input) => match input {
                 OptionsContextPageInputActions::BetweenTerms(value) => {
                     self.options_page_input.between_terms = value
@@ -213,15 +1375,78 @@ impl Flashcards {
                 OptionsContextPageInputActions::ImportContent(value) => {
                     self.options_page_input.import_content = value
                 }
+                OptionsContextPageInputActions::TestSheetShuffled(value) => {
+                    self.options_page_input.test_sheet_shuffled = value
+                }
+                OptionsContextPageInputActions::TestSheetAnswerKey(value) => {
+                    self.options_page_input.test_sheet_answer_key = value
+                }
+                OptionsContextPageInputActions::FindText(value) => {
+                    self.options_page_input.find_text = value
+                }
+                OptionsContextPageInputActions::ReplaceText(value) => {
+                    self.options_page_input.replace_text = value
+                }
+                OptionsContextPageInputActions::ReplaceSides(index) => {
+                    if let Some(sides) = REPLACE_SIDES.get(index) {
+                        self.options_page_input.replace_sides = *sides;
+                    }
+                }
+                OptionsContextPageInputActions::ReplaceCaseSensitive(value) => {
+                    self.options_page_input.replace_case_sensitive = value
+                }
+                OptionsContextPageInputActions::PdfLayout(index) => {
+                    if let Some(layout) = PDF_LAYOUTS.get(index) {
+                        self.options_page_input.pdf_layout = *layout;
+                    }
+                }
+                OptionsContextPageInputActions::StudyMode(index) => {
+                    if let Some(mode) = STUDY_MODES.get(index) {
+                        self.study_mode = *mode;
+                        commands.push(self.save_study_preferences());
+                    }
+                }
+                OptionsContextPageInputActions::StudyDirection(index) => {
+                    if let Some(direction) = STUDY_DIRECTIONS.get(index) {
+                        self.study_direction = *direction;
+                        commands.push(self.save_study_preferences());
+                    }
+                }
+                OptionsContextPageInputActions::StudyFilter(index) => {
+                    if let Some(filter) = STUDY_FILTERS.get(index) {
+                        self.study_filter = *filter;
+                        commands.push(self.save_study_preferences());
+                    }
+                }
+                OptionsContextPageInputActions::StudyOrder(index) => {
+                    if let Some(order) = STUDY_ORDERS.get(index) {
+                        self.study_order = *order;
+                        commands.push(self.save_study_preferences());
+                    }
+                }
             },
-            Message::Import => {
-                let content = parse_import_content(
-                    &self.options_page_input.between_cards,
-                    &self.options_page_input.between_terms,
-                    &self.options_page_input.import_content,
-                );
-                commands.push(Command::ImportFlashcards(content))
+            Message::FindReplace => {
+                if self.find_replace_preview() > 0 {
+                    commands.push(Command::FindReplace(
+                        self.current_folder_id,
+                        self.options_page_input.find_text.clone(),
+                        self.options_page_input.replace_text.clone(),
+                        self.options_page_input.replace_sides,
+                        self.options_page_input.replace_case_sensitive,
+                    ));
+                }
+            }
+            Message::Replaced => {
+                self.options_page_input.find_text = String::new();
+                self.options_page_input.replace_text = String::new();
+
+                commands.push(Command::LoadFlashcards(self.current_folder_id))
             }
+            Message::Import => commands.push(Command::ImportQuizlet(
+                self.options_page_input.import_content.clone(),
+                self.options_page_input.between_terms.clone(),
+                self.options_page_input.between_cards.clone(),
+            )),
             Message::RestartSingleFlashcardStatus(flashcard_id) => {
                 commands.push(Command::RestartSingleFlashcardStatus(flashcard_id))
             }
@@ -234,10 +1459,24 @@ impl Flashcards {
                     let flashcards = parse_ankifile(&path);
                     match flashcards {
                         Ok(flashcards) => commands.push(Command::ImportFlashcards(flashcards)),
-                        Err(err) => println!("{:?}", err), //TODO: Error handling?
+                        Err(err) => commands.push(Command::ShowError(err.into())),
                     }
                 }
             }
+            Message::OpenCsvFileSelection => commands.push(Command::OpenCsvFileSelection),
+            Message::OpenCsvFileResult(open_result) => {
+                for path in open_result {
+                    match std::fs::read_to_string(decode_file_path(&path)) {
+                        Ok(content) => commands.push(Command::ImportCsvFlashcards(content)),
+                        Err(err) => commands.push(Command::ShowError(err.into())),
+                    }
+                }
+            }
+            //The rows that could be parsed are imported anyway
+            Message::ImportFailed(error) => {
+                commands.push(Command::LoadFlashcards(self.current_folder_id));
+                commands.push(Command::ShowError(error));
+            }
             Message::OpenFolderExportDestination => {
                 if self.flashcards.is_empty() == false {
                     commands.push(Command::OpenFolderExportDestination)
@@ -248,29 +1487,259 @@ impl Flashcards {
             }
             Message::OpenFolderExportDestinationResult(save_result) => {
                 for path in save_result {
-                    let _ = export_flashcards(&path, &self.flashcards);
+                    if let Err(err) = export_flashcards(&path, &self.flashcards) {
+                        commands.push(Command::ShowError(err.into()));
+                    }
                 }
             }
-        }
-
-        commands
-    }
-
-    fn flashcard_header_row(&self) -> Element<Message> {
-        let spacing = theme::active().cosmic().spacing;
-
-        //TODO: Replace Text with IconCache::get("add-symbolic", 18) - For now it causes visual issues when the page is empty...
-        let new_flashcard_button = widget::button(widget::text(fl!("new")))
-            .style(theme::Button::Suggested)
-            .padding(spacing.space_xxs)
-            .on_press(Message::ToggleCreatePage(None));
-
-        //TODO: IconCache::get("menu-vertical-symbolic", 18) - For now it causes visual issues when the page is empty...
-        let flashcard_options_button = widget::button(widget::text(fl!("options")))
-            .style(theme::Button::Standard)
-            .padding(spacing.space_xxs)
+            Message::OpenTestSheetExportDestination => {
+                commands.push(Command::OpenTestSheetExportDestination)
+            }
+            Message::OpenTestSheetExportDestinationResult(save_result) => {
+                for path in save_result {
+                    if let Err(err) = export_test_sheet(
+                        &path,
+                        &self.flashcards,
+                        (&fl!("test-sheet-title"), &fl!("test-sheet-answer-key")),
+                        self.options_page_input.test_sheet_shuffled,
+                        self.options_page_input.test_sheet_answer_key,
+                    ) {
+                        commands.push(Command::ShowError(err.into()));
+                    }
+                }
+            }
+            Message::OpenPdfExportDestination => {
+                if self.flashcards.is_empty() == false {
+                    commands.push(Command::OpenPdfExportDestination)
+                }
+            }
+            Message::OpenPdfExportDestinationResult(save_result) => {
+                if let Some(path) = save_result.first() {
+                    commands.push(Command::ExportFolderPdf(
+                        self.current_folder_id,
+                        decode_file_path(path),
+                        self.options_page_input.pdf_layout,
+                    ))
+                }
+            }
+            Message::OpenSyncSourceSelection => commands.push(Command::OpenSyncSourceSelection),
+            Message::OpenSyncSourceResult(open_result) => {
+                if let Some(path) = open_result.first() {
+                    commands.push(Command::SetFolderSource(
+                        self.current_folder_id,
+                        decode_file_path(path),
+                    ))
+                }
+            }
+            Message::SetFolderSource(source_path) => self.current_folder_source = source_path,
+            Message::SyncDeleteMissingToggled(value) => self.sync_delete_missing = value,
+            Message::OpenImageFileSelection => commands.push(Command::OpenImageFileSelection),
+            Message::OpenImageFileResult(open_result) => {
+                if let Some(path) = open_result.first() {
+                    commands.push(Command::StoreFlashcardImage(decode_file_path(path)))
+                }
+            }
+            Message::SetImage(image_path) => self.new_edit_flashcard.image_path = image_path,
+            Message::OpenAudioFileSelection => commands.push(Command::OpenAudioFileSelection),
+            Message::OpenAudioFileResult(open_result) => {
+                if let Some(path) = open_result.first() {
+                    commands.push(Command::StoreFlashcardAudio(decode_file_path(path)))
+                }
+            }
+            Message::SetAudio(audio_path) => self.new_edit_flashcard.audio_path = audio_path,
+            Message::PlayAudio => {
+                //Replacing the playback stops the previous one
+                self.audio_playback = None;
+                if let Some(audio_path) = self
+                    .currently_studying_flashcard
+                    .as_ref()
+                    .and_then(|flashcard| flashcard.audio_path.as_ref())
+                {
+                    match AudioPlayback::play(audio_path) {
+                        Ok(playback) => self.audio_playback = Some(playback),
+                        Err(err) => commands.push(Command::ShowError(err)),
+                    }
+                }
+            }
+            Message::SyncFromSource => {
+                if let Some(source_path) = &self.current_folder_source {
+                    //Count the flashcards that are no longer in the file, so deleting them can be confirmed.
+                    //The fronts are compared like the sync does, so the count is what gets deleted
+                    let missing = if self.sync_delete_missing {
+                        match parse_tsv_file(source_path) {
+                            Ok(flashcards) => {
+                                let fronts: HashSet<String> = flashcards
+                                    .iter()
+                                    .map(|flashcard| front_key(&flashcard.front))
+                                    .collect();
+                                self.flashcards
+                                    .iter()
+                                    .filter(|flashcard| {
+                                        !fronts.contains(&front_key(&flashcard.front))
+                                    })
+                                    .count()
+                            }
+                            Err(err) => {
+                                commands.push(Command::ShowError(err.into()));
+                                return commands;
+                            }
+                        }
+                    } else {
+                        0
+                    };
+
+                    commands.push(Command::SyncFromSource {
+                        folder_id: self.current_folder_id,
+                        delete_missing: self.sync_delete_missing,
+                        missing,
+                    })
+                }
+            }
+        }
+
+        commands
+    }
+
+    /// Flashcards shown in the list, the folder (or the search results) narrowed down by the
+    /// selected tags
+    fn visible_flashcards(&self) -> Vec<&Flashcard> {
+        let mut flashcards: Vec<&Flashcard> = self
+            .search_results
+            .as_ref()
+            .unwrap_or(&self.flashcards)
+            .iter()
+            .filter(|flashcard| {
+                matches_tags(&flashcard.tags, &self.tag_filter, self.match_all_tags)
+            })
+            .collect();
+
+        self.list_sort.sort(&mut flashcards);
+        flashcards
+    }
+
+    /// Actions over the selected flashcards, shown while in the selection mode
+    fn selection_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut delete_button = widget::button(widget::text(fl!("delete-selected")))
+            .style(theme::Button::Destructive)
+            .padding(spacing.space_xxs);
+        if self.selected_flashcards.is_empty() == false {
+            delete_button = delete_button.on_press(Message::DeleteSelected);
+        }
+
+        widget::row::with_capacity(4)
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .push(
+                widget::text::body(fl!(
+                    "selected-flashcards",
+                    selected = self.selected_flashcards.len()
+                ))
+                .width(Length::Fill),
+            )
+            .push(
+                widget::button(widget::text(fl!("select-all")))
+                    .style(theme::Button::Standard)
+                    .padding(spacing.space_xxs)
+                    .on_press(Message::SelectAll),
+            )
+            .push(
+                widget::button(widget::text(fl!("clear-selection")))
+                    .style(theme::Button::Standard)
+                    .padding(spacing.space_xxs)
+                    .on_press(Message::ClearSelection),
+            )
+            .push(delete_button)
+            .into()
+    }
+
+    /// Front and back inputs above the flashcard list, Enter in the back one adds the flashcard
+    fn quick_add_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut add_button = widget::button(IconCache::get("list-add-symbolic", 18))
+            .padding(spacing.space_xxs)
+            .style(theme::Button::Suggested);
+        if self.quick_add_front.trim().is_empty() == false {
+            add_button = add_button.on_press(Message::QuickAdd);
+        }
+
+        let inputs = widget::row::with_capacity(3)
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .push(
+                widget::text_input(fl!("flashcard-front-placeholder"), &self.quick_add_front)
+                    .id(self.quick_add_front_id.clone())
+                    .on_input(Message::QuickAddFrontInput)
+                    .on_submit(Message::QuickAddNext)
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::text_input(fl!("flashcard-back-placeholder"), &self.quick_add_back)
+                    .id(self.quick_add_back_id.clone())
+                    .on_input(Message::QuickAddBackInput)
+                    .on_submit(Message::QuickAdd)
+                    .width(Length::Fill),
+            )
+            .push(add_button);
+
+        let mut column = widget::column::with_capacity(2)
+            .spacing(spacing.space_xxxs)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .push(inputs);
+        if let Some(error) = &self.quick_add_error {
+            column = column.push(widget::text::caption(error.as_str()));
+        }
+
+        column.into()
+    }
+
+    fn flashcard_header_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        //TODO: Replace Text with IconCache::get("add-symbolic", 18) - For now it causes visual issues when the page is empty...
+        let new_flashcard_button = widget::button(widget::text(fl!("new")))
+            .style(theme::Button::Suggested)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleCreatePage(None));
+
+        //TODO: IconCache::get("menu-vertical-symbolic", 18) - For now it causes visual issues when the page is empty...
+        let flashcard_options_button = widget::button(widget::text(fl!("options")))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
             .on_press(Message::ToggleOptionsPage);
 
+        let study_mode_button = widget::button(widget::text(self.study_mode.title()))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleStudyMode);
+
+        let study_direction_button = widget::button(widget::text(self.study_direction.title()))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleStudyDirection);
+
+        let study_filter_button = widget::button(widget::text(self.study_filter.title()))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleStudyFilter);
+
+        let study_order_button = widget::button(widget::text(self.study_order.title()))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleStudyOrder);
+
+        let selection_button = widget::button(widget::text(fl!("select")))
+            .style(if self.selection_mode {
+                theme::Button::Suggested
+            } else {
+                theme::Button::Standard
+            })
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleSelectionMode);
+
         let study_button = if self.flashcards.is_empty() == false {
             widget::button(widget::text(fl!("study")))
                 .style(theme::Button::Suggested)
@@ -287,12 +1756,588 @@ impl Flashcards {
             .spacing(spacing.space_s)
             .padding([spacing.space_none, spacing.space_xxs])
             .push(widget::text::title3(fl!("flashcards")).width(Length::Fill))
+            .push(study_mode_button)
+            .push(study_direction_button)
+            .push(study_filter_button)
+            .push(study_order_button)
             .push(study_button)
+            .push(selection_button)
             .push(new_flashcard_button)
             .push(flashcard_options_button)
             .into()
     }
 
+    /// Starts a new study session with the flashcards the study filter lets through,
+    /// the session ends once as many as the session length have been graded
+    pub fn start_session(&mut self) {
+        let now = scheduler::now();
+        let available = self
+            .flashcards
+            .iter()
+            .filter(|flashcard| self.in_study_pool(flashcard, now))
+            .count();
+        self.session_size = match self.session_length {
+            Some(length) => available.min(length),
+            None => available,
+        };
+        self.rng = study_rng();
+        self.studied_count = 0;
+        self.session_logged = false;
+        self.creating_from_study = false;
+        self.editing_from_study = false;
+        self.joining_session = false;
+        self.grade_history.clear();
+        self.session_results = SessionResults::default();
+        self.session_response_times.clear();
+        self.session_folders.clear();
+        self.last_studied_id = None;
+        self.browsing = false;
+        self.next_flashcard();
+    }
+
+    /// Starts a session that goes on from where the paused one was left, the flashcard that was
+    /// being studied is shown again if it's still in the folder
+    pub fn resume_session(&mut self, paused: PausedSession) {
+        self.start_session();
+        self.session_size = paused.session_size;
+        self.studied_count = paused.studied_count;
+        self.session_results = paused.results;
+        self.session_response_times = paused
+            .response_times
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        self.last_studied_id = paused.last_studied_id;
+
+        let studied_flashcard = self
+            .flashcards
+            .iter()
+            .find(|flashcard| paused.flashcard_id.is_some() && flashcard.id == paused.flashcard_id)
+            .cloned();
+        let flashcard = match studied_flashcard {
+            Some(flashcard) => Some(flashcard),
+            None => self.select_due_flashcard(),
+        };
+        self.show_flashcard(flashcard);
+    }
+
+    /// Progress of the folder study session, None if nothing has been graded yet or it's over
+    pub fn paused_session(&self) -> Option<PausedSession> {
+        if self.studying_all_due || self.studied_count == 0 || self.session_finished() {
+            return None;
+        }
+
+        Some(PausedSession {
+            folder_id: self.current_folder_id,
+            flashcard_id: self
+                .currently_studying_flashcard
+                .as_ref()
+                .and_then(|flashcard| flashcard.id),
+            last_studied_id: self.last_studied_id,
+            studied_count: self.studied_count,
+            session_size: self.session_size,
+            results: self.session_results,
+            response_times: self
+                .session_response_times
+                .iter()
+                .map(|time| time.as_millis() as u64)
+                .collect(),
+        })
+    }
+
+    /// Starts a session with the due flashcards of every folder, each one is graded with the
+    /// scheduler and interval limits of its studyset (by flashcard id). The folder preferences
+    /// don't apply to it
+    pub fn start_all_due_session(
+        &mut self,
+        locations: Vec<FlashcardLocation>,
+        schedulers: HashMap<i32, (SchedulerKind, IntervalLimits)>,
+    ) {
+        self.studying_all_due = true;
+        self.card_schedulers = schedulers;
+        self.folder_labels = locations
+            .iter()
+            .filter_map(|location| {
+                let label = format!("{} · {}", location.studyset_name, location.folder_name);
+                location.flashcard.id.map(|id| (id, label))
+            })
+            .collect();
+        self.flashcards = locations
+            .into_iter()
+            .map(|location| location.flashcard)
+            .collect();
+        self.study_direction = StudyDirection::default();
+        self.study_filter = StudyFilter::AllDue;
+        self.study_order = StudyOrder::default();
+        self.start_session();
+    }
+
+    /// How the current folder is studied
+    pub fn study_preferences(&self) -> FolderStudyPreferences {
+        FolderStudyPreferences {
+            mode: self.study_mode,
+            direction: self.study_direction,
+            order: self.study_order,
+            filter: self.study_filter,
+        }
+    }
+
+    /// Studies the current folder the way it was saved in the database
+    pub fn set_study_preferences(&mut self, preferences: FolderStudyPreferences) {
+        self.study_mode = preferences.mode;
+        self.study_direction = preferences.direction;
+        self.study_order = preferences.order;
+        self.study_filter = preferences.filter;
+    }
+
+    fn save_study_preferences(&self) -> Command {
+        Command::SaveStudyPreferences(self.current_folder_id, self.study_preferences())
+    }
+
+    /// Flashcards the study session goes through
+    pub fn study_scope(&self) -> StudyScope {
+        if self.studying_all_due {
+            StudyScope::AllDue
+        } else {
+            StudyScope::Folder(self.current_folder_id)
+        }
+    }
+
+    /// Adds the flashcards that weren't loaded before, and the study filter lets through,
+    /// to the current session. If there was nothing left to study the first one is shown
+    fn join_session(&mut self, previous_ids: &[Option<i32>]) {
+        let now = scheduler::now();
+        let joined = self
+            .flashcards
+            .iter()
+            .filter(|flashcard| {
+                previous_ids.contains(&flashcard.id) == false && self.in_study_pool(flashcard, now)
+            })
+            .count();
+        self.session_size += joined;
+
+        if joined > 0 && self.currently_studying_flashcard.is_none() {
+            self.next_flashcard();
+        }
+    }
+
+    /// True once every flashcard of the session has been graded
+    fn session_finished(&self) -> bool {
+        self.session_size > 0 && self.studied_count >= self.session_size
+    }
+
+    /// Moves the study page to the next due flashcard
+    pub fn next_flashcard(&mut self) {
+        let flashcard = self.select_due_flashcard();
+        self.show_flashcard(flashcard);
+    }
+
+    /// Flashcards the browse mode goes through, the ones the study filter lets through in creation order
+    fn browse_pool(&self) -> Vec<&Flashcard> {
+        let now = scheduler::now();
+        self.flashcards
+            .iter()
+            .filter(|flashcard| self.in_study_pool(flashcard, now))
+            .collect()
+    }
+
+    /// Shows the flashcard at the position in the browse pool, wrapping around at the ends
+    fn browse_to(&mut self, index: usize) {
+        let pool = self.browse_pool();
+        if pool.is_empty() {
+            self.show_flashcard(None);
+            return;
+        }
+
+        self.browse_index = index % pool.len();
+        let flashcard = pool[self.browse_index].clone();
+        self.show_flashcard(Some(flashcard));
+    }
+
+    /// How many flashcards of the folder the find and replace of the options page would change
+    fn find_replace_preview(&self) -> usize {
+        let input = &self.options_page_input;
+        self.flashcards
+            .iter()
+            .filter(|flashcard| {
+                replace_flashcard_text(
+                    &flashcard.front,
+                    &flashcard.back,
+                    &input.find_text,
+                    &input.replace_text,
+                    input.replace_sides,
+                    input.replace_case_sensitive,
+                )
+                .is_some()
+            })
+            .count()
+    }
+
+    /// Average time it took to answer the flashcards graded in the session
+    fn average_response_time(&self) -> Option<Duration> {
+        let count = self.session_response_times.len() as u32;
+        if count == 0 {
+            return None;
+        }
+
+        Some(self.session_response_times.iter().sum::<Duration>() / count)
+    }
+
+    /// Drops the changes made in the create/edit context page
+    pub fn discard_edit(&mut self) {
+        self.new_edit_flashcard = CreateEditFlashcardState::new();
+        self.editing_from_study = false;
+    }
+
+    /// Stops what belongs to the study session once its page is left
+    pub fn leave_session(&mut self) {
+        self.audio_playback = None;
+        self.grade_history.clear();
+        self.browsing = false;
+        self.editing_from_study = false;
+        self.studying_all_due = false;
+        self.card_schedulers.clear();
+        self.folder_labels.clear();
+    }
+
+    /// Shows the flashcard in the study page, from the side given by the study direction.
+    /// None leaves the session without a flashcard, so the page says there's nothing to study
+    fn show_flashcard(&mut self, flashcard: Option<Flashcard>) {
+        self.audio_playback = None;
+        self.currently_studying_flashcard = flashcard;
+        self.shown_at = Some(Instant::now());
+        self.response_time = None;
+        self.choices = self.select_choices();
+        self.selected_choice = None;
+        self.typed_answer = String::new();
+        self.typed_answer_result = None;
+        self.currently_studying_flashcard_side = match self.study_direction {
+            StudyDirection::FrontFirst => CurrentFlashcardSide::Front,
+            StudyDirection::BackFirst => CurrentFlashcardSide::Back,
+            StudyDirection::Random => {
+                if self.rng.gen::<bool>() {
+                    CurrentFlashcardSide::Front
+                } else {
+                    CurrentFlashcardSide::Back
+                }
+            }
+        };
+    }
+
+    /// The back of the current flashcard and (up to) three different backs of the folder, shuffled
+    fn select_choices(&mut self) -> Vec<String> {
+        let Some(flashcard) = &self.currently_studying_flashcard else {
+            return Vec::new();
+        };
+        let correct = study_answer(flashcard);
+
+        //Deduplicated in a sorted set, a HashSet would give them in a different order every run
+        //and the seeded shuffle wouldn't repeat
+        let mut distractors: Vec<String> = self
+            .flashcards
+            .iter()
+            .map(study_answer)
+            .filter(|answer| *answer != correct)
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        distractors.shuffle(&mut self.rng);
+        distractors.truncate(MAX_CHOICES - 1);
+
+        let mut choices = distractors;
+        choices.push(correct);
+        choices.shuffle(&mut self.rng);
+        choices
+    }
+
+    /// Whether the study filter lets the flashcard through, the default filter leaves out
+    /// the mastered flashcards unless they're included
+    fn in_study_pool(&self, flashcard: &Flashcard, now: i64) -> bool {
+        if self.study_filter == StudyFilter::AllDue
+            && self.include_mastered == false
+            && scheduler::is_mastered(flashcard, self.mastered_streak, now)
+        {
+            return false;
+        }
+
+        self.study_filter.includes(flashcard, now)
+    }
+
+    /// Selects, in the study order, a flashcard between the ones the study filter lets through
+    /// (by default the due ones), None when there's none left to study
+    pub fn select_due_flashcard(&mut self) -> Option<Flashcard> {
+        let now = scheduler::now();
+        let mut due_flashcards: Vec<Flashcard> = self
+            .flashcards
+            .iter()
+            .filter(|flashcard| self.in_study_pool(flashcard, now))
+            .cloned()
+            .collect();
+
+        //Filters that don't wait for the due date still study the due (and new) flashcards first
+        if due_flashcards
+            .iter()
+            .any(|flashcard| scheduler::is_due(flashcard, now))
+        {
+            due_flashcards.retain(|flashcard| scheduler::is_due(flashcard, now));
+        }
+
+        //Sequential goes on from the last studied flashcard, so it isn't repeated until the rest are seen
+        if self.study_order == StudyOrder::Sequential {
+            let first = due_flashcards.iter().min_by_key(|flashcard| flashcard.id);
+            let following = due_flashcards
+                .iter()
+                .filter(|flashcard| flashcard.id > self.last_studied_id)
+                .min_by_key(|flashcard| flashcard.id);
+
+            return following.or(first).cloned();
+        }
+
+        //The last studied flashcard is only repeated when it's the only one left
+        if due_flashcards.len() > 1 && self.last_studied_id.is_some() {
+            due_flashcards.retain(|flashcard| flashcard.id != self.last_studied_id);
+        }
+
+        let selected = match self.study_order {
+            StudyOrder::OldestFirst => due_flashcards
+                .iter()
+                .min_by_key(|flashcard| (flashcard.due_date, flashcard.id))
+                .cloned(),
+            _ => select_weighted_flashcard(&due_flashcards, &mut self.rng),
+        };
+        selected
+    }
+
+    /// Bar with the share of flashcards in each status and a legend with the counts
+    fn folder_stats_bar(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+        let stats = self.folder_stats;
+
+        let segment = |count: usize, style: theme::Button| {
+            widget::button(widget::Space::with_height(Length::Fixed(6.0)))
+                .style(style)
+                .padding(0)
+                .width(Length::FillPortion(count.min(u16::MAX as usize) as u16))
+        };
+
+        let mut bar = widget::row::with_capacity(4).spacing(spacing.space_xxxs);
+        for (count, style) in [
+            (
+                stats.good,
+                button_style(false, false, ButtonStyle::GoodButton),
+            ),
+            (stats.ok, button_style(false, false, ButtonStyle::OkButton)),
+            (
+                stats.bad,
+                button_style(false, false, ButtonStyle::BadButton),
+            ),
+            (stats.unseen, theme::Button::Standard),
+        ] {
+            if count > 0 {
+                bar = bar.push(segment(count, style));
+            }
+        }
+
+        let legend = widget::text::caption(fl!(
+            "folder-stats",
+            good = stats.good,
+            ok = stats.ok,
+            bad = stats.bad,
+            unseen = stats.unseen,
+            total = stats.total
+        ));
+
+        widget::column::with_capacity(2)
+            .push(bar)
+            .push(legend)
+            .spacing(spacing.space_xxxs)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .into()
+    }
+
+    /// Buttons to go to the previous and next pages of the flashcard list
+    fn list_pagination_row(&self, page: usize, page_count: usize) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut previous_button = widget::button(IconCache::get("go-previous-symbolic", 18))
+            .padding(spacing.space_xxs)
+            .style(theme::Button::Standard);
+        if page > 0 {
+            previous_button = previous_button.on_press(Message::SetListPage(page - 1));
+        }
+
+        let mut next_button = widget::button(IconCache::get("go-next-symbolic", 18))
+            .padding(spacing.space_xxs)
+            .style(theme::Button::Standard);
+        if page + 1 < page_count {
+            next_button = next_button.on_press(Message::SetListPage(page + 1));
+        }
+
+        widget::row::with_capacity(3)
+            .push(previous_button)
+            .push(widget::text::body(fl!(
+                "list-page",
+                current = page + 1,
+                total = page_count
+            )))
+            .push(next_button)
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_s)
+            .padding([
+                spacing.space_none,
+                spacing.space_xxs,
+                spacing.space_xxs,
+                spacing.space_xxs,
+            ])
+            .into()
+    }
+
+    /// Every tag used in the folder, sorted
+    fn folder_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .flashcards
+            .iter()
+            .flat_map(|flashcard| flashcard.tags.iter().cloned())
+            .collect::<HashSet<String>>()
+            .into_iter()
+            .collect();
+        tags.sort();
+        tags
+    }
+
+    /// Buttons to filter the flashcard list by tag, several can be selected and pressing a
+    /// selected one unselects it
+    fn tag_filter_row(&self, tags: Vec<String>) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut row = widget::row::with_capacity(tags.len())
+            .spacing(spacing.space_xxs)
+            .padding([spacing.space_none, spacing.space_xxs]);
+
+        for tag in tags {
+            let selected = self.tag_filter.contains(&tag);
+            row = row.push(
+                widget::button(widget::text(tag.clone()))
+                    .padding([spacing.space_xxxs, spacing.space_xs])
+                    .style(if selected {
+                        theme::Button::Suggested
+                    } else {
+                        theme::Button::Standard
+                    })
+                    .on_press(Message::ToggleTagFilter(tag)),
+            );
+        }
+
+        row.into()
+    }
+
+    /// A side of the flashcard as shown while studying, centered and below its image (if any),
+    /// with its inline math converted and rendered as Markdown if enabled
+    fn flashcard_text(&self, flashcard: &Flashcard, text: &str, max_size: u16) -> Element<Message> {
+        //Math goes first so its ^ and _ aren't mistaken for Markdown
+        let text = render_inline_math(text);
+        //Long sides get a smaller text, so they fit in the card instead of overflowing it
+        let size = fitting_text_size(&text, max_size);
+        let content: Element<Message> = if self.render_markdown {
+            view_markdown(&text, size)
+        } else {
+            widget::Text::new(break_long_words(&text, MAX_WORD_LENGTH))
+                .size(size)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+                .into()
+        };
+
+        let mut column = widget::column::with_capacity(2)
+            .spacing(theme::active().cosmic().spacing.space_s)
+            .align_items(Alignment::Center)
+            .width(Length::Fill);
+        if let Some(image_path) = &flashcard.image_path {
+            column = column.push(flashcard_image(image_path, 200.0));
+        }
+
+        widget::container(column.push(content))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_y(Vertical::Center)
+            .into()
+    }
+
+    /// Shows the image of the flashcard being created or edited, with buttons to pick or remove it
+    fn image_selection_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut buttons = widget::row::with_capacity(2)
+            .push(
+                widget::button::standard(fl!("flashcard-image-select-button"))
+                    .on_press(Message::OpenImageFileSelection),
+            )
+            .spacing(spacing.space_xxs);
+
+        let mut column = widget::column::with_capacity(3)
+            .push(widget::text::body(fl!("flashcard-image-title")))
+            .spacing(spacing.space_xxs)
+            .padding([0, 15, 0, 15]);
+
+        match &self.new_edit_flashcard.image_path {
+            Some(image_path) => {
+                column = column.push(flashcard_image(image_path, 96.0));
+                buttons = buttons.push(
+                    widget::button::destructive(fl!("flashcard-image-remove-button"))
+                        .on_press(Message::SetImage(None)),
+                );
+            }
+            None => column = column.push(widget::text::caption(fl!("flashcard-image-none"))),
+        }
+
+        column.push(buttons).into()
+    }
+
+    /// Shows the audio file of the flashcard being created or edited, with buttons to pick or remove it
+    fn audio_selection_row(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut buttons = widget::row::with_capacity(2)
+            .push(
+                widget::button::standard(fl!("flashcard-audio-select-button"))
+                    .on_press(Message::OpenAudioFileSelection),
+            )
+            .spacing(spacing.space_xxs);
+
+        let file_name = match &self.new_edit_flashcard.audio_path {
+            Some(audio_path) => {
+                buttons = buttons.push(
+                    widget::button::destructive(fl!("flashcard-audio-remove-button"))
+                        .on_press(Message::SetAudio(None)),
+                );
+                std::path::Path::new(audio_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            }
+            None => fl!("flashcard-audio-none"),
+        };
+
+        widget::column::with_capacity(3)
+            .push(widget::text::body(fl!("flashcard-audio-title")))
+            .push(widget::text::caption(file_name))
+            .push(buttons)
+            .spacing(spacing.space_xxs)
+            .padding([0, 15, 0, 15])
+            .into()
+    }
+
+    /// Text shown on the list row, falls back to the back when the front is empty
+    fn preview_text(flashcard: &Flashcard) -> String {
+        if !flashcard.front.trim().is_empty() {
+            flashcard.front.clone()
+        } else if !flashcard.back.trim().is_empty() {
+            flashcard.back.clone()
+        } else {
+            fl!("empty-flashcard")
+        }
+    }
+
     pub fn view(&self) -> Element<Message> {
         let spacing = theme::active().cosmic().spacing;
 
@@ -302,7 +2347,20 @@ impl Flashcards {
                 .spacing(spacing.space_xxxs)
                 .padding([spacing.space_none, spacing.space_xxs]);
 
-            for flashcard in &self.flashcards {
+            let visible_flashcards = self.visible_flashcards();
+
+            //Only the rows of the current page are built, the page is clamped in case the list shrank
+            let page_count = visible_flashcards
+                .len()
+                .div_ceil(FLASHCARDS_PAGE_SIZE)
+                .max(1);
+            let page = self.list_page.min(page_count - 1);
+
+            for flashcard in visible_flashcards
+                .into_iter()
+                .skip(page * FLASHCARDS_PAGE_SIZE)
+                .take(FLASHCARDS_PAGE_SIZE)
+            {
                 let edit_button = widget::button(IconCache::get("edit-button-symbolic", 18))
                     .padding(spacing.space_xxs)
                     .style(theme::Button::Standard)
@@ -311,49 +2369,115 @@ impl Flashcards {
                 let delete_button = widget::button(IconCache::get("user-trash-full-symbolic", 18))
                     .padding(spacing.space_xxs)
                     .style(theme::Button::Destructive)
-                    .on_press(Message::Delete(flashcard.id));
-
-                //TODO: Custom Button to make it look like a badge
-                let badge = widget::text(match flashcard.status {
-                    1 => format!("{}     ", fl!("bad-status")), // High chance (status = 1 = flashcard Bad)
-                    2 => format!("{}     ", fl!("ok-status")), // Medium chance (status = 2 = flashcard Ok)
-                    3 => format!("{}     ", fl!("good-status")), // Low chance (status = 3 = flashcard Good)
-                    _ => String::new(), // Default chance for other statuses
-                })
-                .vertical_alignment(Vertical::Center)
-                .horizontal_alignment(Horizontal::Left)
-                .width(Length::Shrink);
+                    .on_press(Message::RequestDelete(flashcard.id));
+
+                let move_button = widget::button(IconCache::get("go-jump-symbolic", 18))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Standard)
+                    .on_press(Message::RequestMove(flashcard.id));
+
+                let favorite_button = favorite_button(flashcard);
+
+                let info_button = widget::button(IconCache::get("dialog-information-symbolic", 18))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Standard)
+                    .on_press(Message::ShowHistory(flashcard.clone()));
 
-                let flashcard_front = widget::text(flashcard.front.clone())
+                let preview = if self.render_markdown {
+                    markdown_to_plain_text(&Self::preview_text(flashcard))
+                } else {
+                    Self::preview_text(flashcard)
+                };
+                let flashcard_front = widget::text(break_long_words(&preview, MAX_WORD_LENGTH))
                     .vertical_alignment(Vertical::Center)
                     .horizontal_alignment(Horizontal::Left)
                     .width(Length::Fill);
 
-                let row = widget::row::with_capacity(2)
+                let mut row = widget::row::with_capacity(9)
                     .align_items(Alignment::Center)
                     .spacing(spacing.space_xxs)
-                    .padding([spacing.space_xxxs, spacing.space_xxs])
+                    .padding([spacing.space_xxxs, spacing.space_xxs]);
+                if let (true, Some(flashcard_id)) = (self.selection_mode, flashcard.id) {
+                    let selected = self.selected_flashcards.contains(&flashcard_id);
+                    row = row.push(
+                        widget::button(IconCache::get("object-select-symbolic", 18))
+                            .padding(spacing.space_xxs)
+                            .style(if selected {
+                                theme::Button::Suggested
+                            } else {
+                                theme::Button::Standard
+                            })
+                            .on_press(Message::ToggleSelected(flashcard_id)),
+                    );
+                }
+                if let Some(image_path) = &flashcard.image_path {
+                    row = row.push(flashcard_image(image_path, 32.0));
+                }
+
+                row = row
                     .push(flashcard_front)
-                    .push(badge)
-                    .push(delete_button)
-                    .push(edit_button);
+                    .push(status_badge(flashcard.status))
+                    .push(favorite_button);
+                if self.selection_mode == false {
+                    row = row
+                        .push(info_button)
+                        .push(move_button)
+                        .push(delete_button)
+                        .push(edit_button);
+                }
 
                 flashcards = flashcards.add(row);
             }
 
-            widget::column::with_capacity(2)
+            let mut column = widget::column::with_capacity(5)
                 .spacing(spacing.space_xxs)
                 .push(self.flashcard_header_row())
-                .push(flashcards)
+                .push(self.quick_add_row())
+                .push(self.folder_stats_bar())
+                .push(
+                    widget::row::with_capacity(2)
+                        .align_items(Alignment::Center)
+                        .spacing(spacing.space_xxs)
+                        .padding([spacing.space_none, spacing.space_xxs])
+                        .push(
+                            widget::text_input(fl!("search-flashcards"), &self.search_query)
+                                .on_input(Message::SearchInput)
+                                .width(Length::Fill),
+                        )
+                        .push(widget::dropdown(
+                            &self.sort_labels,
+                            FLASHCARD_SORTS
+                                .iter()
+                                .position(|sort| *sort == self.list_sort),
+                            Message::SetListSort,
+                        )),
+                );
+
+            let tags = self.folder_tags();
+            if tags.is_empty() == false {
+                column = column.push(self.tag_filter_row(tags));
+            }
+
+            if self.selection_mode {
+                column = column.push(self.selection_row());
+            }
+
+            column = column.push(flashcards);
+            if page_count > 1 {
+                column = column.push(self.list_pagination_row(page, page_count));
+            }
+
+            column
                 .apply(widget::container)
                 .height(Length::Shrink)
                 .apply(widget::scrollable)
                 .height(Length::Fill)
                 .into()
         } else {
-            widget::column::with_capacity(2)
+            widget::column::with_capacity(3)
                 .spacing(spacing.space_xxs)
                 .push(self.flashcard_header_row())
+                .push(self.quick_add_row())
                 .push(
                     widget::Container::new(
                         widget::Text::new(fl!("empty-page")).size(spacing.space_xl),
@@ -372,6 +2496,65 @@ impl Flashcards {
     pub fn create_edit_flashcard_contextpage(&self) -> Element<Message> {
         let spacing = theme::active().cosmic().spacing;
 
+        let upsert_button = match self.new_edit_flashcard.id {
+            Some(_id) => {
+                if self.new_edit_flashcard.front.is_empty() == false
+                    && self.new_edit_flashcard.back.is_empty() == false
+                {
+                    widget::button(
+                        widget::text(fl!("edit"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::Upsert)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                } else {
+                    widget::button(
+                        widget::text(fl!("edit"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                }
+            }
+            None => {
+                if self.new_edit_flashcard.front.is_empty() == false
+                    && self.new_edit_flashcard.back.is_empty() == false
+                {
+                    widget::button(
+                        widget::text(fl!("create"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::Upsert)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                } else {
+                    widget::button(
+                        widget::text(fl!("create"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                }
+            }
+        };
+
+        //The reason the flashcard couldn't be saved is shown above the button
+        let mut upsert_column = widget::column::with_capacity(2).spacing(spacing.space_xxs);
+        if let Some(error) = &self.new_edit_flashcard.error {
+            upsert_column =
+                upsert_column.push(widget::text::body(error.as_str()).width(Length::Fill));
+        }
+        upsert_column = upsert_column.push(upsert_button);
+
         widget::settings::view_column(vec![
             widget::settings::view_section(fl!("flashcard-options"))
                 .add(
@@ -400,64 +2583,30 @@ impl Flashcards {
                     .spacing(spacing.space_xxs)
                     .padding([0, 15, 0, 15]),
                 )
-                .add(match self.new_edit_flashcard.id {
-                    Some(_id) => {
-                        if self.new_edit_flashcard.front.is_empty() == false
-                            && self.new_edit_flashcard.back.is_empty() == false
-                        {
-                            widget::button(
-                                widget::text(fl!("edit"))
-                                    .horizontal_alignment(
-                                        cosmic::iced::alignment::Horizontal::Center,
-                                    )
-                                    .width(Length::Fill),
-                            )
-                            .on_press(Message::Upsert)
-                            .style(theme::Button::Suggested)
-                            .padding([10, 0, 10, 0])
-                            .width(Length::Fill)
-                        } else {
-                            widget::button(
-                                widget::text(fl!("edit"))
-                                    .horizontal_alignment(
-                                        cosmic::iced::alignment::Horizontal::Center,
-                                    )
-                                    .width(Length::Fill),
-                            )
-                            .style(theme::Button::Suggested)
-                            .padding([10, 0, 10, 0])
-                            .width(Length::Fill)
-                        }
-                    }
-                    None => {
-                        if self.new_edit_flashcard.front.is_empty() == false
-                            && self.new_edit_flashcard.back.is_empty() == false
-                        {
-                            widget::button(
-                                widget::text(fl!("create"))
-                                    .horizontal_alignment(
-                                        cosmic::iced::alignment::Horizontal::Center,
-                                    )
-                                    .width(Length::Fill),
-                            )
-                            .on_press(Message::Upsert)
-                            .style(theme::Button::Suggested)
-                            .padding([10, 0, 10, 0])
-                            .width(Length::Fill)
-                        } else {
-                            widget::button(
-                                widget::text(fl!("create"))
-                                    .horizontal_alignment(
-                                        cosmic::iced::alignment::Horizontal::Center,
-                                    )
-                                    .width(Length::Fill),
-                            )
-                            .style(theme::Button::Suggested)
-                            .padding([10, 0, 10, 0])
-                            .width(Length::Fill)
-                        }
-                    }
-                })
+                .add(widget::settings::item(
+                    fl!("flashcard-cloze"),
+                    widget::toggler(
+                        None,
+                        self.new_edit_flashcard.card_type == CardType::Cloze,
+                        Message::ContextPageCloze,
+                    ),
+                ))
+                .add(
+                    widget::column::with_children(vec![
+                        widget::text::body(fl!("flashcard-tags-title")).into(),
+                        widget::text_input(
+                            fl!("flashcard-tags-placeholder"),
+                            &self.new_edit_flashcard.tags,
+                        )
+                        .on_input(Message::ContextPageTagsInput)
+                        .into(),
+                    ])
+                    .spacing(spacing.space_xxs)
+                    .padding([0, 15, 0, 15]),
+                )
+                .add(self.image_selection_row())
+                .add(self.audio_selection_row())
+                .add(upsert_column)
                 .into(),
             widget::settings::view_section(fl!("reset-flashcard-title"))
                 .add(
@@ -478,81 +2627,497 @@ impl Flashcards {
         .into()
     }
 
-    pub fn view_study_page(&self) -> Element<Message> {
+    pub fn view_study_page(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        if self.session_finished() && self.browsing == false {
+            return self.view_session_complete_page();
+        }
+
+        //Every flashcard of the folder has been studied until its due date (or none matches the filter)
+        let Some(flashcard) = &self.currently_studying_flashcard else {
+            return self.view_empty_session_page();
+        };
+
+        //The browse mode always shows the flashcards, whatever the study mode
+        let (study_content, current, total) = if self.browsing {
+            (
+                self.view_browse_page(flashcard),
+                self.browse_index + 1,
+                self.browse_pool().len(),
+            )
+        } else {
+            let content = match self.study_mode {
+                StudyMode::Flashcards => self.view_flashcard_study_page(flashcard),
+                StudyMode::MultipleChoice => self.view_multiple_choice_page(flashcard),
+                StudyMode::Typed => self.view_typed_answer_page(flashcard),
+            };
+            (
+                content,
+                (self.studied_count + 1).min(self.session_size),
+                self.session_size,
+            )
+        };
+
+        let progress = widget::text::body(fl!("study-progress", current = current, total = total))
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center);
+
+        //The study content goes before the other buttons, so the keyboard focus reaches the
+        //flashcard and its grade buttons first
+        let column = widget::column::with_capacity(3)
+            .push(progress)
+            .push(study_content)
+            .spacing(spacing.space_xxs)
+            .align_items(Alignment::Center);
+
+        let browse_label = if self.browsing {
+            fl!("browse-mode")
+        } else {
+            fl!("grade-mode")
+        };
+        let mut buttons = widget::row::with_capacity(7)
+            .spacing(spacing.space_xxs)
+            .push(widget::button::standard(browse_label).on_press(Message::ToggleBrowse));
+        //The flashcards created while studying go to the current folder, the study all due
+        //session doesn't have one
+        if !self.studying_all_due {
+            buttons = buttons.push(
+                widget::button(IconCache::get("add-symbolic", 18))
+                    .name(fl!("add-flashcard"))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Standard)
+                    .on_press(Message::CreateWhileStudying),
+            );
+        }
+        buttons = buttons.push(
+            widget::button(IconCache::get("edit-button-symbolic", 18))
+                .name(fl!("edit-flashcard"))
+                .padding(spacing.space_xxs)
+                .style(theme::Button::Standard)
+                .on_press(Message::EditWhileStudying),
+        );
+        buttons = buttons.push(favorite_button(flashcard));
+        //Only the pages that show a side of the flashcard can flip it
+        if self.browsing || self.study_mode == StudyMode::Flashcards {
+            buttons = buttons.push(
+                widget::button(IconCache::get("object-flip-horizontal-symbolic", 18))
+                    .name(self.studied_side_accessible_name(flashcard))
+                    .description(fl!("flip-flashcard"))
+                    .padding(spacing.space_xxs)
+                    .style(theme::Button::Standard)
+                    .on_press(Message::SwapFlashcardSide),
+            );
+        }
+        if self.grade_history.is_empty() == false && self.browsing == false {
+            buttons = buttons
+                .push(widget::button::standard(fl!("undo-grade")).on_press(Message::UndoLastGrade));
+        }
+        if flashcard.audio_path.is_some() {
+            buttons = buttons
+                .push(widget::button::standard(fl!("play-audio")).on_press(Message::PlayAudio));
+        }
+
+        column.push(buttons).into()
+    }
+
+    /// Says there's nothing to study in the session, with a button back to the flashcard list
+    fn view_empty_session_page(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let reason = match self.study_filter {
+            StudyFilter::AllDue => fl!("no-flashcards-due"),
+            _ => fl!("no-flashcards-filter"),
+        };
+
+        widget::column::with_capacity(3)
+            .push(widget::text::title3(fl!("no-flashcards-to-study")))
+            .push(widget::text::body(reason))
+            .push(
+                widget::button::suggested(fl!("back-to-flashcards"))
+                    .on_press(Message::LeaveStudyPage),
+            )
+            .spacing(spacing.space_s)
+            .align_items(Alignment::Center)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center)
+            .into()
+    }
+
+    /// Shows how the study session went, with a button to start a new one
+    fn view_session_complete_page(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let results = widget::row::with_capacity(3)
+            .push(
+                widget::text::title4(format!(
+                    "{}: {}",
+                    self.study_labels.bad(),
+                    self.session_results.bad
+                ))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center),
+            )
+            .push(
+                widget::text::title4(format!(
+                    "{}: {}",
+                    self.study_labels.ok(),
+                    self.session_results.ok
+                ))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center),
+            )
+            .push(
+                widget::text::title4(format!(
+                    "{}: {}",
+                    self.study_labels.good(),
+                    self.session_results.good
+                ))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center),
+            )
+            .spacing(spacing.space_s)
+            .width(Length::Fill);
+
+        let mut column = widget::column::with_capacity(4)
+            .push(
+                widget::Text::new(fl!("session-complete", studied = self.studied_count))
+                    .size(spacing.space_xl)
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill),
+            )
+            .push(results);
+        if let Some(average) = self.average_response_time() {
+            column = column.push(
+                widget::text::body(fl!(
+                    "session-average-response",
+                    seconds = format!("{:.1}", average.as_secs_f32())
+                ))
+                .horizontal_alignment(Horizontal::Center)
+                .width(Length::Fill),
+            );
+        }
+        //The study all due session says how many flashcards were studied in each folder
+        for (folder, studied) in &self.session_folders {
+            if *studied > 0 {
+                column = column.push(
+                    widget::text::body(fl!(
+                        "session-folder-studied",
+                        folder = folder.as_str(),
+                        studied = studied
+                    ))
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill),
+                );
+            }
+        }
+
+        let column = column
+            .push(
+                widget::row::with_capacity(2)
+                    .push(
+                        widget::button::standard(fl!("undo-grade"))
+                            .on_press(Message::UndoLastGrade),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("study-again")).on_press(Message::StudyAgain),
+                    )
+                    .spacing(spacing.space_xxs),
+            )
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_m)
+            .width(Length::Fill);
+
+        widget::Container::new(column)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(cosmic::iced::alignment::Horizontal::Center)
+            .align_y(cosmic::iced::alignment::Vertical::Center)
+            .into()
+    }
+
+    /// Shows the current side of the flashcard and the buttons to move to the previous or next one,
+    /// without grading them
+    fn view_browse_page(&self, flashcard: &Flashcard) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let navigation_row = widget::row::with_capacity(2)
+            .push(
+                widget::button::standard(fl!("previous-flashcard"))
+                    .on_press(Message::BrowsePrevious)
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::button::standard(fl!("next-flashcard"))
+                    .on_press(Message::BrowseNext)
+                    .width(Length::Fill),
+            )
+            .spacing(spacing.space_s)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .width(Length::Fill);
+
+        widget::column::with_capacity(2)
+            .push(self.flashcard_side_container(flashcard))
+            .push(navigation_row)
+            .spacing(spacing.space_s)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .into()
+    }
+
+    /// The text of the side of the studied flashcard that is shown
+    fn studied_side_text(&self, flashcard: &Flashcard) -> String {
+        match self.currently_studying_flashcard_side {
+            CurrentFlashcardSide::Front => study_question(flashcard),
+            CurrentFlashcardSide::Back => study_back(flashcard),
+        }
+    }
+
+    /// What screen readers announce for the side that is shown, the Markdown markers aren't read
+    fn studied_side_accessible_name(&self, flashcard: &Flashcard) -> String {
+        let text = self.studied_side_text(flashcard);
+        let content = if self.render_markdown {
+            markdown_to_plain_text(&text)
+        } else {
+            text
+        };
+
+        match self.currently_studying_flashcard_side {
+            CurrentFlashcardSide::Front => fl!("flashcard-front-shown", content = content),
+            CurrentFlashcardSide::Back => fl!("flashcard-back-shown", content = content),
+        }
+    }
+
+    /// The current side of the studied flashcard, it's flipped when clicked or tapped.
+    /// It isn't a button so it doesn't get highlighted on hover, the keyboard and screen readers
+    /// use the flip button of the study toolbar (or Space) instead
+    fn flashcard_side_container(&self, flashcard: &Flashcard) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        widget::mouse_area(
+            widget::container(self.flashcard_text(
+                flashcard,
+                &self.studied_side_text(flashcard),
+                spacing.space_xxl,
+            ))
+            .style(theme::Container::ContextDrawer)
+            .padding(spacing.space_xxs)
+            .width(Length::Fill)
+            .height(Length::Fill),
+        )
+        .on_press(Message::SwapFlashcardSide)
+        .into()
+    }
+
+    /// Shows the current side of the flashcard and the buttons to grade it
+    fn view_flashcard_study_page(&self, flashcard: &Flashcard) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let options_row = widget::row::with_capacity(5)
+            .push(self.grade_button(
+                flashcard,
+                fl!("grade-again"),
+                StudyActions::Again,
+                theme::Button::Destructive,
+            ))
+            .push(self.grade_button(
+                flashcard,
+                self.study_labels.bad(),
+                StudyActions::Bad,
+                button_style(false, false, ButtonStyle::BadButton),
+            ))
+            .push(self.grade_button(
+                flashcard,
+                self.study_labels.ok(),
+                StudyActions::Ok,
+                button_style(false, false, ButtonStyle::NeutralButton),
+            ))
+            .push(self.grade_button(
+                flashcard,
+                self.study_labels.good(),
+                StudyActions::Good,
+                button_style(false, false, ButtonStyle::GoodButton),
+            ))
+            .push(self.grade_button(
+                flashcard,
+                fl!("grade-easy"),
+                StudyActions::Easy,
+                theme::Button::Suggested,
+            ))
+            .align_items(cosmic::iced::Alignment::Center)
+            .spacing(spacing.space_s)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .width(Length::Fill);
+
+        let shortcuts_hint = widget::text::caption(fl!(
+            "study-shortcuts-hint",
+            again = fl!("grade-again"),
+            bad = self.study_labels.bad(),
+            ok = self.study_labels.ok(),
+            good = self.study_labels.good(),
+            easy = fl!("grade-easy")
+        ))
+        .width(Length::Fill)
+        .horizontal_alignment(Horizontal::Center);
+
+        let mut study_page = widget::Column::new();
+
+        //Never studied flashcards (status = 0) get a badge, so it's clear they're being learnt, not reviewed
+        if flashcard.status == 0 {
+            study_page = study_page.push(
+                widget::container(widget::text::heading(fl!("new-flashcard-badge")))
+                    .style(theme::Container::Card)
+                    .padding([spacing.space_xxxs, spacing.space_xs]),
+            );
+        }
+
+        study_page
+            .push(self.flashcard_side_container(flashcard))
+            .push(options_row)
+            .push(shortcuts_hint)
+            .spacing(spacing.space_s)
+            .padding([spacing.space_none, spacing.space_xxs])
+            .into()
+    }
+
+    /// Button that grades the studied flashcard with the action
+    fn grade_button(
+        &self,
+        flashcard: &Flashcard,
+        label: String,
+        action: StudyActions,
+        style: theme::Button,
+    ) -> Element<Message> {
+        widget::button(
+            widget::Text::new(label.clone())
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .name(fl!("grade-flashcard", grade = label))
+        .on_press(Message::UpdateFlashcardStatus(flashcard.clone(), action))
+        .style(style)
+        .height(Length::Fixed(60.0))
+        .width(Length::Fill)
+        .into()
+    }
+
+    /// Shows the front of the flashcard and the possible answers as buttons
+    fn view_multiple_choice_page(&self, flashcard: &Flashcard) -> Element<Message> {
         let spacing = theme::active().cosmic().spacing;
 
-        let flashcard_container = widget::container(
-            widget::button(
-                widget::Text::new(match self.currently_studying_flashcard_side {
-                    CurrentFlashcardSide::Front => &self.currently_studying_flashcard.front,
-                    CurrentFlashcardSide::Back => &self.currently_studying_flashcard.back,
-                })
-                .size(spacing.space_xxl)
-                .width(Length::Fill)
-                .height(Length::Fill)
-                .vertical_alignment(Vertical::Center)
-                .horizontal_alignment(Horizontal::Center),
-            )
-            .on_press(Message::SwapFlashcardSide)
-            .style(button_style(false, false, ButtonStyle::NoHover))
-            .height(Length::Fill)
-            .width(Length::Fill),
-        )
+        let flashcard_container = widget::container(self.flashcard_text(
+            flashcard,
+            &study_question(flashcard),
+            spacing.space_xxl,
+        ))
         .style(theme::Container::ContextDrawer)
         .width(Length::Fill)
         .height(Length::Fill);
 
-        let options_row = widget::row::with_capacity(3)
-            .push(
-                widget::button(
-                    widget::Text::new(fl!("bad-status"))
-                        .horizontal_alignment(Horizontal::Center)
-                        .vertical_alignment(Vertical::Center),
-                )
-                .on_press(Message::UpdateFlashcardStatus(
-                    self.currently_studying_flashcard.clone(),
-                    StudyActions::Bad,
-                ))
-                .style(button_style(false, false, ButtonStyle::BadButton))
-                .height(Length::Fixed(60.0))
-                .width(Length::Fill),
-            )
-            .push(
-                widget::button(
-                    widget::Text::new(fl!("ok-status"))
-                        .horizontal_alignment(Horizontal::Center)
-                        .vertical_alignment(Vertical::Center),
-                )
-                .on_press(Message::UpdateFlashcardStatus(
-                    self.currently_studying_flashcard.clone(),
-                    StudyActions::Ok,
-                ))
-                .style(button_style(false, false, ButtonStyle::OkButton))
-                .height(Length::Fixed(60.0))
-                .width(Length::Fill),
-            )
-            .push(
+        let mut choices = widget::column::with_capacity(self.choices.len())
+            .spacing(spacing.space_xxs)
+            .width(Length::Fill);
+
+        let answer = study_answer(flashcard);
+        for (index, choice) in self.choices.iter().enumerate() {
+            let is_correct = *choice == answer;
+
+            //Once answered, the right answer is shown in green and a wrong pick in red
+            let style = match self.selected_choice {
+                Some(_) if is_correct => button_style(false, false, ButtonStyle::GoodButton),
+                Some(selected) if selected == index => {
+                    button_style(false, false, ButtonStyle::BadButton)
+                }
+                _ => theme::Button::Standard,
+            };
+
+            choices = choices.push(
                 widget::button(
-                    widget::Text::new(fl!("good-status"))
+                    widget::Text::new(break_long_words(choice, MAX_WORD_LENGTH))
                         .horizontal_alignment(Horizontal::Center)
-                        .vertical_alignment(Vertical::Center),
+                        .vertical_alignment(Vertical::Center)
+                        .width(Length::Fill),
                 )
-                .on_press(Message::UpdateFlashcardStatus(
-                    self.currently_studying_flashcard.clone(),
-                    StudyActions::Good,
-                ))
-                .style(button_style(false, false, ButtonStyle::GoodButton))
-                .height(Length::Fixed(60.0))
+                .on_press(Message::SelectChoice(index))
+                .style(style)
+                .padding(spacing.space_s)
                 .width(Length::Fill),
-            )
-            .align_items(cosmic::iced::Alignment::Center)
+            );
+        }
+
+        widget::column::with_capacity(2)
+            .push(flashcard_container)
+            .push(choices)
             .spacing(spacing.space_s)
             .padding([spacing.space_none, spacing.space_xxs])
-            .width(Length::Fill);
+            .into()
+    }
+
+    /// Shows the front of the flashcard and an input for the back, once submitted the expected answer is shown
+    fn view_typed_answer_page(&self, flashcard: &Flashcard) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let flashcard_container = widget::container(self.flashcard_text(
+            flashcard,
+            &study_question(flashcard),
+            spacing.space_xxl,
+        ))
+        .style(theme::Container::ContextDrawer)
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+        let mut answer_row = widget::row::with_capacity(2)
+            .align_items(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        match self.typed_answer_result {
+            None => {
+                answer_row = answer_row
+                    .push(
+                        widget::text_input(fl!("typed-answer-placeholder"), &self.typed_answer)
+                            .on_input(Message::TypedAnswerInput)
+                            .on_submit(Message::SubmitTypedAnswer)
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("typed-answer-check"))
+                            .on_press(Message::SubmitTypedAnswer),
+                    );
+            }
+            Some(correct) => {
+                let (result, style) = if correct {
+                    (fl!("typed-answer-correct"), ButtonStyle::GoodButton)
+                } else {
+                    (fl!("typed-answer-incorrect"), ButtonStyle::BadButton)
+                };
+
+                answer_row = answer_row
+                    .push(
+                        widget::button(
+                            widget::Text::new(format!(
+                                "{} {}",
+                                result,
+                                break_long_words(&study_answer(flashcard), MAX_WORD_LENGTH)
+                            ))
+                            .horizontal_alignment(Horizontal::Center)
+                            .width(Length::Fill),
+                        )
+                        .style(button_style(false, false, style))
+                        .padding(spacing.space_s)
+                        .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("typed-answer-next"))
+                            .on_press(Message::GradeTypedAnswer),
+                    );
+            }
+        }
 
-        widget::Column::new()
+        widget::column::with_capacity(2)
             .push(flashcard_container)
-            .push(options_row)
+            .push(answer_row)
             .spacing(spacing.space_s)
             .padding([spacing.space_none, spacing.space_xxs])
             .into()
@@ -561,8 +3126,65 @@ impl Flashcards {
     // The flashcard options context page for this app.
     pub fn flashcard_options_contextpage(&self) -> Element<Message> {
         let spacing = theme::active().cosmic().spacing;
+        let selected_pdf_layout = PDF_LAYOUTS
+            .iter()
+            .position(|layout| *layout == self.options_page_input.pdf_layout);
 
         widget::settings::view_column(vec![
+            widget::settings::view_section(fl!("folder-study-preferences"))
+                .add(widget::settings::item(
+                    fl!("study-mode-title"),
+                    widget::dropdown(
+                        &self.study_mode_labels,
+                        STUDY_MODES.iter().position(|mode| *mode == self.study_mode),
+                        |index| {
+                            Message::OptionsPageInput(OptionsContextPageInputActions::StudyMode(
+                                index,
+                            ))
+                        },
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("study-direction-title"),
+                    widget::dropdown(
+                        &self.study_direction_labels,
+                        STUDY_DIRECTIONS
+                            .iter()
+                            .position(|direction| *direction == self.study_direction),
+                        |index| {
+                            Message::OptionsPageInput(
+                                OptionsContextPageInputActions::StudyDirection(index),
+                            )
+                        },
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("study-filter-title"),
+                    widget::dropdown(
+                        &self.study_filter_labels,
+                        STUDY_FILTERS
+                            .iter()
+                            .position(|filter| *filter == self.study_filter),
+                        |index| {
+                            Message::OptionsPageInput(OptionsContextPageInputActions::StudyFilter(
+                                index,
+                            ))
+                        },
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("study-order-title"),
+                    widget::dropdown(
+                        &self.study_order_labels,
+                        STUDY_ORDERS.iter().position(|order| *order == self.study_order),
+                        |index| {
+                            Message::OptionsPageInput(OptionsContextPageInputActions::StudyOrder(
+                                index,
+                            ))
+                        },
+                    ),
+                ))
+                .into(),
             widget::settings::view_section(fl!("folder-import"))
                 .add(
                     widget::column::with_children(vec![
@@ -642,6 +3264,19 @@ impl Flashcards {
                     },
                 )
                 .into(),
+            widget::settings::view_section(fl!("paste-import-title"))
+                .add(
+                    widget::button(
+                        widget::text(fl!("paste-import-open-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::TogglePasteImportPage)
+                    .style(theme::Button::Standard)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill),
+                )
+                .into(),
             widget::settings::view_section(fl!("import-anki-title"))
                 .add(
                     widget::column()
@@ -670,6 +3305,65 @@ impl Flashcards {
                         .align_items(Alignment::Center),
                 )
                 .into(),
+            widget::settings::view_section(fl!("import-csv-title"))
+                .add(
+                    widget::button(
+                        widget::text(fl!("import-csv-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::OpenCsvFileSelection)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill),
+                )
+                .into(),
+            widget::settings::view_section(fl!("sync-source-title"))
+                .add(widget::text::body(match &self.current_folder_source {
+                    Some(source_path) => source_path.clone(),
+                    None => fl!("sync-source-none"),
+                }))
+                .add(
+                    widget::button(
+                        widget::text(fl!("sync-source-select-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::OpenSyncSourceSelection)
+                    .style(theme::Button::Standard)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill),
+                )
+                .add(widget::settings::item(
+                    fl!("sync-source-delete-missing"),
+                    widget::toggler(
+                        None,
+                        self.sync_delete_missing,
+                        Message::SyncDeleteMissingToggled,
+                    ),
+                ))
+                .add(if self.current_folder_source.is_some() {
+                    widget::button(
+                        widget::text(fl!("sync-source-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::SyncFromSource)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                } else {
+                    widget::button(
+                        widget::text(fl!("sync-source-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill)
+                })
+                .into(),
+            self.find_replace_section(),
             widget::settings::view_section(fl!("reset-folder-flashcards-title"))
                 .add(
                     widget::button(
@@ -695,18 +3389,255 @@ impl Flashcards {
                     .padding([10, 0, 10, 0])
                     .width(Length::Fill),
                 )
-                .into()
+                .into(),
+            widget::settings::view_section(fl!("export-test-sheet-title"))
+                .add(widget::settings::item(
+                    fl!("test-sheet-shuffled"),
+                    widget::toggler(None, self.options_page_input.test_sheet_shuffled, |value| {
+                        Message::OptionsPageInput(
+                            OptionsContextPageInputActions::TestSheetShuffled(value),
+                        )
+                    }),
+                ))
+                .add(widget::settings::item(
+                    fl!("test-sheet-answer-key"),
+                    widget::toggler(
+                        None,
+                        self.options_page_input.test_sheet_answer_key,
+                        |value| {
+                            Message::OptionsPageInput(
+                                OptionsContextPageInputActions::TestSheetAnswerKey(value),
+                            )
+                        },
+                    ),
+                ))
+                .add(
+                    widget::button(
+                        widget::text(fl!("export-test-sheet-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::OpenTestSheetExportDestination)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill),
+                )
+                .into(),
+            widget::settings::view_section(fl!("export-pdf-title"))
+                .add(widget::settings::item(
+                    fl!("pdf-layout"),
+                    widget::dropdown(&self.pdf_layout_labels, selected_pdf_layout, |index| {
+                        Message::OptionsPageInput(OptionsContextPageInputActions::PdfLayout(index))
+                    }),
+                ))
+                .add(
+                    widget::button(
+                        widget::text(fl!("export-pdf-button"))
+                            .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::OpenPdfExportDestination)
+                    .style(theme::Button::Suggested)
+                    .padding([10, 0, 10, 0])
+                    .width(Length::Fill),
+                )
+                .into(),
         ])
         .into()
     }
+
+    /// Context page to paste rows copied from a spreadsheet, a flashcard per `front<TAB>back` row
+    pub fn paste_import_contextpage(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut import_button = widget::button(
+            widget::text(fl!("import-button"))
+                .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .style(theme::Button::Suggested)
+        .padding([10, 0, 10, 0])
+        .width(Length::Fill);
+        if self.paste_import_content.text().trim().is_empty() == false {
+            import_button = import_button.on_press(Message::PasteImport);
+        }
+
+        let mut column = widget::column::with_capacity(3)
+            .push(widget::text::body(fl!("paste-import-description")))
+            .push(
+                text_editor(&self.paste_import_content)
+                    .on_action(Message::PasteImportAction)
+                    .height(Length::Fixed(300.0)),
+            )
+            .push(import_button)
+            .spacing(spacing.space_xxs)
+            .padding([0, 15, 0, 15]);
+        if let Some(summary) = self.paste_import_result {
+            column = column.push(widget::text::body(fl!(
+                "paste-import-result",
+                added = summary.added,
+                skipped = summary.skipped
+            )));
+        }
+
+        let section = widget::settings::view_section(fl!("paste-import-title")).add(column);
+
+        widget::settings::view_column(vec![section.into()]).into()
+    }
+
+    /// Past grades of a flashcard, the most recent first
+    pub fn flashcard_history_contextpage(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let Some(flashcard) = &self.history_flashcard else {
+            return widget::settings::view_column(Vec::new()).into();
+        };
+
+        let mut column = widget::column::with_capacity(4)
+            .spacing(spacing.space_xxs)
+            .padding([0, 15, 0, 15])
+            .push(widget::text::title4(break_long_words(
+                &flashcard.front,
+                MAX_WORD_LENGTH,
+            )))
+            .push(
+                widget::row::with_capacity(2)
+                    .align_items(Alignment::Center)
+                    .spacing(spacing.space_xxs)
+                    .push(
+                        widget::text::body(fl!(
+                            "flashcard-history-reviews",
+                            reviews = self.flashcard_history.len()
+                        ))
+                        .width(Length::Fill),
+                    )
+                    .push(status_badge(flashcard.status)),
+            );
+        if flashcard.response_count > 0 {
+            column = column.push(widget::text::body(fl!(
+                "flashcard-history-average-response",
+                seconds = format!("{:.1}", flashcard.average_response_ms as f32 / 1000.0)
+            )));
+        }
+
+        let mut history_section = widget::settings::view_section(fl!("flashcard-history-grades"));
+        if self.flashcard_history.is_empty() {
+            history_section =
+                history_section.add(widget::text::body(fl!("flashcard-history-empty")));
+        }
+        for review in &self.flashcard_history {
+            history_section = history_section.add(widget::settings::item(
+                review.date.clone(),
+                widget::text::body(self.grade_label(review.grade)),
+            ));
+        }
+
+        let section = widget::settings::view_section(fl!("flashcard-history")).add(column);
+
+        widget::settings::view_column(vec![section.into(), history_section.into()]).into()
+    }
+
+    /// Name of the study button that gives the grade
+    fn grade_label(&self, grade: i32) -> String {
+        match grade {
+            0 => fl!("grade-again"),
+            1 | 2 => self.study_labels.bad(),
+            3 => self.study_labels.ok(),
+            4 => self.study_labels.good(),
+            _ => fl!("grade-easy"),
+        }
+    }
+
+    /// Find and replace of the options page, with how many flashcards would change before applying it
+    fn find_replace_section(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+        let preview = self.find_replace_preview();
+        let selected_sides = REPLACE_SIDES
+            .iter()
+            .position(|sides| *sides == self.options_page_input.replace_sides);
+
+        let mut replace_button = widget::button(
+            widget::text(fl!("find-replace-button"))
+                .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .style(theme::Button::Suggested)
+        .padding([10, 0, 10, 0])
+        .width(Length::Fill);
+        if preview > 0 {
+            replace_button = replace_button.on_press(Message::FindReplace);
+        }
+
+        widget::settings::view_section(fl!("find-replace-title"))
+            .add(
+                widget::column::with_children(vec![
+                    widget::text::body(fl!("find-title")).into(),
+                    widget::text_input(fl!("find-placeholder"), &self.options_page_input.find_text)
+                        .on_input(|value| {
+                            Message::OptionsPageInput(OptionsContextPageInputActions::FindText(
+                                value,
+                            ))
+                        })
+                        .into(),
+                ])
+                .spacing(spacing.space_xxs)
+                .padding([0, 15, 0, 15]),
+            )
+            .add(
+                widget::column::with_children(vec![
+                    widget::text::body(fl!("replace-title")).into(),
+                    widget::text_input(
+                        fl!("replace-placeholder"),
+                        &self.options_page_input.replace_text,
+                    )
+                    .on_input(|value| {
+                        Message::OptionsPageInput(OptionsContextPageInputActions::ReplaceText(
+                            value,
+                        ))
+                    })
+                    .into(),
+                ])
+                .spacing(spacing.space_xxs)
+                .padding([0, 15, 0, 15]),
+            )
+            .add(widget::settings::item(
+                fl!("replace-sides"),
+                widget::dropdown(&self.replace_side_labels, selected_sides, |index| {
+                    Message::OptionsPageInput(OptionsContextPageInputActions::ReplaceSides(index))
+                }),
+            ))
+            .add(widget::settings::item(
+                fl!("replace-case-sensitive"),
+                widget::toggler(
+                    None,
+                    self.options_page_input.replace_case_sensitive,
+                    |value| {
+                        Message::OptionsPageInput(
+                            OptionsContextPageInputActions::ReplaceCaseSensitive(value),
+                        )
+                    },
+                ),
+            ))
+            .add(
+                widget::column::with_capacity(2)
+                    .push(widget::text::body(fl!(
+                        "find-replace-preview",
+                        affected = preview
+                    )))
+                    .push(replace_button)
+                    .spacing(spacing.space_xxs),
+            )
+            .into()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ButtonStyle {
-    NoHover,
     OkButton,
     GoodButton,
     BadButton,
+    /// Colored like the components of the theme, for the grade between Bad and Good
+    NeutralButton,
 }
 
 fn button_appearance(
@@ -738,70 +3669,50 @@ fn button_appearance(
     // appearance.border_radius = cosmic.radius_s().into();
 
     appearance.border_radius = cosmic.radius_xs().into();
-    appearance.icon_color = Some(Color::from(cosmic.on_accent_color()));
     appearance.outline_width = 1.0;
     appearance.border_width = 2.0;
 
-    if style != ButtonStyle::NoHover {
-        appearance.text_color = Some(Color::from(cosmic.on_accent_color()));
-    }
-
-    let custom_bg_color = match style {
-        // Orange
-        ButtonStyle::OkButton => Color {
-            r: 245.0 / 255.0,
-            g: 188.0 / 255.0,
-            b: 66.0 / 255.0,
-            a: 0.75,
-        },
-        // Green
-        ButtonStyle::GoodButton => Color {
-            r: 21.0 / 255.0,
-            g: 191.0 / 255.0,
-            b: 89.0 / 255.0,
-            a: 0.75,
-        },
-        // Red
-        ButtonStyle::BadButton => Color {
-            r: 191.0 / 255.0,
-            g: 57.0 / 255.0,
-            b: 57.0 / 255.0,
-            a: 0.75,
-        },
-        ButtonStyle::NoHover => Color::from(cosmic.bg_color()),
-    };
-
-    let custom_border_color = match style {
-        // Darker Orange
-        ButtonStyle::OkButton => Color {
-            r: 250.0 / 255.0,
-            g: 146.0 / 255.0,
-            b: 12.0 / 255.0,
-            a: 1.0,
-        },
-        // Brighter Green
-        ButtonStyle::GoodButton => Color {
-            r: 10.0 / 255.0,
-            g: 209.0 / 255.0,
-            b: 90.0 / 255.0,
-            a: 1.0,
-        },
-        // Darker Red
-        ButtonStyle::BadButton => Color {
-            r: 107.0 / 255.0,
-            g: 7.0 / 255.0,
-            b: 7.0 / 255.0,
-            a: 1.0,
-        },
-        ButtonStyle::NoHover => Color::from(cosmic.bg_color()),
+    let status = match style {
+        ButtonStyle::BadButton => 1,
+        ButtonStyle::OkButton => 2,
+        ButtonStyle::GoodButton => 3,
+        ButtonStyle::NeutralButton => 0,
     };
 
-    appearance.background = Some(Color::from(custom_bg_color).into());
-    appearance.border_color = Color::from(custom_border_color);
+    let color = status_color(status, theme);
+    appearance.icon_color = Some(status_text_color(status, theme));
+    appearance.text_color = Some(status_text_color(status, theme));
+    appearance.background = Some(Color { a: 0.75, ..color }.into());
+    appearance.border_color = color;
 
     appearance
 }
 
+/// Color of a status (Bad, Ok, Good) taken from the success, warning and destructive
+/// colors of the theme, so it follows the light and dark variants
+fn status_color(status: i32, theme: &theme::Theme) -> Color {
+    let cosmic = theme.cosmic();
+
+    match status {
+        1 => Color::from(cosmic.destructive_color()),
+        2 => Color::from(cosmic.warning_color()),
+        3 => Color::from(cosmic.success_color()),
+        _ => Color::from(cosmic.bg_component_color()),
+    }
+}
+
+/// Color of the text drawn over the color of a status
+fn status_text_color(status: i32, theme: &theme::Theme) -> Color {
+    let cosmic = theme.cosmic();
+
+    match status {
+        1 => Color::from(cosmic.destructive.on),
+        2 => Color::from(cosmic.warning.on),
+        3 => Color::from(cosmic.success.on),
+        _ => Color::from(cosmic.on_bg_component_color()),
+    }
+}
+
 fn button_style(selected: bool, accent: bool, style: ButtonStyle) -> theme::Button {
     theme::Button::Custom {
         active: Box::new(move |focused, theme| {
@@ -816,3 +3727,144 @@ fn button_style(selected: bool, accent: bool, style: ButtonStyle) -> theme::Butt
         }),
     }
 }
+
+/// Badge with the name of the status of a flashcard in its color (like the buttons used to
+/// grade it), a button without an action so it can reuse those styles
+fn status_badge<'a>(status: i32) -> Element<'a, Message> {
+    let spacing = theme::active().cosmic().spacing;
+
+    let (label, style) = match status {
+        1 => (
+            fl!("bad-status"),
+            button_style(false, false, ButtonStyle::BadButton),
+        ),
+        2 => (
+            fl!("ok-status"),
+            button_style(false, false, ButtonStyle::OkButton),
+        ),
+        3 => (
+            fl!("good-status"),
+            button_style(false, false, ButtonStyle::GoodButton),
+        ),
+        _ => (fl!("new-flashcard-badge"), theme::Button::Standard),
+    };
+
+    widget::button(widget::text::caption(label))
+        .padding([spacing.space_xxxs, spacing.space_xs])
+        .style(style)
+        .into()
+}
+
+/// What the flashcard asks while studying, cloze flashcards hide their answers
+fn study_question(flashcard: &Flashcard) -> String {
+    match flashcard.card_type {
+        CardType::Basic => flashcard.front.clone(),
+        CardType::Cloze => cloze_text(&flashcard.front, false),
+    }
+}
+
+/// The answer the multiple choice and typed modes expect, every answer of a cloze flashcard
+fn study_answer(flashcard: &Flashcard) -> String {
+    match flashcard.card_type {
+        CardType::Basic => flashcard.back.clone(),
+        CardType::Cloze => cloze_answers(&flashcard.front).join(", "),
+    }
+}
+
+/// The flipped side of the flashcard, cloze flashcards show their front with the answers
+/// revealed, followed by the back (if any) as an extra note
+fn study_back(flashcard: &Flashcard) -> String {
+    match flashcard.card_type {
+        CardType::Basic => flashcard.back.clone(),
+        CardType::Cloze if flashcard.back.trim().is_empty() => cloze_text(&flashcard.front, true),
+        CardType::Cloze => format!(
+            "{}\n\n{}",
+            cloze_text(&flashcard.front, true),
+            flashcard.back
+        ),
+    }
+}
+
+/// Star that marks the flashcard as a favorite, filled while it is one
+fn favorite_button<'a>(flashcard: &Flashcard) -> Element<'a, Message> {
+    let spacing = theme::active().cosmic().spacing;
+
+    let (icon, name) = if flashcard.favorite {
+        ("starred-symbolic", fl!("remove-favorite"))
+    } else {
+        ("non-starred-symbolic", fl!("add-favorite"))
+    };
+
+    let mut button = widget::button(IconCache::get(icon, 18))
+        .name(name)
+        .padding(spacing.space_xxs)
+        .style(theme::Button::Standard);
+    if let Some(flashcard_id) = flashcard.id {
+        button = button.on_press(Message::ToggleFavorite(flashcard_id));
+    }
+
+    button.into()
+}
+
+/// The image of a flashcard, a placeholder icon is shown if the file is gone
+fn flashcard_image<'a>(image_path: &str, height: f32) -> Element<'a, Message> {
+    if std::path::Path::new(image_path).exists() {
+        widget::image(widget::image::Handle::from_path(image_path))
+            .height(Length::Fixed(height))
+            .into()
+    } else {
+        IconCache::get("image-missing-symbolic", height as u16).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::core::icon_cache::{IconCache, ICON_CACHE};
+
+    fn folder(size: usize) -> Flashcards {
+        let mut flashcards = Flashcards::new();
+        flashcards.flashcards = (0..size)
+            .map(|index| {
+                let mut flashcard =
+                    Flashcard::new(format!("front {}", index), format!("back {}", index));
+                flashcard.id = Some(index as i32);
+                flashcard
+            })
+            .collect();
+        flashcards
+    }
+
+    /// Average time to build the view of the flashcard list
+    fn view_time(flashcards: &Flashcards, runs: u32) -> Duration {
+        let start = Instant::now();
+        for _ in 0..runs {
+            drop(flashcards.view());
+        }
+        start.elapsed() / runs
+    }
+
+    /// Benchmark of the flashcard list of a big folder: only the rows of a page are built, so it
+    /// doesn't take much longer than a folder of a single page. It's timing based, run it with
+    /// `cargo test --release -- --ignored --nocapture big_folder`
+    #[test]
+    #[ignore]
+    fn big_folder_list_builds_a_single_page() {
+        ICON_CACHE.get_or_init(|| Mutex::new(IconCache::new()));
+        let (small, big) = (folder(FLASHCARDS_PAGE_SIZE), folder(5_000));
+
+        //Warm up
+        view_time(&small, 5);
+        let small_time = view_time(&small, 20);
+        let big_time = view_time(&big, 20);
+        println!(
+            "{} flashcards: {:?}, 5000 flashcards: {:?}",
+            FLASHCARDS_PAGE_SIZE, small_time, big_time
+        );
+
+        //Building every row would take around 50 times as long
+        assert!(big_time < small_time * 5);
+    }
+}
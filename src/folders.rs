@@ -1,47 +1,61 @@
 use cosmic::{
-    iced::{
-        alignment::{Horizontal, Vertical},
-        Alignment, Length,
-    },
-    theme, widget, Apply, Element,
+    iced::{alignment::Horizontal, Alignment, Color, Length},
+    theme,
+    widget::{self, text_editor},
+    Apply, Element,
 };
 
-use crate::{core::icon_cache::IconCache, fl, models::Folder};
+use crate::{
+    core::icon_cache::IconCache,
+    fl,
+    models::{Folder, MoveDirection},
+    utils::break_long_words,
+};
+
+/// Max length of a word before it's forced to wrap
+const MAX_WORD_LENGTH: usize = 20;
 
 pub struct Folders {
     pub current_studyset_id: Option<i32>,
+    /// Accent color of the current studyset (as hex), None if it has the default styling
+    pub current_studyset_color: Option<String>,
     pub folders: Vec<Folder>,
-    pub new_folder: NewFolderState,
+    /// Folder being renamed inline in its row
+    pub renaming_folder: Option<RenameFolderState>,
+    pub rename_input_id: widget::Id,
+    /// Outline of the folders to create at once, a folder per line
+    outline_content: text_editor::Content,
+    /// Folders created from the last outline
+    outline_result: Option<usize>,
 }
 
-pub struct NewFolderState {
-    id: Option<i32>,
+pub struct RenameFolderState {
+    id: i32,
     name: String,
 }
 
-impl NewFolderState {
-    pub fn new() -> NewFolderState {
-        NewFolderState {
-            id: None,
-            name: String::new(),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 pub enum Message {
     OpenCreateFolderDialog,
-    ToggleEditContextPage(Option<Folder>),
+    ToggleOutlinePage,
+    OutlineAction(text_editor::Action),
+    CreateFromOutline,
+    //The usize is the number of folders created
+    CreatedFromOutline(usize),
+    StartRename(Folder),
+    CancelRename,
 
     LoadFolders,
-    Upsert,
+    Rename,
     Delete(Option<i32>),
+    Move(i32, MoveDirection),
+    //The i32 is the id of the folder merged into another one
+    Merge(i32),
 
     Upserted,
     SetFolders(Vec<Folder>),
-    LoadedSingle(Folder),
     OpenFolder(i32),
-    NewFolderNameInput(String),
+    RenameFolderInput(String),
 }
 
 pub enum Command {
@@ -49,18 +63,30 @@ pub enum Command {
     LoadFolders(i32),
     //The i32 is the Folder Id
     OpenFolder(i32),
-    UpsertFolder(Folder),
+    //The i32 is the Folder Id
+    RenameFolder(i32, String),
     OpenCreateFolderDialog,
-    ToggleEditContextPage(Option<Folder>),
+    ToggleOutlinePage,
+    //The i32 is the Studyset Id
+    CreateFolders(i32, Vec<String>),
+    FocusRenameInput,
     DeleteFolder(Option<i32>),
+    //The i32 is the Folder Id
+    MoveFolder(i32, MoveDirection),
+    //The i32 is the id of the folder merged into another one
+    PickMergeTarget(i32),
 }
 
 impl Folders {
     pub fn new() -> Self {
         Self {
             current_studyset_id: None,
+            current_studyset_color: None,
             folders: Vec::new(),
-            new_folder: NewFolderState::new(),
+            renaming_folder: None,
+            rename_input_id: widget::Id::unique(),
+            outline_content: text_editor::Content::new(),
+            outline_result: None,
         }
     }
 
@@ -69,36 +95,65 @@ impl Folders {
 
         match message {
             Message::OpenCreateFolderDialog => commands.push(Command::OpenCreateFolderDialog),
-            Message::Upsert => commands.push(Command::UpsertFolder(Folder {
-                id: self.new_folder.id,
-                name: self.new_folder.name.to_string(),
-                flashcards: Vec::new(),
-            })),
+            Message::ToggleOutlinePage => {
+                self.outline_result = None;
+                commands.push(Command::ToggleOutlinePage)
+            }
+            Message::OutlineAction(action) => {
+                self.outline_result = None;
+                self.outline_content.perform(action);
+            }
+            Message::CreateFromOutline => {
+                if let Some(set_id) = self.current_studyset_id {
+                    let names = self
+                        .outline_content
+                        .text()
+                        .lines()
+                        .map(String::from)
+                        .collect();
+                    commands.push(Command::CreateFolders(set_id, names));
+                }
+            }
+            Message::CreatedFromOutline(created) => {
+                self.outline_result = Some(created);
+                self.outline_content = text_editor::Content::new();
+                commands.extend(self.update(Message::LoadFolders));
+            }
+            Message::StartRename(folder) => {
+                if let Some(id) = folder.id {
+                    self.renaming_folder = Some(RenameFolderState {
+                        id,
+                        name: folder.name,
+                    });
+                    commands.push(Command::FocusRenameInput);
+                }
+            }
+            Message::CancelRename => self.renaming_folder = None,
+            Message::Rename => {
+                if let Some(folder) = &self.renaming_folder {
+                    commands.push(Command::RenameFolder(folder.id, folder.name.clone()));
+                }
+            }
             Message::Upserted => {
-                self.new_folder = NewFolderState::new();
+                self.renaming_folder = None;
                 commands.push(Command::LoadFolders(self.current_studyset_id.unwrap()))
             }
-            Message::LoadedSingle(folder) => {
-                self.new_folder = NewFolderState {
-                    id: folder.id,
-                    name: folder.name,
-                };
-            }
             Message::LoadFolders => match self.current_studyset_id {
                 Some(set_id) => commands.push(Command::LoadFolders(set_id)),
                 None => self.current_studyset_id = None,
             },
             Message::SetFolders(folders) => self.folders = folders,
-            Message::NewFolderNameInput(value) => self.new_folder.name = value,
-            Message::OpenFolder(id) => commands.push(Command::OpenFolder(id)),
-            Message::ToggleEditContextPage(folder) => {
-                if folder.is_none() {
-                    self.new_folder = NewFolderState::new();
+            Message::RenameFolderInput(value) => {
+                if let Some(folder) = &mut self.renaming_folder {
+                    folder.name = value;
                 }
-
-                commands.push(Command::ToggleEditContextPage(folder))
             }
+            Message::OpenFolder(id) => commands.push(Command::OpenFolder(id)),
             Message::Delete(folder_id) => commands.push(Command::DeleteFolder(folder_id)),
+            Message::Move(folder_id, direction) => {
+                commands.push(Command::MoveFolder(folder_id, direction))
+            }
+            Message::Merge(folder_id) => commands.push(Command::PickMergeTarget(folder_id)),
         }
         commands
     }
@@ -112,15 +167,63 @@ impl Folders {
             .padding(spacing.space_xxs)
             .on_press(Message::OpenCreateFolderDialog);
 
-        widget::row::with_capacity(2)
+        let outline_button = widget::button(widget::text(fl!("folder-outline")))
+            .style(theme::Button::Standard)
+            .padding(spacing.space_xxs)
+            .on_press(Message::ToggleOutlinePage);
+
+        let mut row = widget::row::with_capacity(4)
             .align_items(cosmic::iced::Alignment::Center)
             .spacing(spacing.space_s)
-            .padding([spacing.space_none, spacing.space_xxs])
-            .push(widget::text::title3(fl!("folders")).width(Length::Fill))
+            .padding([spacing.space_none, spacing.space_xxs]);
+        if let Some(color) = &self.current_studyset_color {
+            row = row.push(color_swatch(color, false));
+        }
+
+        row.push(widget::text::title3(fl!("folders")).width(Length::Fill))
+            .push(outline_button)
             .push(new_folder_button)
             .into()
     }
 
+    /// Context page to create many folders at once, a folder per line of the outline
+    pub fn outline_contextpage(&self) -> Element<Message> {
+        let spacing = theme::active().cosmic().spacing;
+
+        let mut create_button = widget::button(
+            widget::text(fl!("create"))
+                .horizontal_alignment(Horizontal::Center)
+                .width(Length::Fill),
+        )
+        .style(theme::Button::Suggested)
+        .padding([10, 0, 10, 0])
+        .width(Length::Fill);
+        if self.outline_content.text().trim().is_empty() == false {
+            create_button = create_button.on_press(Message::CreateFromOutline);
+        }
+
+        let mut column = widget::column::with_capacity(4)
+            .push(widget::text::body(fl!("folder-outline-description")))
+            .push(
+                text_editor(&self.outline_content)
+                    .on_action(Message::OutlineAction)
+                    .height(Length::Fixed(300.0)),
+            )
+            .push(create_button)
+            .spacing(spacing.space_xxs)
+            .padding([0, 15, 0, 15]);
+        if let Some(created) = self.outline_result {
+            column = column.push(widget::text::body(fl!(
+                "folder-outline-result",
+                created = created
+            )));
+        }
+
+        let section = widget::settings::view_section(fl!("folder-outline-title")).add(column);
+
+        widget::settings::view_column(vec![section.into()]).into()
+    }
+
     pub fn view(&self) -> Element<Message> {
         let spacing = theme::active().cosmic().spacing;
 
@@ -131,11 +234,23 @@ impl Folders {
                     .spacing(spacing.space_xxxs)
                     .padding([spacing.space_none, spacing.space_xxs]);
 
-                for folder in &self.folders {
-                    let edit_button = widget::button(IconCache::get("edit-button-symbolic", 18))
-                        .padding(spacing.space_xxs)
-                        .style(theme::Button::Standard)
-                        .on_press(Message::ToggleEditContextPage(Some(folder.clone())));
+                for (index, folder) in self.folders.iter().enumerate() {
+                    let renaming = self
+                        .renaming_folder
+                        .as_ref()
+                        .filter(|renaming| folder.id == Some(renaming.id));
+
+                    //While renaming, the edit button saves the new name
+                    let edit_button = match renaming {
+                        Some(_) => widget::button(IconCache::get("edit-button-symbolic", 18))
+                            .padding(spacing.space_xxs)
+                            .style(theme::Button::Suggested)
+                            .on_press(Message::Rename),
+                        None => widget::button(IconCache::get("edit-button-symbolic", 18))
+                            .padding(spacing.space_xxs)
+                            .style(theme::Button::Standard)
+                            .on_press(Message::StartRename(folder.clone())),
+                    };
 
                     let open_button = widget::button(IconCache::get("folder-open-symbolic", 18))
                         .padding(spacing.space_xxs)
@@ -143,23 +258,74 @@ impl Folders {
                         .width(Length::Shrink)
                         .on_press(Message::OpenFolder(folder.id.unwrap()));
 
-                    let delete_button =
-                        widget::button(IconCache::get("user-trash-full-symbolic", 18))
+                    //While renaming, the delete button cancels the rename instead
+                    let delete_button = match renaming {
+                        Some(_) => widget::button(IconCache::get("window-close-symbolic", 18))
+                            .padding(spacing.space_xxs)
+                            .style(theme::Button::Standard)
+                            .on_press(Message::CancelRename),
+                        None => widget::button(IconCache::get("user-trash-full-symbolic", 18))
                             .padding(spacing.space_xxs)
                             .style(theme::Button::Destructive)
-                            .on_press(Message::Delete(folder.id));
+                            .on_press(Message::Delete(folder.id)),
+                    };
+
+                    let folder_name: Element<Message> = match renaming {
+                        Some(renaming) => widget::text_input(fl!("folder-name"), &renaming.name)
+                            .id(self.rename_input_id.clone())
+                            .on_input(Message::RenameFolderInput)
+                            .on_submit(Message::Rename)
+                            .width(Length::Fill)
+                            .into(),
+                        None => widget::column::with_capacity(2)
+                            .push(widget::text(break_long_words(
+                                &folder.name,
+                                MAX_WORD_LENGTH,
+                            )))
+                            .push(widget::text::caption(fl!(
+                                "flashcard-count",
+                                count = folder.flashcard_count
+                            )))
+                            .width(Length::Fill)
+                            .into(),
+                    };
+
+                    //The first folder can't go up and the last one can't go down
+                    let mut move_up_button = widget::button(IconCache::get("go-up-symbolic", 18))
+                        .padding(spacing.space_xxs)
+                        .style(theme::Button::Standard);
+                    let mut move_down_button =
+                        widget::button(IconCache::get("go-down-symbolic", 18))
+                            .padding(spacing.space_xxs)
+                            .style(theme::Button::Standard);
+                    if let Some(folder_id) = folder.id {
+                        if index > 0 {
+                            move_up_button = move_up_button
+                                .on_press(Message::Move(folder_id, MoveDirection::Up));
+                        }
+                        if index + 1 < self.folders.len() {
+                            move_down_button = move_down_button
+                                .on_press(Message::Move(folder_id, MoveDirection::Down));
+                        }
+                    }
 
-                    let folder_name = widget::text(folder.name.clone())
-                        .vertical_alignment(Vertical::Center)
-                        .horizontal_alignment(Horizontal::Left)
-                        .width(Length::Fill);
+                    //A folder can only be merged if there's another one to merge it into
+                    let mut merge_button = widget::button(IconCache::get("go-jump-symbolic", 18))
+                        .padding(spacing.space_xxs)
+                        .style(theme::Button::Standard);
+                    if let (Some(folder_id), true) = (folder.id, self.folders.len() > 1) {
+                        merge_button = merge_button.on_press(Message::Merge(folder_id));
+                    }
 
-                    let row = widget::row::with_capacity(2)
+                    let row = widget::row::with_capacity(7)
                         .align_items(Alignment::Center)
                         .spacing(spacing.space_xxs)
                         .padding([spacing.space_xxxs, spacing.space_xxs])
                         .push(open_button)
                         .push(folder_name)
+                        .push(move_up_button)
+                        .push(move_down_button)
+                        .push(merge_button)
                         .push(delete_button)
                         .push(edit_button);
 
@@ -215,43 +381,43 @@ impl Folders {
                 .into()
         }
     }
+}
 
-    /// The edit folder context page for this app.
-    pub fn edit_folder_contextpage(&self) -> Element<Message> {
-        let spacing = theme::active().cosmic().spacing;
-
-        widget::settings::view_column(vec![widget::settings::view_section(fl!("folder-details"))
-            .add(
-                widget::column::with_children(vec![
-                    widget::text::body(fl!("folder-name")).into(),
-                    widget::text_input(fl!("folder-name"), &self.new_folder.name)
-                        .on_input(Message::NewFolderNameInput)
-                        .into(),
-                ])
-                .spacing(spacing.space_xxs)
-                .padding([0, 15, 0, 15]),
-            )
-            .add(if self.new_folder.name.is_empty() == false {
-                widget::button(
-                    widget::text(fl!("edit"))
-                        .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
-                        .width(Length::Fill),
-                )
-                .on_press(Message::Upsert)
-                .style(theme::Button::Suggested)
-                .padding([10, 0, 10, 0])
-                .width(Length::Fill)
-            } else {
-                widget::button(
-                    widget::text(fl!("edit"))
-                        .horizontal_alignment(cosmic::iced::alignment::Horizontal::Center)
-                        .width(Length::Fill),
-                )
-                .style(theme::Button::Suggested)
-                .padding([10, 0, 10, 0])
-                .width(Length::Fill)
-            })
-            .into()])
-        .into()
+/// Parses a `#rrggbb` color, None if it isn't valid
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
     }
+
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A square filled with the accent color of a studyset, a button so it can be used in the
+/// color picker too (selected ones get a border in the accent color of the theme)
+pub fn color_swatch<'a, M: 'a>(hex: &str, selected: bool) -> widget::Button<'a, M> {
+    let color = parse_hex_color(hex).unwrap_or(Color::TRANSPARENT);
+    let appearance = move |theme: &theme::Theme| {
+        let cosmic = theme.cosmic();
+        let mut appearance = widget::button::Appearance::new();
+        appearance.background = Some(color.into());
+        appearance.border_radius = cosmic.radius_xs().into();
+        appearance.border_width = 2.0;
+        appearance.border_color = if selected {
+            Color::from(cosmic.accent_color())
+        } else {
+            color
+        };
+        appearance
+    };
+
+    widget::button(widget::Space::new(Length::Fixed(18.0), Length::Fixed(18.0)))
+        .padding(0)
+        .style(theme::Button::Custom {
+            active: Box::new(move |_focused, theme| appearance(theme)),
+            disabled: Box::new(move |theme| appearance(theme)),
+            hovered: Box::new(move |_focused, theme| appearance(theme)),
+            pressed: Box::new(move |_focused, theme| appearance(theme)),
+        })
 }
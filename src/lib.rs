@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The flashcards, studysets and folders of Oboete without the interface: the database, the
+//! schedulers, the imports and exports and the sync. Nothing in here depends on `cosmic`, so it
+//! can be used from tests or other frontends, the app is built on top of it.
+
+pub mod core {
+    pub mod anki;
+    pub mod api;
+    pub mod database;
+    pub mod import;
+    pub mod math;
+    pub mod migrations;
+    pub mod pdf;
+    pub mod reminders;
+    pub mod scheduler;
+    pub mod sync;
+}
+pub mod models;
+pub mod utils;
@@ -2,25 +2,30 @@
 
 use core::localization;
 
-use app::Oboete;
+use app::{Flags, Oboete};
+use core::config::OboeteConfig;
 use i18n_embed::DesktopLanguageRequester;
+use oboete::{models, utils};
+mod all_flashcards;
 mod app;
 mod core;
 mod flashcards;
 mod folders;
-mod models;
-mod utils;
 
 /// The `cosmic::app::run()` function is the starting point of your application.
 /// It takes two arguments:
 /// - `settings` is a structure that contains everything relevant with your app's configuration, such as antialiasing, themes, icons, etc...
-/// - `()` is the flags that your app needs to use before it starts.
-///  If your app does not need any flags, you can pass in `()`.
+/// - `flags` is the data that your app needs to use before it starts (the config of the app).
 fn main() -> cosmic::iced::Result {
     init_localizer();
 
-    let settings = core::settings::init();
-    cosmic::app::run::<Oboete>(settings, ())
+    let config = OboeteConfig::config();
+    let settings = core::settings::init(&config);
+    let flags = Flags {
+        config_handler: OboeteConfig::config_handler(),
+        config,
+    };
+    cosmic::app::run::<Oboete>(settings, flags)
 }
 
 fn init_localizer() {
@@ -1,28 +1,143 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::core::scheduler::{self, DEFAULT_EASE_FACTOR, LEITNER_FIRST_BOX};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Flashcard {
     pub id: Option<i32>,
     pub front: String,
     pub back: String,
     pub status: i32,
+    /// SM-2 ease factor, how fast the interval grows
+    pub ease_factor: f64,
+    /// Days until the flashcard has to be studied again
+    pub interval: i32,
+    /// Times in a row the flashcard has been answered correctly
+    pub repetitions: i32,
+    /// Unix timestamp from which the flashcard has to be studied again
+    pub due_date: i64,
+    /// Box of the Leitner system the flashcard is in, only used by the studysets scheduled with it
+    #[serde(default = "first_leitner_box")]
+    pub leitner_box: i32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Image shown above the text, a path inside of the data directory of the app
+    #[serde(default)]
+    pub image_path: Option<String>,
+    /// Audio clip that can be played while studying, a path inside of the data directory of the app
+    #[serde(default)]
+    pub audio_path: Option<String>,
+    /// Unix timestamp of when the flashcard was created, 0 for the ones created before it was saved
+    #[serde(default)]
+    pub created_at: i64,
+    /// Average time (in milliseconds) it takes to answer the flashcard while studying
+    #[serde(default)]
+    pub average_response_ms: i64,
+    /// Times the flashcard has been answered since its response time is tracked
+    #[serde(default)]
+    pub response_count: i32,
+    /// Starred by the user, it can be studied on its own with the favorites filter
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub card_type: CardType,
+    /// Times in a row the flashcard has been graded Good or Easy, enough of them make it mastered
+    #[serde(default)]
+    pub good_streak: i32,
 }
 
 impl Flashcard {
-    pub fn new_error_variant() -> Flashcard {
+    pub fn new(front: String, back: String) -> Flashcard {
         Flashcard {
             id: None,
-            front: String::from("Error"),
-            back: String::from("Error"),
+            front,
+            back,
             status: 0,
+            ease_factor: DEFAULT_EASE_FACTOR,
+            interval: 0,
+            repetitions: 0,
+            due_date: 0,
+            leitner_box: LEITNER_FIRST_BOX,
+            tags: Vec::new(),
+            image_path: None,
+            audio_path: None,
+            created_at: 0,
+            average_response_ms: 0,
+            response_count: 0,
+            favorite: false,
+            card_type: CardType::Basic,
+            good_streak: 0,
         }
     }
+
+    /// Adds a response time to the average of the flashcard
+    pub fn record_response(&mut self, response_ms: i64) {
+        let total = self.average_response_ms * self.response_count as i64 + response_ms;
+        self.response_count += 1;
+        self.average_response_ms = total / self.response_count as i64;
+    }
 }
 
-#[derive(Debug, Clone)]
+fn first_leitner_box() -> i32 {
+    LEITNER_FIRST_BOX
+}
+
+/// How a flashcard is studied
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CardType {
+    /// The front is the question and the back its answer
+    #[default]
+    Basic,
+    /// The answers are the parts of the front between {{ and }}, they're hidden until it's flipped
+    Cloze,
+}
+
+impl CardType {
+    /// Value saved in the card_type column of the database
+    pub fn to_db(self) -> i32 {
+        match self {
+            CardType::Basic => 0,
+            CardType::Cloze => 1,
+        }
+    }
+
+    pub fn from_db(value: i32) -> CardType {
+        match value {
+            1 => CardType::Cloze,
+            _ => CardType::Basic,
+        }
+    }
+}
+
+/// Icons that can be picked for a studyset, the first one is the default
+pub const STUDYSET_ICONS: [&str; 8] = [
+    "folder-symbolic",
+    "accessories-dictionary-symbolic",
+    "preferences-desktop-locale-symbolic",
+    "applications-science-symbolic",
+    "applications-engineering-symbolic",
+    "applications-graphics-symbolic",
+    "audio-x-generic-symbolic",
+    "emblem-favorite-symbolic",
+];
+
+/// Accent colors that can be picked for a studyset (as hex), it has none by default
+pub const STUDYSET_COLORS: [&str; 6] = [
+    "#e01b24", "#ff7800", "#f6d32d", "#33d17a", "#3584e4", "#9141ac",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct StudySet {
     pub id: Option<i32>,
     pub name: String,
-    #[allow(dead_code)]
     pub folders: Vec<Folder>,
+    pub icon: String,
+    /// Accent color as hex, None keeps the default styling
+    #[serde(default)]
+    pub color: Option<String>,
 }
 
 impl StudySet {
@@ -31,16 +146,31 @@ impl StudySet {
             id: None,
             name,
             folders: Vec::new(),
+            icon: String::from(STUDYSET_ICONS[0]),
+            color: None,
         }
     }
+
+    /// Returns the icon of the studyset, unknown icons fall back to the default one
+    pub fn icon_name(&self) -> &'static str {
+        STUDYSET_ICONS
+            .iter()
+            .find(|icon| **icon == self.icon)
+            .unwrap_or(&STUDYSET_ICONS[0])
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Folder {
     pub id: Option<i32>,
     pub name: String,
-    #[allow(dead_code)]
     pub flashcards: Vec<Flashcard>,
+    /// File the folder flashcards can be synced from
+    pub source_path: Option<String>,
+    /// Flashcards of the folder, only counted when the folders of a studyset are listed
+    #[serde(skip)]
+    pub flashcard_count: usize,
 }
 
 impl Folder {
@@ -49,6 +179,284 @@ impl Folder {
             id: None,
             name,
             flashcards: Vec::new(),
+            source_path: None,
+            flashcard_count: 0,
+        }
+    }
+}
+
+/// Version of the export documents, raised whenever their format changes.
+/// Version 0 are the documents written before they had one
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Document written by the studyset exports, described in info/EXPORT_FORMAT.md
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExportDocument {
+    /// Version of the format the document was written with
+    pub schema_version: u32,
+    pub studysets: Vec<StudySet>,
+}
+
+impl ExportDocument {
+    pub fn new(studysets: Vec<StudySet>) -> ExportDocument {
+        ExportDocument {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            studysets,
+        }
+    }
+}
+
+/// How the flashcards are studied
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StudyMode {
+    #[default]
+    Flashcards,
+    MultipleChoice,
+    Typed,
+}
+
+impl StudyMode {
+    /// The option after this one, going back to the first after the last
+    pub fn next(&self) -> StudyMode {
+        match self {
+            StudyMode::Flashcards => StudyMode::MultipleChoice,
+            StudyMode::MultipleChoice => StudyMode::Typed,
+            StudyMode::Typed => StudyMode::Flashcards,
+        }
+    }
+
+    /// Value saved in the study preferences of the folder
+    pub fn to_db(self) -> i32 {
+        match self {
+            StudyMode::Flashcards => 0,
+            StudyMode::MultipleChoice => 1,
+            StudyMode::Typed => 2,
+        }
+    }
+
+    pub fn from_db(value: i32) -> StudyMode {
+        match value {
+            1 => StudyMode::MultipleChoice,
+            2 => StudyMode::Typed,
+            _ => StudyMode::Flashcards,
         }
     }
 }
+
+/// Side each flashcard is shown from when it's studied
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StudyDirection {
+    #[default]
+    FrontFirst,
+    BackFirst,
+    Random,
+}
+
+impl StudyDirection {
+    /// The option after this one, going back to the first after the last
+    pub fn next(&self) -> StudyDirection {
+        match self {
+            StudyDirection::FrontFirst => StudyDirection::BackFirst,
+            StudyDirection::BackFirst => StudyDirection::Random,
+            StudyDirection::Random => StudyDirection::FrontFirst,
+        }
+    }
+
+    /// Value saved in the study preferences of the folder
+    pub fn to_db(self) -> i32 {
+        match self {
+            StudyDirection::FrontFirst => 0,
+            StudyDirection::BackFirst => 1,
+            StudyDirection::Random => 2,
+        }
+    }
+
+    pub fn from_db(value: i32) -> StudyDirection {
+        match value {
+            1 => StudyDirection::BackFirst,
+            2 => StudyDirection::Random,
+            _ => StudyDirection::FrontFirst,
+        }
+    }
+}
+
+/// Flashcards of the folder that are studied in a session
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StudyFilter {
+    #[default]
+    AllDue,
+    OnlyBad,
+    Unseen,
+    /// The bad and the never studied flashcards
+    Mixed,
+    Favorites,
+}
+
+impl StudyFilter {
+    /// The option after this one, going back to the first after the last
+    pub fn next(&self) -> StudyFilter {
+        match self {
+            StudyFilter::AllDue => StudyFilter::OnlyBad,
+            StudyFilter::OnlyBad => StudyFilter::Unseen,
+            StudyFilter::Unseen => StudyFilter::Mixed,
+            StudyFilter::Mixed => StudyFilter::Favorites,
+            StudyFilter::Favorites => StudyFilter::AllDue,
+        }
+    }
+
+    /// Value saved in the study preferences of the folder
+    pub fn to_db(self) -> i32 {
+        match self {
+            StudyFilter::AllDue => 0,
+            StudyFilter::OnlyBad => 1,
+            StudyFilter::Unseen => 2,
+            StudyFilter::Mixed => 3,
+            StudyFilter::Favorites => 4,
+        }
+    }
+
+    pub fn from_db(value: i32) -> StudyFilter {
+        match value {
+            1 => StudyFilter::OnlyBad,
+            2 => StudyFilter::Unseen,
+            3 => StudyFilter::Mixed,
+            4 => StudyFilter::Favorites,
+            _ => StudyFilter::AllDue,
+        }
+    }
+
+    /// Whether the flashcard can be studied, only the default filter waits for the due date
+    pub fn includes(&self, flashcard: &Flashcard, now: i64) -> bool {
+        match self {
+            StudyFilter::AllDue => scheduler::is_due(flashcard, now),
+            StudyFilter::OnlyBad => flashcard.status == 1,
+            StudyFilter::Unseen => flashcard.status == 0,
+            StudyFilter::Mixed => matches!(flashcard.status, 0 | 1),
+            StudyFilter::Favorites => flashcard.favorite,
+        }
+    }
+}
+
+/// How the next flashcard to study is chosen
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StudyOrder {
+    /// Random, the worse the status the more likely
+    #[default]
+    Random,
+    /// Creation order, starting again from the first one once every flashcard has been seen
+    Sequential,
+    /// The flashcards that have been due for longer go first
+    OldestFirst,
+}
+
+impl StudyOrder {
+    /// The option after this one, going back to the first after the last
+    pub fn next(&self) -> StudyOrder {
+        match self {
+            StudyOrder::Random => StudyOrder::Sequential,
+            StudyOrder::Sequential => StudyOrder::OldestFirst,
+            StudyOrder::OldestFirst => StudyOrder::Random,
+        }
+    }
+
+    /// Value saved in the study preferences of the folder
+    pub fn to_db(self) -> i32 {
+        match self {
+            StudyOrder::Random => 0,
+            StudyOrder::Sequential => 1,
+            StudyOrder::OldestFirst => 2,
+        }
+    }
+
+    pub fn from_db(value: i32) -> StudyOrder {
+        match value {
+            1 => StudyOrder::Sequential,
+            2 => StudyOrder::OldestFirst,
+            _ => StudyOrder::Random,
+        }
+    }
+}
+
+/// How a folder is studied, saved in the database for each folder
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FolderStudyPreferences {
+    pub mode: StudyMode,
+    pub direction: StudyDirection,
+    pub order: StudyOrder,
+    pub filter: StudyFilter,
+}
+
+/// Direction a folder is moved to inside of its studyset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
+/// Sides of the flashcards an operation is applied to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlashcardSides {
+    #[default]
+    Both,
+    Front,
+    Back,
+}
+
+impl FlashcardSides {
+    pub fn includes_front(&self) -> bool {
+        matches!(self, FlashcardSides::Both | FlashcardSides::Front)
+    }
+
+    pub fn includes_back(&self) -> bool {
+        matches!(self, FlashcardSides::Both | FlashcardSides::Back)
+    }
+}
+
+/// How many flashcards of a folder are in each status (0 = unseen, 1 = bad, 2 = ok, 3 = good)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FolderStats {
+    pub total: usize,
+    pub good: usize,
+    pub ok: usize,
+    pub bad: usize,
+    pub unseen: usize,
+}
+
+/// Result of importing rows of text, how many flashcards were added and how many rows were
+/// skipped because they couldn't be read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub skipped: usize,
+}
+
+/// A grade given to a flashcard while studying it
+#[derive(Debug, Clone)]
+pub struct Review {
+    /// SM-2 grade (0-5) of the study button that was pressed
+    pub grade: i32,
+    /// Unix timestamp of when the flashcard was graded
+    pub reviewed_at: i64,
+    /// Local date and time of the review, formatted by the database
+    pub date: String,
+}
+
+/// A flashcard together with the folder and studyset it belongs to
+#[derive(Debug, Clone)]
+pub struct FlashcardLocation {
+    pub flashcard: Flashcard,
+    pub folder_id: i32,
+    pub folder_name: String,
+    pub studyset_id: i32,
+    pub studyset_name: String,
+}
+
+/// Flashcards a study session goes through, they're reloaded after every grade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StudyScope {
+    /// The flashcards of the folder
+    Folder(i32),
+    /// The due flashcards of every folder
+    AllDue,
+}
@@ -1,77 +1,429 @@
+use std::collections::BTreeSet;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::models::Flashcard;
+use crate::core::import::strip_html;
+use crate::models::{Flashcard, FlashcardSides};
 use percent_encoding::percent_decode_str;
 use rand::prelude::*;
 use rand::seq::SliceRandom;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
+/// What went wrong in an operation, its Display is the description shown to the user.
+/// The sqlx and io errors are reference counted so the error can be cloned into messages
 #[derive(Debug, Clone)]
-pub struct OboeteError {
-    //TODO: Improve Error Handling implies removing this allow(dead_code)
-    #[allow(dead_code)]
-    pub message: String,
+pub enum OboeteError {
+    /// The database hasn't been opened (yet)
+    NoDatabase,
+    Db(Arc<sqlx::Error>),
+    Io(Arc<io::Error>),
+    /// Content (JSON, CSV, Anki packages...) that couldn't be read
+    Parse(String),
+    /// The description of what doesn't exist
+    NotFound(String),
+    /// Input rejected before being saved, the description is already localized where it's shown as is
+    Validation(String),
+    /// The audio output or the audio file couldn't be used
+    Audio(String),
+    /// The remote server couldn't be reached or refused the request
+    Network(String),
+    /// The desktop notification couldn't be sent
+    Notification(String),
+    /// The schema version of a database created by a newer version of the app
+    NewerSchema(i64),
+    /// A studyset or folder name that's empty once trimmed
+    EmptyName,
+    /// The name is already used by another studyset, or by another folder of the same studyset
+    NameTaken(String),
+    /// The flashcard doesn't exist (anymore)
+    FlashcardNotFound,
+    /// The folder doesn't exist (anymore)
+    FolderNotFound,
+}
+
+impl fmt::Display for OboeteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OboeteError::NoDatabase => write!(f, "Cannot access DB pool"),
+            OboeteError::Db(err) => write!(f, "Database error: {}", err),
+            OboeteError::Io(err) => write!(f, "File error: {}", err),
+            OboeteError::Parse(message) => write!(f, "Couldn't read the content: {}", message),
+            OboeteError::Network(message) => write!(f, "Network error: {}", message),
+            OboeteError::Notification(message) => write!(f, "Notification error: {}", message),
+            OboeteError::NewerSchema(version) => write!(
+                f,
+                "The database was created by a newer version of Oboete (schema version {})",
+                version
+            ),
+            OboeteError::EmptyName => write!(f, "The name can't be empty"),
+            OboeteError::NameTaken(name) => write!(f, "\"{}\" is already taken", name),
+            OboeteError::FlashcardNotFound => write!(f, "The flashcard doesn't exist"),
+            OboeteError::FolderNotFound => write!(f, "The folder doesn't exist"),
+            OboeteError::NotFound(message)
+            | OboeteError::Validation(message)
+            | OboeteError::Audio(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for OboeteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OboeteError::Db(err) => Some(err.as_ref()),
+            OboeteError::Io(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<sqlx::Error> for OboeteError {
     fn from(err: sqlx::Error) -> Self {
-        OboeteError {
-            message: err.to_string(),
-        }
+        OboeteError::Db(Arc::new(err))
     }
 }
 
-pub fn select_random_flashcard(flashcards: &Vec<Flashcard>) -> Option<Flashcard> {
-    let mut rng = thread_rng();
-    let mut weighted_flashcards = Vec::new();
+impl From<io::Error> for OboeteError {
+    fn from(err: io::Error) -> Self {
+        OboeteError::Io(Arc::new(err))
+    }
+}
 
-    for flashcard in flashcards {
-        let weight = match flashcard.status {
-            1 => 4, // High chance (status = 1 = flashcard Bad)
-            2 => 3, // Medium chance (status = 2 = flashcard Ok)
+impl From<serde_json::Error> for OboeteError {
+    fn from(err: serde_json::Error) -> Self {
+        OboeteError::Parse(err.to_string())
+    }
+}
+
+/// Environment variable with a number that makes the random choices of the study sessions
+/// (flashcards, sides and multiple choice answers) repeat, for demos and tests
+pub const STUDY_SEED_ENV: &str = "OBOETE_STUDY_SEED";
+
+/// Random number generator of a study session, seeded with `OBOETE_STUDY_SEED` when it's set
+/// to a number, so the same flashcards give the same session. Otherwise it's seeded from entropy
+pub fn study_rng() -> StdRng {
+    study_rng_from(
+        std::env::var(STUDY_SEED_ENV)
+            .ok()
+            .and_then(|seed| seed.trim().parse::<u64>().ok()),
+    )
+}
+
+/// Random number generator seeded with the given seed, or from entropy without one
+pub fn study_rng_from(seed: Option<u64>) -> StdRng {
+    seed.map(StdRng::seed_from_u64)
+        .unwrap_or_else(StdRng::from_entropy)
+}
+
+/// Selects a random flashcard, the worse its status the more likely it is to be chosen
+pub fn select_weighted_flashcard<R: Rng + ?Sized>(
+    flashcards: &[Flashcard],
+    rng: &mut R,
+) -> Option<Flashcard> {
+    // Every weight is positive, so this only fails on an empty slice
+    flashcards
+        .choose_weighted(rng, |flashcard| match flashcard.status {
+            0 => 4, // Highest chance (status = 0 = flashcard never studied)
+            1 => 3, // High chance (status = 1 = flashcard Bad)
+            2 => 2, // Medium chance (status = 2 = flashcard Ok)
             3 => 1, // Low chance (status = 3 = flashcard Good)
             _ => 2, // Default chance for other statuses
-        };
+        })
+        .ok()
+        .cloned()
+}
+
+/// Writes a Markdown test sheet with the fronts and blank space for the answers,
+/// optionally followed by an answer key with the backs. The shuffle follows `OBOETE_STUDY_SEED`
+/// like the study sessions
+pub fn export_test_sheet(
+    file_path: &str,
+    flashcards: &[Flashcard],
+    titles: (&str, &str),
+    shuffled: bool,
+    answer_key: bool,
+) -> Result<(), io::Error> {
+    let mut file = File::create(file_path)?;
+    let mut flashcards: Vec<&Flashcard> = flashcards.iter().collect();
+
+    if shuffled {
+        flashcards.shuffle(&mut study_rng());
+    }
+
+    writeln!(file, "# {}", titles.0)?;
+    for (index, flashcard) in flashcards.iter().enumerate() {
+        writeln!(file)?;
+        writeln!(file, "{}. {}", index + 1, flashcard.front.trim())?;
+        writeln!(file)?;
+        writeln!(file, "    ______________________________")?;
+    }
+
+    if answer_key {
+        writeln!(file)?;
+        writeln!(file, "# {}", titles.1)?;
+        writeln!(file)?;
+        for (index, flashcard) in flashcards.iter().enumerate() {
+            writeln!(
+                file,
+                "{}. {}: {}",
+                index + 1,
+                flashcard.front.trim(),
+                flashcard.back.trim()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts zero-width spaces inside words longer than `max_len` characters,
+/// so long unbroken strings (URLs, chemical names...) can wrap instead of overflowing
+pub fn break_long_words(text: &str, max_len: usize) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_len = 0;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            word_len = 0;
+        } else {
+            if word_len == max_len {
+                result.push('\u{200B}');
+                word_len = 0;
+            }
+            word_len += 1;
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+/// Text size for a side of a flashcard: texts up to 40 characters use `max_size`, longer ones
+/// shrink with the square root of their length (so they take about the same area) down to half of it
+pub fn fitting_text_size(text: &str, max_size: u16) -> u16 {
+    const FULL_SIZE_CHARS: f32 = 40.0;
+
+    let len = text.chars().count() as f32;
+    if len <= FULL_SIZE_CHARS {
+        return max_size;
+    }
+
+    let size = max_size as f32 * (FULL_SIZE_CHARS / len).sqrt();
+    (size.round() as u16).max(max_size / 2)
+}
+
+/// Lowercases the text and removes its diacritics (the accents of "café", the umlauts of
+/// "über", the cedilla of "français"...), so searches and answers match without them
+pub fn normalize_for_search(text: &str) -> String {
+    text.to_lowercase()
+        .nfd()
+        .filter(|character| !is_combining_mark(*character))
+        .collect()
+}
+
+/// Compares a typed answer with the back of a flashcard, ignoring case, diacritics and extra
+/// whitespace. Up to `tolerance` typos (Levenshtein distance) are accepted
+pub fn answer_matches(input: &str, expected: &str, tolerance: usize) -> bool {
+    let normalize = |text: &str| {
+        normalize_for_search(&text.split_whitespace().collect::<Vec<&str>>().join(" "))
+    };
+    let (input, expected) = (normalize(input), normalize(expected));
+
+    if input.is_empty() {
+        return expected.is_empty();
+    }
+
+    levenshtein_distance(&input, &expected) <= tolerance
+}
 
-        for _ in 0..weight {
-            weighted_flashcards.push(flashcard);
+/// Key the fronts are compared with: the same term gives the same key whatever its case and
+/// surrounding whitespace. Used to find duplicates and to match the flashcards of a source file
+pub fn front_key(front: &str) -> String {
+    front.trim().to_lowercase()
+}
+
+/// Whether a flashcard with these tags passes the tag filter: it needs every selected tag with
+/// `match_all`, any of them otherwise. Nothing selected lets every flashcard through
+pub fn matches_tags(tags: &[String], selected: &BTreeSet<String>, match_all: bool) -> bool {
+    if selected.is_empty() {
+        return true;
+    }
+
+    if match_all {
+        selected.iter().all(|tag| tags.contains(tag))
+    } else {
+        selected.iter().any(|tag| tags.contains(tag))
+    }
+}
+
+/// Whether two fronts are the same term, ignoring the case and the surrounding whitespace
+pub fn same_front(a: &str, b: &str) -> bool {
+    front_key(a) == front_key(b)
+}
+
+/// Replaces the text in the given sides of a flashcard, returns the new front and back
+/// or None if neither of them contains the text
+pub fn replace_flashcard_text(
+    front: &str,
+    back: &str,
+    find: &str,
+    replace: &str,
+    sides: FlashcardSides,
+    case_sensitive: bool,
+) -> Option<(String, String)> {
+    let replace_side = |text: &str, included: bool| match included {
+        true => replace_text(text, find, replace, case_sensitive),
+        false => None,
+    };
+    let new_front = replace_side(front, sides.includes_front());
+    let new_back = replace_side(back, sides.includes_back());
+
+    if new_front.is_none() && new_back.is_none() {
+        return None;
+    }
+
+    Some((
+        new_front.unwrap_or_else(|| front.to_string()),
+        new_back.unwrap_or_else(|| back.to_string()),
+    ))
+}
+
+/// Replaces every occurrence of `find` in the text, returns None if there isn't any.
+/// Without case sensitivity the lowercase characters are compared, the rest of the text is kept as it is
+pub fn replace_text(text: &str, find: &str, replace: &str, case_sensitive: bool) -> Option<String> {
+    if find.is_empty() {
+        return None;
+    }
+
+    if case_sensitive {
+        return text.contains(find).then(|| text.replace(find, replace));
+    }
+
+    let find: Vec<char> = find.chars().flat_map(char::to_lowercase).collect();
+    let mut result = String::with_capacity(text.len());
+    let mut replaced = false;
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        match lowercase_prefix_len(rest, &find) {
+            Some(len) => {
+                result.push_str(replace);
+                rest = &rest[len..];
+                replaced = true;
+            }
+            None => {
+                result.push(character);
+                rest = &rest[character.len_utf8()..];
+            }
         }
     }
 
-    // Select a random flashcard from the weighted list
-    weighted_flashcards.choose(&mut rng).copied().cloned()
+    replaced.then_some(result)
 }
 
+/// Length (in bytes) of the start of the text whose lowercase characters are the given ones
+fn lowercase_prefix_len(text: &str, lowercase: &[char]) -> Option<usize> {
+    let mut matched = 0;
+
+    for (index, character) in text.char_indices() {
+        for lower in character.to_lowercase() {
+            if lowercase.get(matched) != Some(&lower) {
+                return None;
+            }
+            matched += 1;
+        }
+
+        if matched == lowercase.len() {
+            return Some(index + character.len_utf8());
+        }
+    }
+
+    None
+}
+
+/// Minimum number of single character insertions, deletions or substitutions to turn `a` into `b`
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Splits content like the Quizlet exports, the terms and cards are trimmed and
+/// cards missing the term or the definition are skipped
 pub fn parse_import_content(
-    line_delimiter: &String,
-    term_delimiter: &String,
-    content: &String,
+    line_delimiter: &str,
+    term_delimiter: &str,
+    content: &str,
 ) -> Vec<Flashcard> {
+    let line_delimiter = unescape_separator(line_delimiter);
+    let term_delimiter = unescape_separator(term_delimiter);
+
+    if line_delimiter.is_empty() || term_delimiter.is_empty() {
+        return Vec::new();
+    }
+
     content
-        .split(line_delimiter)
+        .split(line_delimiter.as_str())
         .filter_map(|line| {
-            let mut terms = line.split(term_delimiter);
-            if let (Some(front), Some(back)) = (terms.next(), terms.next()) {
-                Some(Flashcard {
-                    id: None,
-                    front: front.to_string(),
-                    back: back.to_string(),
-                    status: 0,
-                })
-            } else {
+            let (front, back) = line.split_once(term_delimiter.as_str())?;
+            let (front, back) = (front.trim(), back.trim());
+            if front.is_empty() || back.is_empty() {
                 None
+            } else {
+                Some(Flashcard::new(front.to_string(), back.to_string()))
             }
         })
         .collect()
 }
 
+/// Parses rows pasted from a spreadsheet, one `front<TAB>back` flashcard per line. Blank lines
+/// are ignored and empty cells at the end of a row too, the other rows that don't have exactly
+/// a front and a back are skipped. Returns the flashcards and how many rows were skipped
+pub fn parse_pasted_tsv(text: &str) -> (Vec<Flashcard>, usize) {
+    let mut flashcards = Vec::new();
+    let mut skipped = 0;
+
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let mut cells: Vec<&str> = line.split('\t').map(|cell| cell.trim()).collect();
+        while cells.last().is_some_and(|cell| cell.is_empty()) {
+            cells.pop();
+        }
+
+        match cells.as_slice() {
+            [front, back] if !front.is_empty() && !back.is_empty() => {
+                flashcards.push(Flashcard::new(front.to_string(), back.to_string()))
+            }
+            _ => skipped += 1,
+        }
+    }
+
+    (flashcards, skipped)
+}
+
+/// Tabs and line breaks can't be typed in a text input, so `\t` and `\n` stand for them
+fn unescape_separator(separator: &str) -> String {
+    separator.replace("\\t", "\t").replace("\\n", "\n")
+}
+
 pub fn parse_ankifile(file_path: &str) -> Result<Vec<Flashcard>, io::Error> {
     let decoded_path = percent_decode_str(file_path)
         .decode_utf8_lossy()
         .to_string();
     let path = Path::new(&decoded_path);
-    let file = File::open(&path)?;
+    let file = File::open(path)?;
     let reader = io::BufReader::new(file);
 
     let mut flashcards = Vec::new();
@@ -84,18 +436,114 @@ pub fn parse_ankifile(file_path: &str) -> Result<Vec<Flashcard>, io::Error> {
         }
         let parts: Vec<&str> = line.split('\t').collect();
         if parts.len() == 2 {
-            flashcards.push(Flashcard {
-                id: None,
-                front: parts[0].to_string(),
-                back: parts[1].to_string(),
-                status: 0,
-            });
+            flashcards.push(Flashcard::new(strip_html(parts[0]), strip_html(parts[1])));
         }
     }
 
     Ok(flashcards)
 }
 
+/// Parses two column (`front,back`) CSV content, fields can be quoted to contain commas,
+/// quotes (as `""`) or line breaks. Returns the flashcards and the rows (starting at 1) that couldn't be parsed
+pub fn parse_csv_flashcards(csv: &str) -> (Vec<Flashcard>, Vec<usize>) {
+    let mut flashcards = Vec::new();
+    let mut failed_rows = Vec::new();
+
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = csv.chars().peekable();
+
+    let mut finish_row = |fields: &mut Vec<String>, row_line: usize| {
+        let is_blank = fields.iter().all(|field| field.trim().is_empty());
+        if !is_blank {
+            match fields.as_slice() {
+                [front, back] if !front.trim().is_empty() && !back.trim().is_empty() => flashcards
+                    .push(Flashcard::new(
+                        front.trim().to_string(),
+                        back.trim().to_string(),
+                    )),
+                _ => failed_rows.push(row_line),
+            }
+        }
+        fields.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                finish_row(&mut fields, row_line);
+                line += 1;
+                row_line = line;
+            }
+            '\r' if !in_quotes => {}
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        //Unterminated quote, the rest of the content can't be trusted
+        failed_rows.push(row_line);
+    } else {
+        fields.push(field);
+        finish_row(&mut fields, row_line);
+    }
+
+    (flashcards, failed_rows)
+}
+
+/// Parses a tab separated file (one `front\tback` flashcard per line), as saved by most spreadsheet apps
+pub fn parse_tsv_file(file_path: &str) -> Result<Vec<Flashcard>, io::Error> {
+    let file = File::open(Path::new(file_path))?;
+    let reader = io::BufReader::new(file);
+
+    let mut flashcards = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut parts = line.split('\t');
+        if let (Some(front), Some(back)) = (parts.next(), parts.next()) {
+            if front.trim().is_empty() || back.trim().is_empty() {
+                continue;
+            }
+            flashcards.push(Flashcard::new(
+                front.trim().to_string(),
+                back.trim().to_string(),
+            ));
+        }
+    }
+
+    Ok(flashcards)
+}
+
+/// Decodes the percent-encoded path returned by the file chooser
+pub fn decode_file_path(file_path: &str) -> String {
+    percent_decode_str(file_path)
+        .decode_utf8_lossy()
+        .to_string()
+}
+
 pub fn export_flashcards(file_path: &str, flashcards: &Vec<Flashcard>) -> Result<(), io::Error> {
     let mut file = File::create(file_path)?;
 
@@ -106,3 +554,307 @@ pub fn export_flashcards(file_path: &str, flashcards: &Vec<Flashcard>) -> Result
 
     Ok(())
 }
+
+/// Shown instead of the hidden answers of a cloze flashcard
+const CLOZE_BLANK: &str = "[…]";
+
+/// Part of the front of a cloze flashcard, the answers are written between {{ and }}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClozeSpan<'a> {
+    Text(&'a str),
+    Answer(&'a str),
+}
+
+/// Splits the front of a cloze flashcard into its text and answers, an unclosed {{ is kept as text
+pub fn parse_cloze(text: &str) -> Vec<ClozeSpan<'_>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        if start > 0 {
+            spans.push(ClozeSpan::Text(&rest[..start]));
+        }
+        spans.push(ClozeSpan::Answer(&rest[start + 2..start + 2 + length]));
+        rest = &rest[start + 2 + length + 2..];
+    }
+    if !rest.is_empty() {
+        spans.push(ClozeSpan::Text(rest));
+    }
+
+    spans
+}
+
+/// The answers of a cloze flashcard (trimmed), in the order they're written
+pub fn cloze_answers(text: &str) -> Vec<&str> {
+    parse_cloze(text)
+        .into_iter()
+        .filter_map(|span| match span {
+            ClozeSpan::Answer(answer) => Some(answer.trim()),
+            ClozeSpan::Text(_) => None,
+        })
+        .collect()
+}
+
+/// The front of a cloze flashcard with its answers hidden behind a blank, or shown without the braces
+pub fn cloze_text(text: &str, reveal: bool) -> String {
+    parse_cloze(text)
+        .into_iter()
+        .map(|span| match span {
+            ClozeSpan::Text(text) => text,
+            ClozeSpan::Answer(answer) if reveal => answer.trim(),
+            ClozeSpan::Answer(_) => CLOZE_BLANK,
+        })
+        .collect()
+}
+
+/// Environment variable that moves the data of the app (database, attachments and backups)
+/// somewhere else, for portable installs
+pub const DATA_DIR_ENV: &str = "OBOETE_DATA_DIR";
+
+/// Directory the app keeps its data in, resolved in this order:
+/// 1. `OBOETE_DATA_DIR` when it's set and not empty. A relative path is taken from the directory
+///    of the executable, so a portable install can keep its data next to it. The directory is
+///    created if it's missing, if that fails the error is returned instead of using another one
+/// 2. The data directory of the user (`$XDG_DATA_HOME` or `~/.local/share` on Linux) with the app id
+pub fn app_data_dir(app_id: &str) -> Result<PathBuf, OboeteError> {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        let mut dir = PathBuf::from(dir);
+        if dir.is_relative() {
+            if let Some(exe_dir) = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            {
+                dir = exe_dir.join(dir);
+            }
+        }
+
+        return match std::fs::create_dir_all(&dir) {
+            Ok(()) => Ok(dir),
+            Err(err) => Err(io::Error::new(
+                err.kind(),
+                format!("Error creating {} ({})", dir.display(), err),
+            )
+            .into()),
+        };
+    }
+
+    dirs::data_dir()
+        .map(|dir| dir.join(app_id))
+        .ok_or_else(|| OboeteError::NotFound(String::from("No data directory")))
+}
+
+/// Copies a file attached to a flashcard into the data directory of the app (inside of `directory`),
+/// so the flashcard keeps working if the original file is moved. Returns the path of the copy
+pub fn store_attachment(
+    app_id: &str,
+    directory: &str,
+    source_path: &str,
+) -> Result<String, OboeteError> {
+    let data_dir = app_data_dir(app_id)?.join(directory);
+    std::fs::create_dir_all(&data_dir)?;
+
+    //The timestamp keeps the names unique, the extension is kept so the file type can be detected
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let file_name = match Path::new(source_path).extension() {
+        Some(extension) => format!("{}.{}", timestamp, extension.to_string_lossy()),
+        None => timestamp.to_string(),
+    };
+
+    let destination = data_dir.join(file_name);
+    std::fs::copy(source_path, &destination)?;
+
+    Ok(destination.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sides(flashcards: &[Flashcard]) -> Vec<(&str, &str)> {
+        flashcards
+            .iter()
+            .map(|flashcard| (flashcard.front.as_str(), flashcard.back.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn csv_quoted_fields_keep_commas_quotes_and_line_breaks() {
+        let csv = "\"hello, world\",\"say \"\"hi\"\"\"\n\"two\nlines\",back\n";
+        let (flashcards, failed_rows) = parse_csv_flashcards(csv);
+
+        assert_eq!(
+            sides(&flashcards),
+            [("hello, world", "say \"hi\""), ("two\nlines", "back")]
+        );
+        assert!(failed_rows.is_empty());
+    }
+
+    #[test]
+    fn csv_skips_empty_lines_and_reports_broken_rows() {
+        let csv = "a,b\r\n\r\n,\nonly front\nc,d,e\n  \nf, g \n";
+        let (flashcards, failed_rows) = parse_csv_flashcards(csv);
+
+        assert_eq!(sides(&flashcards), [("a", "b"), ("f", "g")]);
+        //The rows are counted from 1 with the empty lines
+        assert_eq!(failed_rows, [4, 5]);
+    }
+
+    #[test]
+    fn csv_unterminated_quote_fails_its_row() {
+        let (flashcards, failed_rows) = parse_csv_flashcards("a,b\n\"open,c\nd,e");
+
+        assert_eq!(sides(&flashcards), [("a", "b")]);
+        assert_eq!(failed_rows, [2]);
+    }
+
+    #[test]
+    fn pasted_tsv_skips_blank_lines_and_rows_with_extra_tabs() {
+        let text = "犬\tdog\n\n  \n猫\tcat\t\t\n鳥\tbird\textra\n\tmissing front\n魚\n";
+        let (flashcards, skipped) = parse_pasted_tsv(text);
+
+        assert_eq!(sides(&flashcards), [("犬", "dog"), ("猫", "cat")]);
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn import_content_uses_escaped_separators() {
+        let content = "front one\tback one\n\nno separator\n  front two \t back\ttwo ";
+        let flashcards = parse_import_content("\\n", "\\t", content);
+
+        assert_eq!(
+            sides(&flashcards),
+            [("front one", "back one"), ("front two", "back\ttwo")]
+        );
+        assert!(parse_import_content("", ",", "a,b").is_empty());
+    }
+
+    #[test]
+    fn tsv_file_takes_the_first_two_columns() {
+        let path = std::env::temp_dir().join(format!("oboete-utils-{}.tsv", std::process::id()));
+        std::fs::write(&path, "a\tb\n\nc\td\tnote\n\te\nf\n g \t h \n").unwrap();
+
+        let flashcards = parse_tsv_file(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(sides(&flashcards), [("a", "b"), ("c", "d"), ("g", "h")]);
+    }
+
+    #[test]
+    fn cloze_splits_text_and_answers() {
+        assert_eq!(
+            parse_cloze("{{水}} is water, {{ 火 }} is fire"),
+            [
+                ClozeSpan::Answer("水"),
+                ClozeSpan::Text(" is water, "),
+                ClozeSpan::Answer(" 火 "),
+                ClozeSpan::Text(" is fire"),
+            ]
+        );
+        assert_eq!(parse_cloze("no answers"), [ClozeSpan::Text("no answers")]);
+        assert!(parse_cloze("").is_empty());
+    }
+
+    #[test]
+    fn cloze_keeps_unclosed_braces_as_text() {
+        assert_eq!(
+            parse_cloze("a {{b}} c {{d"),
+            [
+                ClozeSpan::Text("a "),
+                ClozeSpan::Answer("b"),
+                ClozeSpan::Text(" c {{d"),
+            ]
+        );
+        assert!(cloze_answers("}} {{").is_empty());
+    }
+
+    #[test]
+    fn cloze_hides_and_reveals_the_answers() {
+        let front = "The capital of {{ Japan }} is {{Tokyo}}.";
+
+        assert_eq!(cloze_answers(front), ["Japan", "Tokyo"]);
+        assert_eq!(cloze_text(front, false), "The capital of […] is […].");
+        assert_eq!(cloze_text(front, true), "The capital of Japan is Tokyo.");
+    }
+
+    #[test]
+    fn normalize_removes_case_and_diacritics() {
+        assert_eq!(normalize_for_search("Café Mañana"), "cafe manana");
+        assert_eq!(
+            normalize_for_search("Français Élève Noël"),
+            "francais eleve noel"
+        );
+        assert_eq!(normalize_for_search("ÜBER Bär Öl"), "uber bar ol");
+        //Characters without a decomposition are kept
+        assert_eq!(normalize_for_search("Straße 日本"), "straße 日本");
+    }
+
+    #[test]
+    fn answers_match_without_case_accents_or_extra_spaces() {
+        assert!(answer_matches("  la   CANCIÓN ", "la canción", 0));
+        assert!(answer_matches("uber", "über", 0));
+        assert!(answer_matches("", "", 0));
+        assert!(!answer_matches("", "dog", 2));
+    }
+
+    #[test]
+    fn answers_accept_typos_up_to_the_tolerance() {
+        assert!(answer_matches("elefant", "elephant", 2));
+        assert!(!answer_matches("elefant", "elephant", 1));
+        assert!(answer_matches("hous", "house", 1));
+        //A swap of two letters counts as two typos
+        assert!(answer_matches("houes", "house", 2));
+        assert!(!answer_matches("houes", "house", 1));
+        assert!(!answer_matches("hose", "house", 0));
+
+        //Short answers get the same tolerance
+        assert!(answer_matches("cot", "cat", 1));
+        assert!(!answer_matches("cot", "cat", 0));
+        assert!(!answer_matches("dgo", "dog", 1));
+    }
+
+    #[test]
+    fn tag_filter_matches_any_or_every_selected_tag() {
+        let tags = [String::from("verb"), String::from("n5")];
+        let selected = |names: &[&str]| -> BTreeSet<String> {
+            names.iter().map(|name| name.to_string()).collect()
+        };
+
+        assert!(matches_tags(&tags, &selected(&[]), true));
+        assert!(matches_tags(&[], &selected(&[]), false));
+        assert!(matches_tags(&tags, &selected(&["verb", "n4"]), false));
+        assert!(!matches_tags(&tags, &selected(&["verb", "n4"]), true));
+        assert!(matches_tags(&tags, &selected(&["verb", "n5"]), true));
+        assert!(!matches_tags(&[], &selected(&["verb"]), false));
+    }
+
+    #[test]
+    fn seeded_study_rng_repeats_the_choices() {
+        let flashcards: Vec<Flashcard> = (0..20)
+            .map(|index| {
+                let mut flashcard = Flashcard::new(index.to_string(), String::new());
+                flashcard.status = index % 4;
+                flashcard
+            })
+            .collect();
+        let choices = |rng: &mut StdRng| {
+            (0..50)
+                .map(|_| select_weighted_flashcard(&flashcards, rng).unwrap().front)
+                .collect::<Vec<String>>()
+        };
+
+        let (mut first, mut second) = (study_rng_from(Some(42)), study_rng_from(Some(42)));
+
+        assert_eq!(choices(&mut first), choices(&mut second));
+        assert_eq!(
+            choices(&mut StdRng::seed_from_u64(7)),
+            choices(&mut StdRng::seed_from_u64(7))
+        );
+        assert!(select_weighted_flashcard(&[], &mut StdRng::seed_from_u64(7)).is_none());
+    }
+}
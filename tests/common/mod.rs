@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Helpers shared by the integration tests, every test gets its own in-memory database
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use oboete::{
+    core::database::{create_folder, create_studyset, upsert_flashcard, OboeteDb},
+    models::Flashcard,
+};
+
+/// A new in-memory database with every migration applied
+pub async fn database() -> Option<OboeteDb> {
+    Some(OboeteDb::in_memory().await.expect("in-memory database"))
+}
+
+/// Creates a studyset with a single folder, returns the ids of both
+pub async fn studyset_with_folder(db: &Option<OboeteDb>, name: &str) -> (i32, i32) {
+    let studyset = create_studyset(db.clone(), name.to_string())
+        .await
+        .expect("studyset");
+    let studyset_id = studyset.id.expect("studyset id");
+    let folder_id = create_folder(db.clone(), String::from("Folder"), studyset_id)
+        .await
+        .expect("folder");
+
+    (studyset_id, folder_id as i32)
+}
+
+/// Saves a new flashcard in the folder, returns its id
+pub async fn add_flashcard(db: &Option<OboeteDb>, folder_id: i32, front: &str, back: &str) -> i32 {
+    upsert_flashcard(
+        db.clone(),
+        Flashcard::new(front.to_string(), back.to_string()),
+        folder_id,
+    )
+    .await
+    .expect("flashcard") as i32
+}
+
+/// Path for a database file of a test in the temporary directory, removing any previous one
+pub fn temp_db_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("oboete-test-{}-{}.db", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
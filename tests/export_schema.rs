@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::BTreeSet;
+
+use oboete::models::ExportDocument;
+use serde_json::Value;
+
+const SCHEMA: &str = include_str!("../info/export-schema.json");
+const FORMAT: &str = include_str!("../info/EXPORT_FORMAT.md");
+
+fn generated_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ExportDocument)).unwrap()
+}
+
+/// The JSON block of EXPORT_FORMAT.md, the example document
+fn format_example() -> &'static str {
+    let start = FORMAT
+        .find("```json")
+        .expect("EXPORT_FORMAT.md has an example")
+        + "```json".len();
+    let end = start + FORMAT[start..].find("```").unwrap();
+    &FORMAT[start..end]
+}
+
+fn keys(value: &Value, found: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                found.insert(key.clone());
+                keys(value, found);
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| keys(item, found)),
+        _ => {}
+    }
+}
+
+fn schema_properties(schema: &Value) -> BTreeSet<String> {
+    let mut objects = vec![schema];
+    if let Some(Value::Object(definitions)) = schema.get("definitions") {
+        objects.extend(definitions.values());
+    }
+
+    objects
+        .into_iter()
+        .filter_map(|object| object.get("properties").and_then(Value::as_object))
+        .flat_map(|properties| properties.keys().cloned())
+        .collect()
+}
+
+#[test]
+fn schema_file_matches_the_types() {
+    let file: Value = serde_json::from_str(SCHEMA).unwrap();
+    assert!(
+        file == generated_schema(),
+        "info/export-schema.json is out of date, regenerate it with `cargo run --example export_schema --features schema > info/export-schema.json`"
+    );
+}
+
+#[test]
+fn format_example_follows_the_schema() {
+    let example = format_example();
+    serde_json::from_str::<ExportDocument>(example).unwrap();
+
+    let mut example_keys = BTreeSet::new();
+    keys(&serde_json::from_str(example).unwrap(), &mut example_keys);
+    let properties = schema_properties(&generated_schema());
+    let unknown: Vec<_> = example_keys.difference(&properties).collect();
+    assert!(
+        unknown.is_empty(),
+        "EXPORT_FORMAT.md documents unknown fields: {unknown:?}"
+    );
+
+    assert!(FORMAT.contains("(export-schema.json)"));
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::{
+    core::database::{create_folder, get_folder_flashcards, get_single_folder, merge_folders},
+    utils::OboeteError,
+};
+
+use common::{add_flashcard, database, studyset_with_folder};
+
+#[tokio::test]
+async fn merged_folder_moves_every_flashcard_into_the_target() {
+    let db = database().await;
+    let (studyset_id, target_id) = studyset_with_folder(&db, "Japanese").await;
+    let source_id = create_folder(db.clone(), String::from("Animals"), studyset_id)
+        .await
+        .unwrap() as i32;
+    add_flashcard(&db, target_id, "水", "water").await;
+    add_flashcard(&db, source_id, "犬", "dog").await;
+    add_flashcard(&db, source_id, "猫", "cat").await;
+
+    merge_folders(db.clone(), source_id, target_id)
+        .await
+        .unwrap();
+
+    let flashcards = get_folder_flashcards(db.clone(), target_id).await.unwrap();
+    assert_eq!(flashcards.len(), 3);
+    assert!(get_single_folder(db.clone(), source_id).await.is_err());
+
+    //Merging a folder with itself does nothing
+    merge_folders(db.clone(), target_id, target_id)
+        .await
+        .unwrap();
+    assert_eq!(get_folder_flashcards(db, target_id).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn folders_of_other_studysets_are_not_merged() {
+    let db = database().await;
+    let (_, source_id) = studyset_with_folder(&db, "Japanese").await;
+    let (_, target_id) = studyset_with_folder(&db, "German").await;
+    add_flashcard(&db, source_id, "犬", "dog").await;
+
+    let result = merge_folders(db.clone(), source_id, target_id).await;
+    assert!(matches!(result, Err(OboeteError::Validation(_))));
+
+    //Nothing was moved
+    assert_eq!(
+        get_folder_flashcards(db.clone(), source_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(get_folder_flashcards(db, target_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::{
+    core::{
+        database::{
+            get_all_due_flashcards, get_due_count, get_flashcard_history, get_single_flashcard,
+            grade_flashcard, import_flashcards, undo_flashcard_grade,
+        },
+        scheduler::{self, IntervalLimits, SchedulerKind},
+    },
+    models::{Flashcard, StudyScope},
+};
+
+use common::{add_flashcard, database, studyset_with_folder};
+
+#[tokio::test]
+async fn grading_saves_the_schedule_and_the_history() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Grading").await;
+    let id = add_flashcard(&db, folder_id, "犬", "dog").await;
+
+    let mut flashcard = get_single_flashcard(db.clone(), id).await.unwrap();
+    let now = scheduler::now();
+    SchedulerKind::Sm2.update(&mut flashcard, 5, now, IntervalLimits::default());
+    let graded = flashcard.clone();
+
+    let flashcards = grade_flashcard(db.clone(), flashcard, 5, StudyScope::Folder(folder_id))
+        .await
+        .unwrap();
+    assert_eq!(flashcards.len(), 1);
+
+    let saved = get_single_flashcard(db.clone(), id).await.unwrap();
+    assert_eq!(saved.interval, 1);
+    assert_eq!(saved.repetitions, 1);
+    assert_eq!(saved.due_date, graded.due_date);
+    assert_eq!(saved.ease_factor, graded.ease_factor);
+    assert_eq!(saved.good_streak, 1);
+
+    let history = get_flashcard_history(db.clone(), id).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].grade, 5);
+}
+
+#[tokio::test]
+async fn graded_flashcards_are_no_longer_due() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Due").await;
+    let id = add_flashcard(&db, folder_id, "猫", "cat").await;
+    add_flashcard(&db, folder_id, "鳥", "bird").await;
+    assert_eq!(get_all_due_flashcards(db.clone()).await.unwrap().len(), 2);
+
+    let mut flashcard = get_single_flashcard(db.clone(), id).await.unwrap();
+    SchedulerKind::Leitner.update(
+        &mut flashcard,
+        4,
+        scheduler::now(),
+        IntervalLimits::default(),
+    );
+    grade_flashcard(db.clone(), flashcard, 4, StudyScope::AllDue)
+        .await
+        .unwrap();
+
+    let due = get_all_due_flashcards(db.clone()).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].flashcard.front, "鳥");
+}
+
+#[tokio::test]
+async fn undoing_a_grade_restores_the_flashcard() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Undo").await;
+    let id = add_flashcard(&db, folder_id, "魚", "fish").await;
+
+    let before = get_single_flashcard(db.clone(), id).await.unwrap();
+    let mut flashcard = before.clone();
+    SchedulerKind::Sm2.update(
+        &mut flashcard,
+        3,
+        scheduler::now(),
+        IntervalLimits::default(),
+    );
+    grade_flashcard(db.clone(), flashcard, 3, StudyScope::Folder(folder_id))
+        .await
+        .unwrap();
+
+    undo_flashcard_grade(db.clone(), before.clone(), StudyScope::Folder(folder_id))
+        .await
+        .unwrap();
+
+    let saved = get_single_flashcard(db.clone(), id).await.unwrap();
+    assert_eq!(saved.due_date, before.due_date);
+    assert_eq!(saved.repetitions, before.repetitions);
+    assert!(get_flashcard_history(db, id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn due_count_leaves_out_the_mastered_flashcards() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Mastered").await;
+    add_flashcard(&db, folder_id, "猫", "cat").await;
+
+    let mut mastered = Flashcard::new(String::from("鳥"), String::from("bird"));
+    mastered.good_streak = 5;
+    mastered.due_date = scheduler::now() - 1;
+    import_flashcards(db.clone(), vec![mastered], folder_id)
+        .await
+        .unwrap();
+
+    assert_eq!(get_due_count(db.clone(), Some(5)).await.unwrap(), 1);
+    assert_eq!(get_due_count(db.clone(), Some(6)).await.unwrap(), 2);
+    assert_eq!(get_due_count(db, None).await.unwrap(), 2);
+}
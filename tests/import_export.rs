@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::{
+    core::{
+        database::{
+            export_all, export_studyset, get_all_flashcards, get_all_full_studysets,
+            get_all_studysets, get_folder_flashcards, get_single_flashcard, grade_flashcard,
+            import_all, import_flashcards, import_tsv, replace_all_studysets, upsert_flashcard,
+        },
+        scheduler::{self, IntervalLimits, SchedulerKind},
+    },
+    models::{CardType, Flashcard, ImportSummary, StudyScope, EXPORT_SCHEMA_VERSION},
+};
+
+use common::{add_flashcard, database, studyset_with_folder};
+
+#[tokio::test]
+async fn exported_studyset_is_imported_as_a_copy() {
+    let db = database().await;
+    let (studyset_id, folder_id) = studyset_with_folder(&db, "Kanji").await;
+    add_flashcard(&db, folder_id, "山", "mountain").await;
+    add_flashcard(&db, folder_id, "川", "river").await;
+
+    let json = export_studyset(db.clone(), studyset_id).await.unwrap();
+    assert!(json.contains(&format!("\"schema_version\": {}", EXPORT_SCHEMA_VERSION)));
+
+    let imported = import_all(db.clone(), json).await.unwrap();
+    assert_eq!(imported.len(), 1);
+    assert_ne!(imported[0].id, Some(studyset_id));
+
+    let studysets = get_all_full_studysets(db).await.unwrap();
+    assert_eq!(studysets.len(), 2);
+    assert_eq!(studysets[1].name, studysets[0].name);
+    assert_eq!(studysets[1].folders.len(), 1);
+}
+
+#[tokio::test]
+async fn replacing_keeps_only_the_imported_studysets() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Old").await;
+    add_flashcard(&db, folder_id, "old", "viejo").await;
+
+    let other = database().await;
+    let (_, other_folder_id) = studyset_with_folder(&other, "New").await;
+    add_flashcard(&other, other_folder_id, "new", "nuevo").await;
+    let studysets = get_all_full_studysets(other).await.unwrap();
+
+    replace_all_studysets(db.clone(), studysets).await.unwrap();
+
+    //The folders and flashcards of the replaced studysets go with them
+    let flashcards = get_all_flashcards(db.clone()).await.unwrap();
+    assert_eq!(flashcards.len(), 1);
+    assert_eq!(flashcards[0].flashcard.front, "new");
+
+    let names: Vec<String> = get_all_studysets(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|studyset| studyset.name)
+        .collect();
+    assert_eq!(names, vec![String::from("New")]);
+}
+
+#[tokio::test]
+async fn legacy_export_without_version_is_imported() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Legacy").await;
+    add_flashcard(&db, folder_id, "front", "back").await;
+
+    //Before the export documents were versioned, export_all wrote a bare list of studysets
+    let document: serde_json::Value =
+        serde_json::from_str(&export_all(db.clone()).await.unwrap()).expect("export document");
+    let legacy = document["studysets"].to_string();
+
+    let other = database().await;
+    let imported = import_all(other, legacy).await.unwrap();
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].name, "Legacy");
+}
+
+#[tokio::test]
+async fn pasted_rows_are_imported() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Pasted").await;
+
+    let summary = import_tsv(db, folder_id, String::from("one\tuno\n\ntwo\tdos\nbroken"))
+        .await
+        .unwrap();
+    assert_eq!(
+        summary,
+        ImportSummary {
+            added: 2,
+            skipped: 1
+        }
+    );
+}
+
+#[tokio::test]
+async fn imported_flashcards_keep_their_progress() {
+    let db = database().await;
+    let (studyset_id, folder_id) = studyset_with_folder(&db, "Progress").await;
+    let id = upsert_flashcard(
+        db.clone(),
+        Flashcard {
+            tags: vec![String::from("nature")],
+            image_path: Some(String::from("images/tree.png")),
+            audio_path: Some(String::from("audio/ki.ogg")),
+            favorite: true,
+            card_type: CardType::Cloze,
+            ..Flashcard::new(String::from("{{木}}"), String::from("tree"))
+        },
+        folder_id,
+    )
+    .await
+    .unwrap() as i32;
+
+    let mut flashcard = get_single_flashcard(db.clone(), id).await.unwrap();
+    let now = scheduler::now();
+    for grade in [5, 4, 5] {
+        SchedulerKind::Sm2.update(&mut flashcard, grade, now, IntervalLimits::default());
+        SchedulerKind::Leitner.update(&mut flashcard, grade, now, IntervalLimits::default());
+        flashcard.record_response(1500);
+    }
+    flashcard.status = 3;
+    grade_flashcard(db.clone(), flashcard, 5, StudyScope::Folder(folder_id))
+        .await
+        .unwrap();
+    let exported = get_single_flashcard(db.clone(), id).await.unwrap();
+
+    let other = database().await;
+    let json = export_studyset(db, studyset_id).await.unwrap();
+    let imported = import_all(other.clone(), json).await.unwrap();
+    let studysets = get_all_full_studysets(other).await.unwrap();
+    assert_eq!(studysets[0].id, imported[0].id);
+
+    let flashcard = &studysets[0].folders[0].flashcards[0];
+    assert_eq!(flashcard.front, exported.front);
+    assert_eq!(flashcard.back, exported.back);
+    assert_eq!(flashcard.status, exported.status);
+    assert_eq!(flashcard.ease_factor, exported.ease_factor);
+    assert_eq!(flashcard.interval, exported.interval);
+    assert_eq!(flashcard.repetitions, exported.repetitions);
+    assert_eq!(flashcard.due_date, exported.due_date);
+    assert_eq!(flashcard.leitner_box, exported.leitner_box);
+    assert_eq!(flashcard.good_streak, exported.good_streak);
+    assert_eq!(flashcard.average_response_ms, exported.average_response_ms);
+    assert_eq!(flashcard.response_count, exported.response_count);
+    assert_eq!(flashcard.created_at, exported.created_at);
+    assert_eq!(flashcard.favorite, exported.favorite);
+    assert_eq!(flashcard.card_type, exported.card_type);
+    assert_eq!(flashcard.image_path, exported.image_path);
+    assert_eq!(flashcard.audio_path, exported.audio_path);
+    assert_eq!(flashcard.tags, exported.tags);
+}
+
+#[tokio::test]
+async fn importing_into_the_same_database_copies_the_flashcards() {
+    let db = database().await;
+    let (studyset_id, folder_id) = studyset_with_folder(&db, "Copies").await;
+    add_flashcard(&db, folder_id, "山", "mountain").await;
+    add_flashcard(&db, folder_id, "川", "river").await;
+
+    let json = export_studyset(db.clone(), studyset_id).await.unwrap();
+    import_all(db.clone(), json).await.unwrap();
+
+    let studysets = get_all_full_studysets(db).await.unwrap();
+    for studyset in &studysets {
+        let fronts: Vec<&str> = studyset.folders[0]
+            .flashcards
+            .iter()
+            .map(|flashcard| flashcard.front.as_str())
+            .collect();
+        assert_eq!(fronts, vec!["山", "川"]);
+    }
+}
+
+#[tokio::test]
+async fn imported_flashcards_keep_their_due_date() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Due date").await;
+    let due_date = scheduler::now() + 3 * 86_400;
+
+    import_flashcards(
+        db.clone(),
+        vec![
+            Flashcard {
+                due_date,
+                interval: 3,
+                tags: vec![String::from("verbs"), String::from(" verbs ")],
+                ..Flashcard::new(String::from("食べる"), String::from("to eat"))
+            },
+            Flashcard::new(String::from("飲む"), String::from("to drink")),
+        ],
+        folder_id,
+    )
+    .await
+    .unwrap();
+
+    let flashcards = get_folder_flashcards(db, folder_id).await.unwrap();
+    assert_eq!(flashcards.len(), 2);
+    assert_eq!(flashcards[0].due_date, due_date);
+    assert_eq!(flashcards[0].interval, 3);
+    assert_eq!(flashcards[0].tags, vec![String::from("verbs")]);
+    assert_eq!(flashcards[1].due_date, 0);
+}
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::{
+    core::{
+        database::{get_folder_flashcards, OboeteDb},
+        migrations::{schema_version, LATEST_VERSION},
+    },
+    utils::OboeteError,
+};
+use sqlx::sqlite::SqlitePool;
+
+use common::temp_db_path;
+
+#[tokio::test]
+async fn new_database_gets_every_migration() {
+    let path = temp_db_path("new");
+    OboeteDb::open(&path).await.expect("database");
+
+    let pool = SqlitePool::connect(path.to_str().unwrap()).await.unwrap();
+    assert_eq!(schema_version(&pool).await.unwrap(), LATEST_VERSION);
+}
+
+#[tokio::test]
+async fn reopening_keeps_the_version() {
+    let path = temp_db_path("reopen");
+    OboeteDb::open(&path).await.expect("first open");
+    OboeteDb::open(&path).await.expect("second open");
+
+    let pool = SqlitePool::connect(path.to_str().unwrap()).await.unwrap();
+    assert_eq!(schema_version(&pool).await.unwrap(), LATEST_VERSION);
+}
+
+/// Schema of the databases created before the schema was versioned, when the columns were
+/// added on launch without recording it, so some of the later ones are already there
+const UNVERSIONED_SCHEMA: &str = r#"
+    CREATE TABLE studysets (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        icon TEXT NOT NULL DEFAULT 'folder-symbolic'
+    );
+
+    CREATE TABLE folders (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        studyset_id INTEGER NOT NULL,
+        source_path TEXT,
+        FOREIGN KEY (studyset_id) REFERENCES studysets(id) ON DELETE CASCADE
+    );
+
+    CREATE TABLE flashcards (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        front TEXT NOT NULL,
+        back TEXT NOT NULL,
+        status INTEGER NOT NULL,
+        folder_id INTEGER NOT NULL,
+        ease_factor REAL NOT NULL DEFAULT 2.5,
+        interval INTEGER NOT NULL DEFAULT 0,
+        repetitions INTEGER NOT NULL DEFAULT 0,
+        due_date INTEGER NOT NULL DEFAULT 0,
+        FOREIGN KEY (folder_id) REFERENCES folders(id) ON DELETE CASCADE
+    );
+
+    INSERT INTO studysets (name, icon) VALUES ('Kanji', 'folder-symbolic');
+    INSERT INTO folders (name, studyset_id, source_path) VALUES ('N5', 1, '/tmp/n5.tsv');
+    INSERT INTO flashcards (front, back, status, folder_id, ease_factor, interval, repetitions, due_date)
+        VALUES ('水', 'water', 3, 1, 2.1, 6, 2, 1700000000);
+"#;
+
+#[tokio::test]
+async fn unversioned_database_with_existing_columns_is_migrated() {
+    let path = temp_db_path("unversioned");
+    let pool = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", path.display()))
+        .await
+        .unwrap();
+    sqlx::query(UNVERSIONED_SCHEMA)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(schema_version(&pool).await.unwrap(), 0);
+    pool.close().await;
+
+    let db = Some(OboeteDb::open(&path).await.expect("migrated database"));
+
+    let pool = SqlitePool::connect(path.to_str().unwrap()).await.unwrap();
+    assert_eq!(schema_version(&pool).await.unwrap(), LATEST_VERSION);
+    let columns: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pragma_table_info('flashcards')")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    for column in [
+        "ease_factor",
+        "due_date",
+        "leitner_box",
+        "created_at",
+        "good_streak",
+    ] {
+        assert_eq!(
+            columns
+                .iter()
+                .filter(|name| name.as_str() == column)
+                .count(),
+            1,
+            "{}",
+            column
+        );
+    }
+    pool.close().await;
+
+    //The rows are kept with the values of the columns that were already there
+    let flashcards = get_folder_flashcards(db, 1).await.unwrap();
+    assert_eq!(flashcards.len(), 1);
+    assert_eq!(flashcards[0].front, "水");
+    assert_eq!(flashcards[0].ease_factor, 2.1);
+    assert_eq!(flashcards[0].interval, 6);
+    assert_eq!(flashcards[0].repetitions, 2);
+    assert_eq!(flashcards[0].due_date, 1_700_000_000);
+    assert_eq!(flashcards[0].leitner_box, 1);
+}
+
+#[tokio::test]
+async fn newer_database_is_refused() {
+    let path = temp_db_path("newer");
+    OboeteDb::open(&path).await.expect("database");
+
+    let pool = SqlitePool::connect(path.to_str().unwrap()).await.unwrap();
+    sqlx::query(&format!("PRAGMA user_version = {}", LATEST_VERSION + 1))
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    match OboeteDb::open(&path).await {
+        Err(OboeteError::NewerSchema(version)) => assert_eq!(version, LATEST_VERSION + 1),
+        other => panic!("expected a newer schema error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn in_memory_database_is_usable() {
+    let db = common::database().await;
+    let (_, folder_id) = common::studyset_with_folder(&db, "Memory").await;
+    common::add_flashcard(&db, folder_id, "front", "back").await;
+
+    let flashcards = oboete::core::database::get_folder_flashcards(db, folder_id)
+        .await
+        .unwrap();
+    assert_eq!(flashcards.len(), 1);
+}
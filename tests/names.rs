@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::{
+    core::database::{
+        create_folder, create_studyset, move_flashcard, rename_folder, rename_studyset,
+    },
+    utils::OboeteError,
+};
+
+use common::{add_flashcard, database, studyset_with_folder};
+
+#[tokio::test]
+async fn studyset_names_are_trimmed_and_unique() {
+    let db = database().await;
+    let studyset = create_studyset(db.clone(), String::from("  Kanji  "))
+        .await
+        .unwrap();
+    assert_eq!(studyset.name, "Kanji");
+
+    assert!(matches!(
+        create_studyset(db.clone(), String::from("   ")).await,
+        Err(OboeteError::EmptyName)
+    ));
+    assert!(matches!(
+        create_studyset(db.clone(), String::from("Kanji ")).await,
+        Err(OboeteError::NameTaken(name)) if name == "Kanji"
+    ));
+
+    let other = create_studyset(db.clone(), String::from("Kana"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        rename_studyset(db.clone(), other.id.unwrap(), String::from("Kanji")).await,
+        Err(OboeteError::NameTaken(_))
+    ));
+    //Keeping its own name isn't taking it
+    assert_eq!(
+        rename_studyset(db, other.id.unwrap(), String::from("Kana"))
+            .await
+            .unwrap(),
+        "Kana"
+    );
+}
+
+#[tokio::test]
+async fn folder_names_are_unique_in_their_studyset() {
+    let db = database().await;
+    let (studyset_id, _) = studyset_with_folder(&db, "First").await;
+    let (other_studyset_id, _) = studyset_with_folder(&db, "Second").await;
+
+    assert!(matches!(
+        create_folder(db.clone(), String::from("Folder"), studyset_id).await,
+        Err(OboeteError::NameTaken(_))
+    ));
+    assert!(matches!(
+        create_folder(db.clone(), String::new(), studyset_id).await,
+        Err(OboeteError::EmptyName)
+    ));
+    assert!(create_folder(db, String::from("Other"), other_studyset_id)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn renamed_folders_keep_their_names_unique() {
+    let db = database().await;
+    let (studyset_id, folder_id) = studyset_with_folder(&db, "Rename").await;
+    let other_id = create_folder(db.clone(), String::from("Other"), studyset_id)
+        .await
+        .unwrap() as i32;
+    let (_, foreign_id) = studyset_with_folder(&db, "Foreign").await;
+
+    assert!(matches!(
+        rename_folder(db.clone(), other_id, String::from(" Folder ")).await,
+        Err(OboeteError::NameTaken(name)) if name == "Folder"
+    ));
+    //Another studyset can have a folder with the same name, and keeping its own name is fine
+    assert!(rename_folder(db.clone(), foreign_id, String::from("Other"))
+        .await
+        .is_ok());
+    assert!(rename_folder(db, folder_id, String::from("Folder"))
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn moving_to_a_missing_folder_fails() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Moves").await;
+    let flashcard_id = add_flashcard(&db, folder_id, "front", "back").await;
+
+    assert!(matches!(
+        move_flashcard(db.clone(), flashcard_id, folder_id + 100).await,
+        Err(OboeteError::FolderNotFound)
+    ));
+    assert!(matches!(
+        move_flashcard(db, flashcard_id + 100, folder_id).await,
+        Err(OboeteError::FlashcardNotFound)
+    ));
+}
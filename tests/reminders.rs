@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod common;
+
+use oboete::core::{
+    database::{get_local_time, log_study_session},
+    reminders::{check_study_reminder, ReminderSchedule},
+};
+
+use common::{add_flashcard, database, studyset_with_folder};
+
+/// Every day from midnight and without quiet hours, so it's always time for the reminder
+fn always() -> ReminderSchedule {
+    ReminderSchedule {
+        hour: 0,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn reminder_needs_due_flashcards_that_werent_studied_today() {
+    let db = database().await;
+    let (_, folder_id) = studyset_with_folder(&db, "Japanese").await;
+
+    //Nothing to study
+    assert_eq!(
+        check_study_reminder(db.clone(), always(), 0, None)
+            .await
+            .unwrap(),
+        None
+    );
+
+    add_flashcard(&db, folder_id, "犬", "dog").await;
+    let reminder = check_study_reminder(db.clone(), always(), 0, None)
+        .await
+        .unwrap()
+        .expect("reminder");
+    assert_eq!(reminder.due_count, 1);
+    assert_eq!(reminder.day, get_local_time(db.clone()).await.unwrap().day);
+
+    //Only one reminder a day
+    assert_eq!(
+        check_study_reminder(db.clone(), always(), reminder.day, None)
+            .await
+            .unwrap(),
+        None
+    );
+
+    log_study_session(db.clone()).await.unwrap();
+    assert_eq!(
+        check_study_reminder(db, always(), 0, None).await.unwrap(),
+        None
+    );
+}